@@ -3,22 +3,24 @@
 use anyhow::Result;
 use base64::engine::general_purpose::STANDARD as B64;
 use base64::Engine as _;
-use chrono::{DateTime, Local, NaiveTime, TimeZone, Utc};
-use parking_lot::Mutex;
+use chrono::{DateTime, Datelike, Local, NaiveTime, TimeZone, Timelike, Utc, Weekday};
+use notify::Watcher as _;
+use once_cell::sync::Lazy;
+use parking_lot::{Mutex, RwLock};
 use reqwest::header::{ACCEPT, CONNECTION, CONTENT_LENGTH, CONTENT_TYPE, USER_AGENT};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{HashMap, HashSet, VecDeque},
     fs::File,
-    io::{self},
-    path::{Path, PathBuf},
+    io::{self, Write},
+    path::{Component, Path, PathBuf},
     process::Command,
     sync::{
         atomic::{AtomicBool, AtomicUsize, Ordering},
         Arc,
     },
-    time::Duration,
+    time::{Duration, Instant},
 };
 use tauri::{Manager, State};
 use urlencoding::encode;
@@ -26,47 +28,357 @@ use urlencoding::encode;
 static SAVING: AtomicBool = AtomicBool::new(false);
 static RESTART_GEN: AtomicUsize = AtomicUsize::new(0);
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 struct ApiConfig {
     base_url: String,
     password: Option<String>,
+    #[serde(default)]
+    admin_username: Option<String>,   // basic auth user; None = DEFAULT_ADMIN_USERNAME
+    #[serde(default)]
+    fallback_password: Option<String>, // tried when the primary gets a 401; promoted to primary if it works
     // new:
     start_cmd: Option<String>,        // e.g. C:\palworldserver\start-palworld.bat
+    #[serde(default)]
+    start_cmd_cwd: Option<String>,    // working directory for start_cmd; None = the command's own folder
     backup_dir: Option<String>,       // backup source folder
     backup_dest_dir: Option<String>,  // backup destination folder
     restart_times: Vec<String>,       // ["03:00","09:00","15:00","21:00"] local time
+    #[serde(default)]
+    restart_schedule: Option<HashMap<String, Vec<String>>>, // {"Sat": ["06:00"], ...}; replaces restart_times when set
     discord_webhook: Option<String>,  // Discord webhook URL for important events
+    #[serde(default)]
+    allow_custom_webhook: bool,       // accept a non-Discord (proxy/relay) webhook host
     allow_actions: bool,              // read-only when false
+    #[serde(default)]
+    profiles: Vec<ServerProfile>,     // additional servers for the multi-server overview
+    #[serde(default)]
+    backup_save_first: bool,          // issue /save and wait for it before zipping
+    #[serde(default)]
+    backup_before_restart: bool,      // zip a pre-restart-<ts>.zip rollback point before each restart
+    #[serde(default)]
+    backup_on_crash: bool,            // zip a crash-<ts>.zip when the server goes down unexpectedly
+    #[serde(default = "default_true")]
+    autosave_enabled: bool,           // autosave loop on/off, independent of restarts
+    #[serde(default = "default_true")]
+    backup_enabled: bool,             // backup loop on/off, independent of restarts
+    // extra response keys per logical field, tried before the built-in ones:
+    // server_name, map, max_players, uptime, players_online, version,
+    // player_id, player_name, level, ping, connected_seconds, game_day, game_time, server_fps
+    field_mappings: Option<FieldMappings>,
+    auth_required: Option<bool>,      // Some(false) never sends auth headers
+    quiet_hours_start: Option<String>, // "HH:MM" local; info-level Discord posts are muted
+    quiet_hours_end: Option<String>,   // "HH:MM" local; may be earlier than start (crosses midnight)
+    daily_summary_time: Option<String>, // "HH:MM" local time to post the daily Discord summary
+    initial_delay_secs: Option<u64>,  // delay before the first autosave/backup after launch; None = full interval
+    ws_listen: Option<String>,        // "127.0.0.1:8765" serves live events over WebSocket; None = off
+    query_port: Option<u16>,          // Steam query port on the REST host; None = DEFAULT_QUERY_PORT
+    announce_failure_threshold: Option<u32>, // alert after N failed broadcasts in a row; None/0 = off
+    backup_name_template: Option<String>, // e.g. "{server}-{date}-{time}"; None = DEFAULT_BACKUP_NAME_TEMPLATE
+    backup_interval_minutes: Option<u64>, // minutes between automatic backups; None = DEFAULT_BACKUP_INTERVAL_MINS
+    backup_retention_days: Option<u64>, // backups older than this are pruned; None = DEFAULT_BACKUP_RETENTION_DAYS
+    backup_max_count: Option<usize>,  // keep only the newest N backups after the age prune; None = no cap
+    shell_override: Option<String>,   // full start_cmd invocation, e.g. "pwsh -NoProfile -File {cmd}"
+    storage_limit_mb: Option<u64>,    // cap for the app data folder; None = DEFAULT_STORAGE_LIMIT_MB
+    capacity_alert_percent: Option<u8>, // Discord alert when online/max reaches this; None = off
+    restart_complete_message: Option<String>, // broadcast once the API is back; None = default, "" = off
+    max_concurrent_requests: Option<usize>, // REST requests in flight at once; None = DEFAULT_MAX_CONCURRENT_REQUESTS
+    save_schedule: Option<Vec<String>>, // "HH:MM" local times for standalone saves (no restart)
+    motd_schedule: Option<Vec<(String, String)>>, // ("HH:MM", text): server description applied from that time on
+    settings_schedule: Option<Vec<SettingsEvent>>, // recurring settings changes, reverted when the event ends
+    server_process_name: Option<String>, // e.g. "PalServer-Win64-Shipping-Cmd.exe", for server_resource_usage
+    save_settle_secs: Option<u64>,    // pause between the restart save and shutdown; None = DEFAULT_SAVE_SETTLE_SECS
+    request_timeout_secs: Option<u64>, // per-request HTTP timeout; None = DEFAULT_REQUEST_TIMEOUT_SECS
+    // HTTP/1.1 only. None = only the save/restart requests (some servers reject their HTTP/2
+    // upgrade); false lets those negotiate too, true forces it on polling as well.
+    force_http1: Option<bool>,
+    // Close connections after each request. None = only the save/restart requests; true costs a
+    // TCP handshake per poll, false reuses connections everywhere for faster polling.
+    disable_keepalive: Option<bool>,
+    ntp_check: Option<bool>,          // Some(false) never queries NTP_SERVER; clock checks use the game server's Date header
+    #[serde(default)]
+    restarts_suspended_until: Option<DateTime<Utc>>, // set by suspend_scheduler_until; ignored once past
 }
+type FieldMappings = HashMap<String, Vec<String>>;
+fn default_true() -> bool { true }
 impl Default for ApiConfig {
     fn default() -> Self {
         Self {
             base_url: String::new(),
             password: None,
+            admin_username: None,
+            fallback_password: None,
             start_cmd: None,
+            start_cmd_cwd: None,
             backup_dir: None,
             backup_dest_dir: None,
             restart_times: vec![], // empty => no scheduled restarts
+            restart_schedule: None,
             discord_webhook: None,
+            allow_custom_webhook: false,
             allow_actions: true,
+            profiles: vec![],
+            backup_save_first: false,
+            backup_before_restart: false,
+            backup_on_crash: false,
+            autosave_enabled: true,
+            backup_enabled: true,
+            field_mappings: None,
+            auth_required: None,
+            quiet_hours_start: None,
+            quiet_hours_end: None,
+            daily_summary_time: None,
+            initial_delay_secs: None,
+            ws_listen: None,
+            query_port: None,
+            announce_failure_threshold: None,
+            backup_name_template: None,
+            backup_interval_minutes: None,
+            backup_retention_days: None,
+            backup_max_count: None,
+            shell_override: None,
+            storage_limit_mb: None,
+            capacity_alert_percent: None,
+            restart_complete_message: None,
+            max_concurrent_requests: None,
+            save_schedule: None,
+            motd_schedule: None,
+            settings_schedule: None,
+            save_settle_secs: None,
+            request_timeout_secs: None,
+            server_process_name: None,
+            force_http1: None,
+            disable_keepalive: None,
+            ntp_check: None,
+            restarts_suspended_until: None,
         }
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct ServerProfile {
+    name: String,
+    base_url: String,
+    password: Option<String>,
+}
+
+// Ping samples kept per player (one per poll).
+const PING_HISTORY_LEN: usize = 120;
+// consecutive polls with no ping while the session keeps running before a player is flagged as a ghost
+const STALE_POLL_THRESHOLD: u32 = 10;
+
+const TRACKER_FILE: &str = "tracker.json";
+// finished sessions kept in tracker.json; past this the oldest SESSION_ARCHIVE_BATCH move to a
+// gzipped "sessions-<timestamp>.jsonl.gz" segment
+const SESSION_HISTORY_LEN: usize = 5000;
+const SESSION_ARCHIVE_BATCH: usize = 1000;
+
+// Persisted part of the tracker (tracker.json).
+#[derive(Default, Serialize, Deserialize)]
+struct TrackerFile {
+    #[serde(default)]
+    first_seen: HashMap<String, DateTime<Utc>>,
+    #[serde(default)]
+    last_seen: HashMap<String, DateTime<Utc>>,
+    #[serde(default)]
+    names: HashMap<String, String>,
+    #[serde(default)]
+    sessions: VecDeque<Session>,
+}
+
+// One finished visit: first to last poll the player was listed in.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct Session {
+    id: String,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+}
+
 #[derive(Default)]
 struct PlayerTracker {
     seen: HashMap<String, DateTime<Utc>>,
+    // (unix seconds, ping) per player id, oldest first
+    pings: HashMap<String, VecDeque<(i64, u32)>>,
+    // first time each id was ever seen; never overwritten, persisted
+    first_seen: HashMap<String, DateTime<Utc>>,
+    // (consecutive polls without a ping, last reported connected_seconds) per online id
+    silent_polls: HashMap<String, (u32, Option<i64>)>,
+    // last poll each id was listed in, and the name it last used; persisted for search_players
+    last_seen: HashMap<String, DateTime<Utc>>,
+    names: HashMap<String, String>,
+    // start of the current visit per online id, and finished visits (persisted)
+    session_start: HashMap<String, DateTime<Utc>>,
+    sessions: VecDeque<Session>,
+    // persisted fields changed since the last flush
+    dirty: bool,
 }
 impl PlayerTracker {
-    fn update_with(&mut self, players: &[Player]) {
+    fn load() -> Self {
+        let file: TrackerFile = load_data(TRACKER_FILE).unwrap_or_default();
+        Self {
+            first_seen: file.first_seen,
+            last_seen: file.last_seen,
+            names: file.names,
+            sessions: file.sessions,
+            ..Self::default()
+        }
+    }
+    // Written on joins, leaves and renames rather than every poll.
+    fn flush(&mut self) {
+        if !self.dirty {
+            return;
+        }
+        self.dirty = false;
+        save_data(
+            TRACKER_FILE,
+            &TrackerFile {
+                first_seen: self.first_seen.clone(),
+                last_seen: self.last_seen.clone(),
+                names: self.names.clone(),
+                sessions: self.sessions.clone(),
+            },
+        );
+    }
+    // Returns ids seen for the very first time.
+    fn update_with(&mut self, players: &[Player]) -> Vec<String> {
         let now = Utc::now();
+        // with no history at all (first launch) nobody can be told apart as new
+        let bootstrapping = self.first_seen.is_empty();
+        let mut brand_new = Vec::new();
         for p in players {
             self.seen.entry(p.id.clone()).or_insert(now);
+            self.session_start.entry(p.id.clone()).or_insert(now);
+            if !self.first_seen.contains_key(&p.id) {
+                self.first_seen.insert(p.id.clone(), now);
+                brand_new.push(p.id.clone());
+            }
+            self.last_seen.insert(p.id.clone(), now);
+            if self.names.get(&p.id) != Some(&p.name) {
+                self.names.insert(p.id.clone(), p.name.clone());
+                self.dirty = true;
+            }
+            let silent = self.silent_polls.entry(p.id.clone()).or_insert((0, None));
+            // a reported session time that stopped moving means the server isn't counting it either
+            let growing = match (silent.1, p.connected_seconds) {
+                (Some(prev), Some(cur)) => cur > prev,
+                _ => true,
+            };
+            if p.ping.unwrap_or(0) == 0 && growing {
+                silent.0 += 1;
+            } else {
+                silent.0 = 0;
+            }
+            silent.1 = p.connected_seconds;
+            if let Some(ping) = p.ping {
+                let hist = self.pings.entry(p.id.clone()).or_default();
+                hist.push_back((now.timestamp(), ping));
+                while hist.len() > PING_HISTORY_LEN {
+                    hist.pop_front();
+                }
+            }
+        }
+        let online: HashSet<&str> = players.iter().map(|p| p.id.as_str()).collect();
+        self.silent_polls.retain(|id, _| online.contains(id.as_str()));
+        // someone left: close their session at the last poll they were listed in
+        let left: Vec<String> = self.session_start.keys().filter(|id| !online.contains(id.as_str())).cloned().collect();
+        for id in left {
+            let start = self.session_start.remove(&id).unwrap_or(now);
+            let end = self.last_seen.get(&id).copied().unwrap_or(now);
+            self.sessions.push_back(Session { id, start, end });
+            if self.sessions.len() > SESSION_HISTORY_LEN {
+                let old: Vec<Session> = self.sessions.drain(..SESSION_ARCHIVE_BATCH).collect();
+                let _ = archive_records("sessions", &old);
+            }
+            self.dirty = true;
+        }
+        if !brand_new.is_empty() {
+            self.dirty = true;
+        }
+        if bootstrapping {
+            brand_new.clear();
+        }
+        brand_new
+    }
+    // Session length and player churn over the last `window`, compared with the window before it.
+    fn engagement(&self, now: DateTime<Utc>, window: chrono::Duration) -> EngagementStats {
+        let cutoff = now - window;
+        let prev_cutoff = cutoff - window;
+        let recent: Vec<&Session> = self.sessions.iter().filter(|s| s.end >= cutoff).collect();
+        let mut active: HashSet<&str> = recent.iter().map(|s| s.id.as_str()).collect();
+        active.extend(self.session_start.keys().map(String::as_str));
+        let previous: HashSet<&str> = self
+            .sessions
+            .iter()
+            .filter(|s| s.end >= prev_cutoff && s.start < cutoff)
+            .map(|s| s.id.as_str())
+            .collect();
+        let new_players = active
+            .iter()
+            .filter(|id| self.first_seen.get(**id).is_some_and(|t| *t >= cutoff))
+            .count();
+        let churned_players = previous.difference(&active).count();
+        let total_secs: i64 = recent.iter().map(|s| (s.end - s.start).num_seconds()).sum();
+        EngagementStats {
+            window_days: window.num_days(),
+            sessions: recent.len(),
+            avg_session_secs: (!recent.is_empty()).then(|| total_secs / recent.len() as i64),
+            active_players: active.len(),
+            new_players,
+            returning_players: active.len() - new_players,
+            churned_players,
+            churn_rate: (!previous.is_empty()).then(|| churned_players as f64 / previous.len() as f64),
         }
     }
     fn connected_for(&self, id: &str) -> Option<i64> {
         self.seen.get(id).map(|t| (Utc::now() - *t).num_seconds())
     }
+    // Ids that have gone STALE_POLL_THRESHOLD polls without a ping, with their poll count.
+    fn stale(&self) -> Vec<(String, u32)> {
+        self.silent_polls
+            .iter()
+            .filter(|(_, (n, _))| *n >= STALE_POLL_THRESHOLD)
+            .map(|(id, (n, _))| (id.clone(), *n))
+            .collect()
+    }
+}
+
+// Heartbeat of one background loop, for task_health.
+#[derive(Clone, Default)]
+struct TaskBeat {
+    running_gen: Option<usize>, // generation of the live task, cleared when its loop exits
+    started: Option<DateTime<Local>>,
+    last_run: Option<DateTime<Local>>,
+    max_gap_secs: u64, // longer than this without a run while alive counts as stale
+    failed: Option<String>, // why the last task of this name gave up, shown by task_health
+}
+type TaskBeats = Arc<Mutex<HashMap<&'static str, TaskBeat>>>;
+
+fn task_started(beats: &TaskBeats, name: &'static str, id: usize, max_gap_secs: u64) {
+    beats.lock().insert(name, TaskBeat { running_gen: Some(id), started: Some(Local::now()), last_run: None, max_gap_secs, failed: None });
+}
+fn task_ran(beats: &TaskBeats, name: &'static str) {
+    if let Some(b) = beats.lock().get_mut(name) {
+        b.last_run = Some(Local::now());
+    }
+}
+fn task_ended(beats: &TaskBeats, name: &'static str, id: usize) {
+    if let Some(b) = beats.lock().get_mut(name).filter(|b| b.running_gen == Some(id)) {
+        b.running_gen = None;
+    }
+}
+// A task that can't do its job stops and says why instead of failing on every run.
+fn task_failed(beats: &TaskBeats, name: &'static str, id: usize, why: String) {
+    if let Some(b) = beats.lock().get_mut(name).filter(|b| b.running_gen == Some(id)) {
+        b.running_gen = None;
+        b.failed = Some(why);
+    }
+}
+
+// Written by the scheduler task, read by scheduler_debug.
+#[derive(Default)]
+struct SchedulerStatus {
+    running_gen: Option<usize>, // generation of the live scheduler task, if any
+    last_fired: Option<DateTime<Local>>,
+    clock_checked: Option<Instant>, // last clock skew check, at most one per CLOCK_CHECK_INTERVAL_SECS
 }
 
 #[derive(Default)]
@@ -75,10 +387,59 @@ struct AppState {
     tracker: Mutex<PlayerTracker>,
     // scheduler generation: bump to cancel previous task
     sched: Arc<AtomicUsize>,
+    sched_status: Arc<Mutex<SchedulerStatus>>,
     last_players: Mutex<HashSet<String>>,
     last_names: Mutex<HashMap<String, String>>,
+    // last version reported by /info, to spot server updates between polls
+    last_version: Mutex<Option<String>>,
+    // status badge contents from the last /info poll, also served by the live events listener
+    badge: Arc<Mutex<BadgeStatus>>,
+    // last world/map reported by /info, to catch a restart that loaded a different world
+    last_map: Mutex<Option<String>>,
+    // set while at/over the capacity threshold so the alert fires once per crossing
+    near_capacity: AtomicBool,
+    // expected restart already warned about by restart_health, so repeated checks post one embed per miss
+    restart_health_warned: Mutex<Option<DateTime<Local>>>,
     autosave_gen: Arc<AtomicUsize>,
+    save_sched_gen: Arc<AtomicUsize>,
+    motd_gen: Arc<AtomicUsize>,
+    settings_event_gen: Arc<AtomicUsize>,
+    // prior values of settings changed by running events, by event name (settings_events.json)
+    settings_events: Arc<Mutex<HashMap<String, HashMap<String, Value>>>>,
     backup_gen: Arc<AtomicUsize>,
+    summary_gen: Arc<AtomicUsize>,
+    countdown_gen: Arc<AtomicUsize>,
+    // bumped per restart so only the latest restart's back-online greeting can fire
+    greet_gen: Arc<AtomicUsize>,
+    ws_gen: Arc<AtomicUsize>,
+    recurring: Arc<Mutex<HashMap<String, RecurringAnnounce>>>,
+    recurring_gen: Arc<AtomicUsize>,
+    // players to alert on when they join, by player id (watchlist.json)
+    watchlist: Mutex<HashMap<String, WatchEntry>>,
+    // bans issued through this app, by player id (bans.json)
+    bans: Arc<Mutex<HashMap<String, BanRecord>>>,
+    ban_expiry_gen: Arc<AtomicUsize>,
+    task_beats: TaskBeats,
+    // consecutive failed backups, for the escalation alert
+    backup_streak: Arc<Mutex<BackupStreak>>,
+    // PID of the last process launched via start_cmd (cmd.exe for .bat launchers)
+    server_pid: Arc<Mutex<Option<u32>>>,
+    // index into SHUTDOWN_SHAPES of the last body the server accepted
+    shutdown_shape: Arc<Mutex<Option<usize>>>,
+    // reachability, expected-down flag and crash detection for the primary server
+    watch: Arc<ServerWatch>,
+    // counters for the daily Discord summary; reset each time a summary is posted
+    daily: Arc<Mutex<DailyStats>>,
+    // REST request limit and recent failures, shared by commands and background tasks
+    rest: RestState,
+}
+
+#[derive(Default)]
+struct DailyStats {
+    peak_players: usize,
+    unique_players: HashSet<String>,
+    restarts: u32,
+    backups: u32,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -88,6 +449,7 @@ struct ServerInfo {
     players_online: usize,
     max_players: Option<usize>,
     uptime_seconds: Option<u64>,
+    version: Option<String>,
 }
 #[derive(Debug, Serialize, Deserialize, Clone)]
 struct Player {
@@ -96,10 +458,94 @@ struct Player {
     level: Option<u32>,
     ping: Option<u32>,
     connected_seconds: Option<i64>,
+    #[serde(default)]
+    platform: String, // "Steam" or "Other/Xbox", inferred from the id
 }
 
 /* ----------------------- helpers ----------------------- */
 
+const DEFAULT_REQUEST_TIMEOUT_SECS: u64 = 10;
+// (force_http1, disable_keepalive, timeout secs) from the config; None leaves each client at its
+// own default.
+type HttpPrefs = (Option<bool>, Option<bool>, u64);
+const DEFAULT_HTTP_PREFS: HttpPrefs = (None, None, DEFAULT_REQUEST_TIMEOUT_SECS);
+
+fn request_timeout_secs(cfg: &ApiConfig) -> u64 {
+    cfg.request_timeout_secs.filter(|s| *s > 0).unwrap_or(DEFAULT_REQUEST_TIMEOUT_SECS)
+}
+
+// Applies the HTTP prefs and rebuilds the shared client when they change.
+fn set_http_prefs(rest: &RestState, cfg: &ApiConfig) {
+    let prefs = (cfg.force_http1, cfg.disable_keepalive, request_timeout_secs(cfg));
+    let mut http = rest.http.write();
+    if http.0 != prefs {
+        *http = (prefs, build_http_client(prefs));
+    }
+}
+
+// Every client is built here. `save_path` clients (save, restart, shutdown) default to HTTP/1.1
+// with no pooled connections, which some servers need; the shared polling client defaults to
+// reqwest's negotiation and keepalive.
+// Client-level timeout is request_timeout_secs; /save requests override it with save_timeout().
+fn client_builder(prefs: HttpPrefs, save_path: bool) -> reqwest::ClientBuilder {
+    let (http1, no_keepalive, timeout) = prefs;
+    let mut b = reqwest::Client::builder().timeout(Duration::from_secs(timeout));
+    if http1.unwrap_or(save_path) {
+        b = b.http1_only();
+    }
+    if no_keepalive.unwrap_or(save_path) {
+        b = b.pool_idle_timeout(Duration::from_secs(0));
+    }
+    b
+}
+
+fn build_http_client(prefs: HttpPrefs) -> reqwest::Client {
+    client_builder(prefs, false).build().unwrap_or_default()
+}
+
+// Large worlds can take well over request_timeout_secs to save.
+const SAVE_REQUEST_TIMEOUT_SECS: u64 = 120;
+
+// A non-2xx reply, kept typed so callers can match on the status instead of the message.
+#[derive(Debug, thiserror::Error)]
+#[error("{method} {url} -> {status}")]
+struct HttpStatusError {
+    method: &'static str,
+    url: String,
+    status: reqwest::StatusCode,
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("{url} timed out after {secs}s")]
+struct TimedOut {
+    url: String,
+    secs: u64,
+}
+
+fn error_status(e: &anyhow::Error) -> Option<reqwest::StatusCode> {
+    e.downcast_ref::<HttpStatusError>().map(|s| s.status).or_else(|| e.downcast_ref::<reqwest::Error>()?.status())
+}
+
+// Timeouts say how long was waited, so it's clear request_timeout_secs is the knob to turn.
+fn request_error(e: reqwest::Error, timeout_secs: u64) -> anyhow::Error {
+    if e.is_timeout() {
+        let url = e.url().map(|u| u.to_string()).unwrap_or_else(|| "request".into());
+        TimedOut { url, secs: timeout_secs }.into()
+    } else {
+        e.into()
+    }
+}
+
+
+fn normalize_base_url(raw: &str) -> String {
+    let b = raw.trim();
+    if b.is_empty() || b.starts_with("http://") || b.starts_with("https://") {
+        b.to_string()
+    } else {
+        format!("http://{}", b)
+    }
+}
+
 fn v1_base(base: &str) -> String {
     let b = base.trim_end_matches('/');
     if b.ends_with("/v1/api") {
@@ -110,1055 +556,6747 @@ fn v1_base(base: &str) -> String {
 }
 
 /* ----------------------- config persistence ----------------------- */
-fn config_path() -> Option<std::path::PathBuf> {
+fn app_data_dir() -> Option<std::path::PathBuf> {
     let base = dirs::config_dir()?;
     let dir = base.join("palworld-rest-api-client");
     let _ = std::fs::create_dir_all(&dir);
-    Some(dir.join("config.json"))
+    Some(dir)
+}
+fn app_data_path(file: &str) -> Option<std::path::PathBuf> {
+    Some(app_data_dir()?.join(file))
+}
+fn config_path() -> Option<std::path::PathBuf> {
+    app_data_path("config.json")
 }
 fn load_saved_config() -> Option<ApiConfig> {
     let path = config_path()?;
     let data = std::fs::read(path).ok()?;
-    serde_json::from_slice::<ApiConfig>(&data).ok()
+    parse_config(&data).ok()
+}
+
+// config.json contents with the stored passwords decrypted. One that no longer decrypts (the
+// key file was lost) loads as unset, so the user is asked for it again. When the key can't be
+// read at all the sealed text is kept as is, so a save in the meantime writes it back unchanged.
+fn parse_config(data: &[u8]) -> serde_json::Result<ApiConfig> {
+    let mut v: Value = serde_json::from_slice(data)?;
+    if let Some(key) = secret_key() {
+        open_config_secrets(&mut v, &key);
+    }
+    serde_json::from_value(v)
+}
+
+// Every password slot in the config JSON: SECRET_CONFIG_KEYS plus each profile's password.
+fn for_each_secret(v: &mut Value, mut f: impl FnMut(&mut Value)) {
+    let Some(obj) = v.as_object_mut() else { return };
+    for field in SECRET_CONFIG_KEYS {
+        if let Some(slot) = obj.get_mut(*field) {
+            f(slot);
+        }
+    }
+    if let Some(Value::Array(profiles)) = obj.get_mut("profiles") {
+        for slot in profiles.iter_mut().filter_map(|p| p.get_mut("password")) {
+            f(slot);
+        }
+    }
 }
+
+fn open_config_secrets(v: &mut Value, key: &ring::aead::LessSafeKey) {
+    for_each_secret(v, |slot| {
+        if let Some(stored) = slot.as_str().filter(|s| s.starts_with(SECRET_PREFIX)) {
+            *slot = open_secret(key, stored).map(Value::from).unwrap_or(Value::Null);
+        }
+    });
+}
+
+fn seal_config_secrets(v: &mut Value, key: &ring::aead::LessSafeKey) {
+    for_each_secret(v, |slot| {
+        // already sealed: kept from a load that couldn't read the key
+        let plain = slot.as_str().filter(|s| !s.is_empty() && !s.starts_with(SECRET_PREFIX));
+        if let Some(sealed) = plain.and_then(|s| seal_secret(key, s)) {
+            *slot = sealed.into();
+        }
+    });
+}
+
+// Stamped into config.json on every save (not part of ApiConfig, so the watcher's equality check
+// ignores it). Files without the key predate versioning and report 0.
+// 2: passwords are stored sealed (see SECRET_PREFIX); older plaintext files still load.
+const CONFIG_VERSION: u64 = 2;
+// Keys older versions wrote that are no longer read; none yet.
+const DEPRECATED_CONFIG_KEYS: &[&str] = &[];
+
 fn save_config(cfg: &ApiConfig) {
     if let Some(path) = config_path() {
-        if let Ok(data) = serde_json::to_vec_pretty(cfg) {
+        let Ok(mut v) = serde_json::to_value(cfg) else { return };
+        if let Some(obj) = v.as_object_mut() {
+            obj.insert("config_version".into(), CONFIG_VERSION.into());
+        }
+        // no key (unwritable data folder) leaves them in plaintext rather than losing them
+        if let Some(key) = secret_key() {
+            seal_config_secrets(&mut v, &key);
+        }
+        if let Ok(data) = serde_json::to_vec_pretty(&v) {
             let _ = std::fs::write(path, data);
         }
     }
 }
 
-/* ----------------------- discord embed helper ----------------------- */
-const COLOR_SUCCESS: u32 = 0x22C55E; // green
-const COLOR_ERROR: u32 = 0xEF4444;   // red
-const COLOR_INFO: u32 = 0x3B82F6;    // blue
+/* ----------------------- stored secrets ----------------------- */
+// Passwords (including the profiles') are written to config.json sealed with AES-256-GCM under a
+// random key kept in secret.key next to it, so the file can be shared or backed up without the
+// plaintext.
+const SECRET_KEY_FILE: &str = "secret.key";
+const SECRET_PREFIX: &str = "enc:v1:";
+const SECRET_CONFIG_KEYS: &[&str] = &["password", "fallback_password"];
 
-async fn discord_embed(hook: &str, desc: &str, color: u32) {
-    let _ = reqwest::Client::new()
-        .post(hook)
-        .json(&serde_json::json!({
-            "embeds": [{ "description": desc, "color": color }]
-        }))
-        .send()
-        .await;
+// The key from secret.key, created on first use. Any other read failure (permissions, a locked or
+// damaged file) returns None rather than replacing the key, which would lose every stored password.
+fn secret_key() -> Option<ring::aead::LessSafeKey> {
+    let path = app_data_path(SECRET_KEY_FILE)?;
+    let bytes = match std::fs::read(&path) {
+        Ok(b) => b,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => create_secret_key(&path)?,
+        Err(_) => return None,
+    };
+    if bytes.len() != 32 {
+        return None;
+    }
+    let unbound = ring::aead::UnboundKey::new(&ring::aead::AES_256_GCM, &bytes).ok()?;
+    Some(ring::aead::LessSafeKey::new(unbound))
 }
 
-/* ----------------------- zip helpers (backups) ----------------------- */
-fn zip_directory(src: &Path, dest_zip: &Path) -> anyhow::Result<()> {
-    if !src.exists() {
-        anyhow::bail!("backup source not found: {}", src.display());
+// Write a new random key, readable by the owner only. create_new never replaces a key file; if
+// another caller created one first, that one is used.
+fn create_secret_key(path: &Path) -> Option<Vec<u8>> {
+    use ring::rand::SecureRandom as _;
+    let mut b = vec![0u8; 32];
+    ring::rand::SystemRandom::new().fill(&mut b).ok()?;
+    let mut opts = std::fs::OpenOptions::new();
+    opts.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt as _;
+        opts.mode(0o600);
     }
-    let file = File::create(dest_zip)?;
-    let mut zip = zip::ZipWriter::new(file);
-    let options = zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
-    let src_abs = src.canonicalize().unwrap_or_else(|_| src.to_path_buf());
-    let backups_dir = src_abs.join("_backups");
-
-    for entry in walkdir::WalkDir::new(&src_abs).into_iter().filter_map(Result::ok) {
-        let path = entry.path();
-        let name_rel = path.strip_prefix(&src_abs).unwrap_or(path);
-        if name_rel.as_os_str().is_empty() { continue; }
-        // skip our backups output folder
-        if path.starts_with(&backups_dir) { continue; }
-        if path.is_dir() {
-            let name = format!("{}/", name_rel.to_string_lossy().replace('\\', "/"));
-            let _ = zip.add_directory(name, options);
-        } else {
-            let name = name_rel.to_string_lossy().replace('\\', "/");
-            if let Ok(mut f) = File::open(path) {
-                let _ = zip.start_file(name, options);
-                let _ = io::copy(&mut f, &mut zip);
-            }
+    match opts.open(path) {
+        Ok(mut f) => {
+            f.write_all(&b).ok()?;
+            f.sync_all().ok()?;
+            Some(b)
         }
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => std::fs::read(path).ok(),
+        Err(_) => None,
     }
-    zip.finish()?;
-    Ok(())
 }
 
-fn prune_old_backups(dir: &Path, days: u64) -> anyhow::Result<usize> {
-    let mut removed = 0usize;
-    if !dir.exists() { return Ok(0); }
-    let cutoff = std::time::SystemTime::now()
-        .checked_sub(Duration::from_secs(days.saturating_mul(86_400)))
-        .unwrap_or(std::time::SystemTime::UNIX_EPOCH);
-    for entry in std::fs::read_dir(dir)? {
-        let entry = entry?;
-        let path = entry.path();
-        if path.is_file() {
-            let name = path.file_name().and_then(|s| s.to_str()).unwrap_or("");
-            let is_backup_zip = name.starts_with("backup-") && name.ends_with(".zip");
-            if !is_backup_zip { continue; }
-            let modified = entry
-                .metadata()
-                .and_then(|m| m.modified())
-                .unwrap_or(std::time::SystemTime::UNIX_EPOCH);
-            if modified < cutoff {
-                let _ = std::fs::remove_file(&path);
-                removed += 1;
-            }
+fn seal_secret(key: &ring::aead::LessSafeKey, plain: &str) -> Option<String> {
+    use ring::rand::SecureRandom as _;
+    let mut nonce = [0u8; ring::aead::NONCE_LEN];
+    ring::rand::SystemRandom::new().fill(&mut nonce).ok()?;
+    let mut buf = plain.as_bytes().to_vec();
+    key.seal_in_place_append_tag(ring::aead::Nonce::assume_unique_for_key(nonce), ring::aead::Aad::empty(), &mut buf)
+        .ok()?;
+    let mut out = nonce.to_vec();
+    out.extend_from_slice(&buf);
+    Some(format!("{}{}", SECRET_PREFIX, B64.encode(out)))
+}
+
+fn open_secret(key: &ring::aead::LessSafeKey, stored: &str) -> Option<String> {
+    let raw = B64.decode(stored.strip_prefix(SECRET_PREFIX)?).ok()?;
+    if raw.len() < ring::aead::NONCE_LEN { return None; }
+    let (nonce, sealed) = raw.split_at(ring::aead::NONCE_LEN);
+    let nonce = ring::aead::Nonce::try_assume_unique_for_key(nonce).ok()?;
+    let mut buf = sealed.to_vec();
+    let plain = key.open_in_place(nonce, ring::aead::Aad::empty(), &mut buf).ok()?;
+    String::from_utf8(plain.to_vec()).ok()
+}
+
+#[derive(Debug, Serialize)]
+struct ConfigHealth {
+    path: Option<String>,
+    exists: bool,
+    parse_error: Option<String>,
+    version: u64,
+    current_version: u64,
+    unknown_keys: Vec<String>,
+    deprecated_keys: Vec<String>,
+    missing_keys: Vec<String>, // fields a re-save would add with their defaults
+    needs_migration: bool,
+}
+
+// Compare config.json's raw keys against the current schema.
+#[tauri::command]
+fn config_health() -> ConfigHealth {
+    let path = config_path();
+    let mut health = ConfigHealth {
+        path: path.as_ref().map(|p| p.to_string_lossy().to_string()),
+        exists: false,
+        parse_error: None,
+        version: 0,
+        current_version: CONFIG_VERSION,
+        unknown_keys: vec![],
+        deprecated_keys: vec![],
+        missing_keys: vec![],
+        needs_migration: false,
+    };
+    let Some(data) = path.and_then(|p| std::fs::read(p).ok()) else { return health };
+    health.exists = true;
+    let raw = match serde_json::from_slice::<Value>(&data) {
+        Ok(Value::Object(m)) => m,
+        Ok(_) => {
+            health.parse_error = Some("config.json is not a JSON object".into());
+            return health;
         }
+        Err(e) => {
+            health.parse_error = Some(e.to_string());
+            return health;
+        }
+    };
+    if let Err(e) = serde_json::from_value::<ApiConfig>(Value::Object(raw.clone())) {
+        health.parse_error = Some(e.to_string());
     }
-    Ok(removed)
+    health.version = raw.get("config_version").and_then(|v| v.as_u64()).unwrap_or(0);
+    let known: HashSet<String> = match serde_json::to_value(ApiConfig::default()) {
+        Ok(Value::Object(m)) => m.keys().cloned().collect(),
+        _ => HashSet::new(),
+    };
+    for key in raw.keys().filter(|k| *k != "config_version") {
+        if DEPRECATED_CONFIG_KEYS.contains(&key.as_str()) {
+            health.deprecated_keys.push(key.clone());
+        } else if !known.contains(key) {
+            health.unknown_keys.push(key.clone());
+        }
+    }
+    health.missing_keys = known.into_iter().filter(|k| !raw.contains_key(k)).collect();
+    health.missing_keys.sort();
+    health.needs_migration = health.version < CONFIG_VERSION
+        || !health.missing_keys.is_empty()
+        || !health.deprecated_keys.is_empty();
+    health
 }
 
-/* ----------------------- background tasks ----------------------- */
-fn spawn_autosave(autosave: Arc<AtomicUsize>, cfg: &ApiConfig) {
-    let my_id = autosave.fetch_add(1, Ordering::SeqCst) + 1;
-    let base = cfg.base_url.clone();
-    let pass = cfg.password.clone().unwrap_or_default();
-    let hook = cfg.discord_webhook.clone();
-    if base.trim().is_empty() { return; }
-    tauri::async_runtime::spawn(async move {
-        let client = match reqwest::Client::builder().http1_only().pool_idle_timeout(Duration::from_secs(0)).build() {
-            Ok(c) => c,
+// Rewrite config.json in the current schema (defaults filled in, deprecated/unknown keys
+// dropped). The previous file is kept as config.json.bak.
+#[tauri::command]
+fn migrate_config() -> Result<ConfigHealth, String> {
+    let path = config_path().ok_or("no config directory")?;
+    let data = std::fs::read(&path).map_err(|e| format!("read {}: {}", path.display(), e))?;
+    let cfg = parse_config(&data).map_err(|e| format!("config.json does not parse: {}", e))?;
+    std::fs::write(path.with_extension("json.bak"), &data).map_err(|e| format!("backup failed: {}", e))?;
+    save_config(&cfg);
+    Ok(config_health())
+}
+
+/* ----------------------- config file watcher ----------------------- */
+// Editors and deploy tools often write a file in several bursts; wait for them to settle.
+const CONFIG_RELOAD_DEBOUNCE_MS: u64 = 500;
+
+// Watch config.json for external edits and apply them like set_config would.
+fn spawn_config_watcher(app: tauri::AppHandle) {
+    let Some(path) = config_path() else { return };
+    let Some(dir) = path.parent().map(Path::to_path_buf) else { return };
+    std::thread::spawn(move || {
+        let (tx, rx) = std::sync::mpsc::channel();
+        let mut watcher = match notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+            let _ = tx.send(res);
+        }) {
+            Ok(w) => w,
             Err(_) => return,
         };
-        loop {
-            // 15 minutes
-            tokio::time::sleep(Duration::from_secs(15 * 60)).await;
-            if autosave.load(Ordering::SeqCst) != my_id { break; }
-            // Discord log start (info)
-            if let Some(h) = hook.clone() { discord_embed(&h, "Auto save started.", COLOR_INFO).await; }
-            // Save request
-            let _ = client
-                .post(format!("{}/save", v1_base(&base)))
-                .basic_auth("admin", Some(&pass))
-                .header(CONTENT_LENGTH, "0")
-                .header(CONNECTION, "close")
-                .header(ACCEPT, "*/*")
-                .header(USER_AGENT, "curl/8.13.0")
-                .send()
-                .await;
-            if let Some(h) = hook.clone() { discord_embed(&h, "Auto save completed.", COLOR_SUCCESS).await; }
+        // watch the folder: atomic "write temp + rename" saves replace the file itself
+        if watcher.watch(&dir, notify::RecursiveMode::NonRecursive).is_err() {
+            return;
+        }
+        while let Ok(res) = rx.recv() {
+            let Ok(ev) = res else { continue };
+            if !ev.paths.iter().any(|p| p.file_name() == path.file_name()) { continue; }
+            while rx.recv_timeout(Duration::from_millis(CONFIG_RELOAD_DEBOUNCE_MS)).is_ok() {}
+            reload_config_from_disk(&app);
         }
     });
 }
 
-fn spawn_backup(backup: Arc<AtomicUsize>, cfg: &ApiConfig) {
-    let my_id = backup.fetch_add(1, Ordering::SeqCst) + 1;
-    let src_opt = cfg.backup_dir.clone();
-    let dest_opt = cfg.backup_dest_dir.clone();
-    let hook = cfg.discord_webhook.clone();
-    if src_opt.is_none() { return; }
-    let src = PathBuf::from(src_opt.unwrap());
-    let dest_root = if let Some(d) = dest_opt { PathBuf::from(d) } else { src.join("_backups") };
-    tauri::async_runtime::spawn(async move {
-        loop {
-            // 30 minutes
-            tokio::time::sleep(Duration::from_secs(30 * 60)).await;
-            if backup.load(Ordering::SeqCst) != my_id { break; }
-            // Prepare output
-            let _ = std::fs::create_dir_all(&dest_root);
-            let ts = chrono::Local::now().format("%Y%m%d-%H%M%S");
-            let dest = dest_root.join(format!("backup-{}.zip", ts));
-            // Run zip
-            let result = zip_directory(&src, &dest);
-            if let Some(h) = hook.clone() {
-                match result {
-                    Ok(()) => {
-                        discord_embed(&h, &format!("Auto backup created: {}", dest.display()), COLOR_SUCCESS).await;
-                        match prune_old_backups(&dest_root, 3) {
-                            Ok(n) if n > 0 => discord_embed(&h, &format!("Pruned {} backup(s) older than 3 days.", n), COLOR_INFO).await,
-                            Ok(_) => {}
-                            Err(e) => discord_embed(&h, &format!("Prune old backups failed: {}", e), COLOR_ERROR).await,
-                        }
-                    }
-                    Err(e) => discord_embed(&h, &format!("Auto backup failed: {}", e), COLOR_ERROR).await,
-                }
-            } else {
-                let _ = prune_old_backups(&dest_root, 3);
-            }
-        }
-    });
+fn reload_config_from_disk(app: &tauri::AppHandle) {
+    // unreadable/half-written file: keep the current config
+    let Some(cfg) = load_saved_config() else { return };
+    let state = app.state::<AppState>();
+    {
+        let mut cur = state.config.lock();
+        // our own save_config writes land here too; nothing to do when unchanged
+        if *cur == cfg { return; }
+        *cur = cfg.clone();
+    }
+    apply_config(&state, &cfg);
+    let _ = app.emit_all("config-reloaded", cfg);
 }
 
-fn build_basic_header(password: &Option<String>) -> Option<String> {
-    password.as_ref().map(|pwd| {
-        let creds = format!("admin:{}", pwd);
-        format!("Basic {}", B64.encode(creds.as_bytes()))
-    })
+/* ----------------------- audit log ----------------------- */
+const AUDIT_FILE: &str = "audit.jsonl";
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct AuditEntry {
+    at: DateTime<Utc>,
+    action: String,
+    target: String,
+    ok: bool,
+    detail: Option<String>,
 }
 
-fn candidate_urls(base: &str, path: &str) -> Vec<String> {
-    let p = path.trim_start_matches('/');
-    let b = base.trim_end_matches('/');
-    let mut v = Vec::new();
-    v.push(format!("{}/{}", b, p)); // if base already has /v1/api
-    if !b.ends_with("/v1/api") {
-        v.push(format!("{}/v1/api/{}", b, p));
+// Append one line to the JSONL audit log of moderation/admin actions.
+fn audit(action: &str, target: &str, ok: bool, detail: Option<String>) {
+    let Some(path) = app_data_path(AUDIT_FILE) else { return };
+    let entry = AuditEntry {
+        at: Utc::now(),
+        action: action.to_string(),
+        target: target.to_string(),
+        ok,
+        detail,
+    };
+    if let Ok(line) = serde_json::to_string(&entry) {
+        if let Ok(mut f) = std::fs::OpenOptions::new().create(true).append(true).open(path) {
+            let _ = writeln!(f, "{}", line);
+        }
+    }
+    if let Ok(true) = rotate_log(AUDIT_FILE) {
+        enforce_saved_storage_limit();
     }
-    v
 }
 
-async fn api_get_value(cfg: &ApiConfig, path: &str) -> Result<Value> {
-    if cfg.base_url.trim().is_empty() {
-        anyhow::bail!("config.base_url not set");
+// The audit log oldest first: rotated "audit-<ts>.jsonl.gz" segments, then the live file.
+fn audit_log_files() -> Vec<PathBuf> {
+    let stem = format!("{}-", AUDIT_FILE.trim_end_matches(".jsonl"));
+    let mut files: Vec<PathBuf> = data_files()
+        .into_iter()
+        .map(|(p, _)| p)
+        .filter(|p| {
+            let name = p.file_name().map(|f| f.to_string_lossy().to_string()).unwrap_or_default();
+            name.starts_with(&stem) && name.ends_with(".jsonl.gz")
+        })
+        .collect();
+    files.sort();
+    files.extend(app_data_path(AUDIT_FILE).filter(|p| p.exists()));
+    files
+}
+
+// A range bound: "YYYY-MM-DD" means that whole local day, otherwise any parse_local_datetime form.
+fn parse_range_bound(s: &str, end: bool) -> Option<DateTime<Utc>> {
+    if let Ok(d) = chrono::NaiveDate::parse_from_str(s.trim(), "%Y-%m-%d") {
+        let d = if end { d.succ_opt()? } else { d };
+        return Local.from_local_datetime(&d.and_hms_opt(0, 0, 0)?).earliest().map(|t| t.with_timezone(&Utc));
     }
-    let client = reqwest::Client::new();
-    let auth = build_basic_header(&cfg.password);
-    let urls = candidate_urls(&cfg.base_url, path);
+    parse_local_datetime(s).map(|t| t.with_timezone(&Utc))
+}
 
-    let mut last_err: Option<anyhow::Error> = None;
-    for url in urls {
-        let mut req = client.get(&url);
-        if let Some(h) = &auth {
-            req = req.header("Authorization", h);
-        }
-        match req.send().await {
-            Ok(resp) if resp.status().is_success() => {
-                return Ok(resp.json::<Value>().await?);
+// Audit entries in [from, to), oldest first, reading the log and its gzipped segments line by
+// line so large histories never sit in memory.
+fn for_each_audit_entry(from: DateTime<Utc>, to: DateTime<Utc>, mut f: impl FnMut(AuditEntry) -> io::Result<()>) -> io::Result<()> {
+    use std::io::BufRead;
+    for path in audit_log_files() {
+        let file = File::open(&path)?;
+        let reader: Box<dyn BufRead> = if path.extension().is_some_and(|e| e == "gz") {
+            Box::new(io::BufReader::new(flate2::read::GzDecoder::new(file)))
+        } else {
+            Box::new(io::BufReader::new(file))
+        };
+        for line in reader.lines() {
+            let line = line?;
+            // skip lines cut short by a crash mid-write
+            let Ok(entry) = serde_json::from_str::<AuditEntry>(&line) else { continue };
+            if entry.at < from || entry.at >= to {
+                continue;
             }
-            Ok(resp) => last_err = Some(anyhow::anyhow!("GET {} -> {}", url, resp.status())),
-            Err(e) => last_err = Some(e.into()),
+            f(entry)?;
         }
     }
-    Err(last_err.unwrap_or_else(|| anyhow::anyhow!("no URL worked")))
+    Ok(())
 }
 
-async fn api_post_value(
-    cfg: &ApiConfig,
-    path: &str,
-    body: Option<serde_json::Value>,
-) -> Result<serde_json::Value> {
-    if cfg.base_url.trim().is_empty() {
-        anyhow::bail!("config.base_url not set");
-    }
-    let client = reqwest::Client::new();
-    let auth = build_basic_header(&cfg.password);
-    let urls = candidate_urls(&cfg.base_url, path);
+// Write audit entries in [from, to) to `dest` as a JSON array. Returns the entry count.
+fn write_audit_range(dest: &Path, from: DateTime<Utc>, to: DateTime<Utc>) -> io::Result<usize> {
+    let mut out = io::BufWriter::new(File::create(dest)?);
+    out.write_all(b"[")?;
+    let mut count = 0;
+    for_each_audit_entry(from, to, |entry| {
+        out.write_all(if count == 0 { b"\n" } else { b",\n" })?;
+        serde_json::to_writer(&mut out, &entry)?;
+        count += 1;
+        Ok(())
+    })?;
+    out.write_all(b"\n]\n")?;
+    out.flush()?;
+    Ok(count)
+}
 
-    let mut last_err: Option<anyhow::Error> = None;
-    for url in urls {
-        let mut req = client.post(&url);
-        if let Some(h) = &auth {
-            req = req.header(reqwest::header::AUTHORIZATION, h);
-        }
-        match &body {
-            Some(b) => {
-                req = req.json(b);
-            }
-            None => {
-                // many servers require CL:0 for /save
-                req = req.header(reqwest::header::CONTENT_LENGTH, "0");
-            }
-        }
-        match req.send().await {
-            Ok(resp) if resp.status().is_success() => {
-                return Ok(resp
-                    .json::<serde_json::Value>()
-                    .await
-                    .unwrap_or(serde_json::Value::Null));
-            }
-            Ok(resp) => last_err = Some(anyhow::anyhow!("POST {} -> {}", url, resp.status())),
-            Err(e) => last_err = Some(e.into()),
-        }
+// Export the audit log between two dates (inclusive days, or exact date-times) to a JSON file.
+#[tauri::command]
+async fn export_audit(from: String, to: String, path: String) -> Result<usize, String> {
+    let start = parse_range_bound(&from, false).ok_or_else(|| format!("invalid start '{}': use YYYY-MM-DD or YYYY-MM-DD HH:MM", from))?;
+    let end = parse_range_bound(&to, true).ok_or_else(|| format!("invalid end '{}': use YYYY-MM-DD or YYYY-MM-DD HH:MM", to))?;
+    if end <= start {
+        return Err("end must be after start".into());
+    }
+    let dest = PathBuf::from(path.trim());
+    if dest.as_os_str().is_empty() {
+        return Err("export path is empty".into());
     }
-    Err(last_err.unwrap_or_else(|| anyhow::anyhow!("no URL worked")))
+    tokio::task::spawn_blocking(move || write_audit_range(&dest, start, end))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
 }
 
-fn s_alt(v: &Value, keys: &[&str]) -> Option<String> {
-    for k in keys {
-        if let Some(s) = v.get(*k).and_then(|x| x.as_str()) {
-            return Some(s.to_string());
-        }
-    }
-    None
+/* ----------------------- data file rotation ----------------------- */
+// Append-only logs roll over to a gzipped segment past this size.
+const LOG_SEGMENT_BYTES: u64 = 1024 * 1024;
+const DEFAULT_STORAGE_LIMIT_MB: u64 = 50;
+
+// Cap on everything in the app data folder.
+fn storage_limit_bytes(cfg: &ApiConfig) -> u64 {
+    cfg.storage_limit_mb.filter(|n| *n > 0).unwrap_or(DEFAULT_STORAGE_LIMIT_MB).saturating_mul(1024 * 1024)
 }
-fn u_alt(v: &Value, keys: &[&str]) -> Option<usize> {
-    for k in keys {
-        if let Some(n) = v.get(*k) {
-            if let Some(u) = n.as_u64() {
-                return Some(u as usize);
-            }
-            if let Some(f) = n.as_f64() {
-                return Some(f as usize);
-            }
-            if let Some(s) = n.as_str().and_then(|t| t.parse::<usize>().ok()) {
-                return Some(s);
-            }
-        }
+
+// Move `file` into "<stem>-<timestamp>.jsonl.gz" once it is big enough; Ok(true) when rotated.
+fn rotate_log(file: &str) -> io::Result<bool> {
+    let Some(path) = app_data_path(file) else { return Ok(false) };
+    if std::fs::metadata(&path)?.len() < LOG_SEGMENT_BYTES {
+        return Ok(false);
     }
-    None
+    let stem = file.trim_end_matches(".jsonl");
+    let gz = path.with_file_name(format!("{}-{}.jsonl.gz", stem, Local::now().format("%Y%m%d-%H%M%S")));
+    let mut enc = flate2::write::GzEncoder::new(File::create(&gz)?, flate2::Compression::default());
+    io::copy(&mut File::open(&path)?, &mut enc)?;
+    enc.finish()?;
+    std::fs::remove_file(&path)?;
+    Ok(true)
 }
-fn u64_alt(v: &Value, keys: &[&str]) -> Option<u64> {
-    for k in keys {
-        if let Some(n) = v.get(*k) {
-            if let Some(u) = n.as_u64() {
-                return Some(u);
-            }
-            if let Some(f) = n.as_f64() {
-                return Some(f as u64);
-            }
-            if let Some(s) = n.as_str().and_then(|t| t.parse::<u64>().ok()) {
-                return Some(s);
-            }
-        }
+
+// Records trimmed from a capped data file (tracker sessions, save sizes) go to a gzipped
+// "<stem>-<timestamp>.jsonl.gz" segment instead of being thrown away.
+fn archive_records<T: Serialize>(stem: &str, records: &[T]) -> io::Result<()> {
+    if records.is_empty() {
+        return Ok(());
     }
-    None
+    let Some(path) = app_data_path(&format!("{}-{}.jsonl.gz", stem, Local::now().format("%Y%m%d-%H%M%S"))) else { return Ok(()) };
+    let mut enc = flate2::write::GzEncoder::new(File::create(&path)?, flate2::Compression::default());
+    for r in records {
+        serde_json::to_writer(&mut enc, r)?;
+        writeln!(enc)?;
+    }
+    enc.finish()?;
+    enforce_saved_storage_limit();
+    Ok(())
 }
 
-fn coerce_server_info(v: &Value) -> ServerInfo {
-    let root = v.get("data").unwrap_or(v);
-    let name = s_alt(root, &["servername", "name", "serverName"])
-        .unwrap_or_else(|| "Unknown".into());
-    let map = s_alt(root, &["map", "world", "World"]);
-    let maxp = u_alt(root, &["max_players", "maxPlayers", "MaxPlayers"]);
-    let up = u64_alt(root, &["uptime", "uptimeSeconds", "Uptime"]);
-    let mut players_online =
-        u_alt(root, &["players_online", "playersOnline", "currentPlayers"]).unwrap_or(0);
-    if players_online == 0 {
-        if let Some(arr) = root.get("players").and_then(|x| x.as_array()) {
-            players_online = arr.len();
-        } else if let Some(obj) = root.get("players").and_then(|x| x.as_object()) {
-            players_online = obj.len();
+// "audit-20240501-120000.jsonl.gz" -> "20240501-120000", so segments of different files order by age.
+fn segment_stamp(path: &Path) -> String {
+    let name = path.file_name().map(|f| f.to_string_lossy().to_string()).unwrap_or_default();
+    let base = name.trim_end_matches(".jsonl.gz");
+    base.get(base.len().saturating_sub(15)..).unwrap_or(base).to_string()
+}
+
+fn data_files() -> Vec<(PathBuf, u64)> {
+    let Some(dir) = app_data_dir() else { return vec![] };
+    let Ok(rd) = std::fs::read_dir(dir) else { return vec![] };
+    rd.filter_map(Result::ok)
+        .filter_map(|e| {
+            let meta = e.metadata().ok()?;
+            meta.is_file().then(|| (e.path(), meta.len()))
+        })
+        .collect()
+}
+
+// Drop the oldest rotated segments until the data folder fits the storage limit.
+// Live files (config, tracker, current log) are never removed.
+fn enforce_storage_limit(cfg: &ApiConfig) -> usize {
+    let files = data_files();
+    let limit = storage_limit_bytes(cfg);
+    let mut total: u64 = files.iter().map(|(_, n)| n).sum();
+    let mut segments: Vec<&(PathBuf, u64)> = files
+        .iter()
+        .filter(|(p, _)| p.to_string_lossy().ends_with(".gz"))
+        .collect();
+    segments.sort_by_key(|(p, _)| segment_stamp(p));
+    let mut removed = 0;
+    for (path, len) in segments {
+        if total <= limit { break; }
+        if std::fs::remove_file(path).is_ok() {
+            total = total.saturating_sub(*len);
+            removed += 1;
         }
     }
-    ServerInfo {
-        name,
-        map,
-        players_online,
-        max_players: maxp,
-        uptime_seconds: up,
-    }
+    removed
 }
-fn player_from_obj(v: &Value) -> Option<Player> {
-    // Prefer explicit userId (often "steam_7656...") over numeric playerId/hex ids
-    let mut id = s_alt(v, &["userId", "user_id", "steamId", "SteamID", "steam_id", "id", "playerId", "uid"]).or_else(|| {
-        v.get("steamId")
-            .and_then(|x| x.as_u64().map(|n| n.to_string()))
-            .or_else(|| v.get("id").and_then(|x| x.as_u64().map(|n| n.to_string())))
-            .or_else(|| v.get("playerId").and_then(|x| x.as_u64().map(|n| n.to_string())))
-    })?;
-    // Some servers use prefixes like "steam_7656..."; normalize to the 17-digit number when present
-    if let Ok(re) = regex::Regex::new(r"(?i)steam_?(\d{17})|(?<!\d)(\d{17})(?!\d)") {
-        if let Some(caps) = re.captures(&id) {
-            if let Some(m) = caps.get(1).or_else(|| caps.get(2)) {
-                // normalize to the form steam_<17-digits>
-                id = format!("steam_{}", m.as_str());
-            }
-        }
+
+// Segments are written from places with no config snapshot (audit, tracker), and only now and
+// then, so the limit is read from config.json.
+fn enforce_saved_storage_limit() -> usize {
+    enforce_storage_limit(&load_saved_config().unwrap_or_default())
+}
+
+#[derive(Debug, Serialize)]
+struct StorageUsage {
+    total_bytes: u64,
+    limit_bytes: u64,
+    files: Vec<(String, u64)>,
+}
+
+#[tauri::command]
+fn get_storage_usage(state: State<'_, AppState>) -> StorageUsage {
+    let mut files: Vec<(String, u64)> = data_files()
+        .into_iter()
+        .map(|(p, n)| (p.file_name().map(|f| f.to_string_lossy().to_string()).unwrap_or_default(), n))
+        .collect();
+    files.sort_by_key(|f| std::cmp::Reverse(f.1));
+    StorageUsage {
+        total_bytes: files.iter().map(|(_, n)| n).sum(),
+        limit_bytes: storage_limit_bytes(&state.config.lock()),
+        files,
     }
-    let name = s_alt(v, &["name", "playerName", "characterName", "displayName"])
-        .unwrap_or_else(|| "Unknown".into());
-    let level = u_alt(v, &["level", "lvl"]).map(|x| x as u32);
-    let ping = u_alt(v, &["ping", "latency"]).map(|x| x as u32);
-    // try to read connected seconds from common keys
-    let connected_seconds = {
-        if let Some(n) = v.get("connected_seconds").and_then(|x| x.as_i64()) { Some(n) }
-        else if let Some(n) = v.get("connectedSeconds").and_then(|x| x.as_i64()) { Some(n) }
-        else if let Some(n) = v.get("sessionSeconds").and_then(|x| x.as_i64()) { Some(n) }
-        else if let Some(n) = v.get("playTimeSec").and_then(|x| x.as_i64()) { Some(n) }
-        else if let Some(n) = v.get("playTimeSeconds").and_then(|x| x.as_i64()) { Some(n) }
-        else { None }
-    };
-    Some(Player {
-        id,
-        name,
-        level,
-        ping,
-        connected_seconds,
-    })
 }
 
-async fn server_is_up(base: &str, pass: &str) -> bool {
-    let client = reqwest::Client::new();
-    for url in candidate_urls(base, "info") {
-        let mut req = client.get(&url);
-        if !pass.is_empty() {
-            req = req.basic_auth("admin", Some(pass));
-        }
-        if let Ok(resp) = req.send().await {
-            if resp.status().is_success() { return true; }
+// Other persisted app data lives as JSON files next to config.json.
+fn load_data<T: serde::de::DeserializeOwned>(file: &str) -> Option<T> {
+    let data = std::fs::read(app_data_path(file)?).ok()?;
+    serde_json::from_slice::<T>(&data).ok()
+}
+fn save_data<T: Serialize>(file: &str, value: &T) {
+    if let Some(path) = app_data_path(file) {
+        if let Ok(data) = serde_json::to_vec_pretty(value) {
+            let _ = std::fs::write(path, data);
         }
     }
-    false
 }
 
-async fn wait_for_server_down(base: &str, pass: &str, max_secs: u64) -> bool {
-    let mut waited = 0u64;
-    loop {
-        if !server_is_up(base, pass).await { return true; }
-        if waited >= max_secs { return false; }
-        tokio::time::sleep(Duration::from_secs(1)).await;
-        waited += 1;
-    }
+/* ----------------------- discord embed helper ----------------------- */
+const COLOR_SUCCESS: u32 = 0x22C55E; // green
+const COLOR_ERROR: u32 = 0xEF4444;   // red
+const COLOR_INFO: u32 = 0x3B82F6;    // blue
+const COLOR_REPORT: u32 = 0x8B5CF6;  // purple (scheduled reports; not muted by quiet hours)
+const COLOR_ALERT: u32 = 0xF59E0B;   // amber (threshold alerts; not muted by quiet hours, not counted as errors)
+
+fn embed_payload(desc: &str, color: u32) -> Value {
+    serde_json::json!({
+        "embeds": [{ "description": desc, "color": color }]
+    })
 }
-fn coerce_players(v: &Value) -> Vec<Player> {
-    let root = v.get("data").unwrap_or(v);
-    let collect = |vv: &Value| -> Vec<Player> {
-        if let Some(arr) = vv.as_array() {
-            arr.iter().filter_map(player_from_obj).collect()
-        } else if let Some(obj) = vv.as_object() {
-            obj.values().filter_map(player_from_obj).collect()
-        } else {
-            vec![]
-        }
-    };
-    if let Some(pl) = root.get("players") {
-        collect(pl)
+
+// Quiet-hours window (start, end) in local time; None unless both ends parse.
+fn quiet_hours(cfg: &ApiConfig) -> Option<(NaiveTime, NaiveTime)> {
+    let parse = |s: &Option<String>| s.as_deref().and_then(|t| NaiveTime::parse_from_str(t.trim(), "%H:%M").ok());
+    parse(&cfg.quiet_hours_start).zip(parse(&cfg.quiet_hours_end))
+}
+
+// Half-open [start, end) window; start > end wraps past midnight, start == end is empty.
+fn in_quiet_window(now: NaiveTime, start: NaiveTime, end: NaiveTime) -> bool {
+    if start <= end {
+        now >= start && now < end
     } else {
-        collect(root)
+        now >= start || now < end
     }
 }
 
-/* --------------------- announce helpers --------------------- */
-
-async fn post_json(client: &reqwest::Client, v1: &str, pass: &str, path: &str, msg: &str) -> bool {
-    client
-        .post(&format!("{}/{}", v1, path))
-        .basic_auth("admin", Some(pass))
-        .header(CONTENT_TYPE, "application/json")
-        .body(format!(r#"{{"message":"{}"}}"#, msg))
-        .send()
-        .await
-        .map(|r| r.status().is_success())
-        .unwrap_or(false)
+// A Discord webhook, the client to post through and the quiet window, taken from the caller's config snapshot.
+#[derive(Clone)]
+struct DiscordHook {
+    url: String,
+    client: reqwest::Client,
+    quiet: Option<(NaiveTime, NaiveTime)>,
 }
-async fn post_text(client: &reqwest::Client, v1: &str, pass: &str, path: &str, msg: &str) -> bool {
-    client
-        .post(&format!("{}/{}", v1, path))
-        .basic_auth("admin", Some(pass))
-        .header(CONTENT_TYPE, "text/plain; charset=utf-8")
-        .body(msg.to_string())
-        .send()
-        .await
-        .map(|r| r.status().is_success())
-        .unwrap_or(false)
+
+fn discord_hook(rest: &RestState, cfg: &ApiConfig) -> Option<DiscordHook> {
+    Some(DiscordHook { url: cfg.discord_webhook.clone()?, client: rest.client(), quiet: quiet_hours(cfg) })
 }
-async fn get_query(client: &reqwest::Client, v1: &str, pass: &str, path: &str, msg: &str) -> bool {
-    client
-        .get(&format!("{}/{path}?message={}", v1, encode(msg)))
-        .basic_auth("admin", Some(pass))
+
+// Post a full embed object (title, fields, ...) as-is; used for on-demand reports, so never muted.
+async fn discord_rich_embed(hook: &DiscordHook, embed: Value) {
+    let _ = hook.client
+        .post(&hook.url)
+        .json(&serde_json::json!({ "embeds": [embed] }))
         .send()
-        .await
-        .map(|r| r.status().is_success())
-        .unwrap_or(false)
+        .await;
 }
-async fn announce_multi(client: &reqwest::Client, base: &str, pass: &str, msg: &str) -> bool {
-    let v1 = v1_base(base);
-    for path in ["announce", "broadcast"] {
-        if post_json(client, &v1, pass, path, msg).await {
-            return true;
+
+const DISCORD_WEBHOOK_HOSTS: [&str; 6] = [
+    "discord.com", "ptb.discord.com", "canary.discord.com",
+    "discordapp.com", "ptb.discordapp.com", "canary.discordapp.com",
+];
+
+// Checks a webhook URL is https://<discord host>/api[/v<N>]/webhooks/<id>/<token>. Any other
+// http(s) URL is accepted only with allow_custom (proxies); channel links are called out specifically.
+fn check_discord_webhook(url: &str, allow_custom: bool) -> Result<String, String> {
+    let parsed = reqwest::Url::parse(url.trim()).map_err(|e| format!("invalid webhook URL '{}': {}", url, e))?;
+    let host = parsed.host_str().unwrap_or("").to_ascii_lowercase();
+    if DISCORD_WEBHOOK_HOSTS.contains(&host.as_str()) {
+        if parsed.scheme() != "https" {
+            return Err("Discord webhook URLs must use https".into());
         }
-        if post_text(client, &v1, pass, path, msg).await {
-            return true;
+        let segs: Vec<&str> = parsed.path_segments().map(|s| s.filter(|p| !p.is_empty()).collect()).unwrap_or_default();
+        if segs.first() == Some(&"channels") {
+            return Err("that is a channel link, not a webhook; copy the URL from Channel Settings > Integrations > Webhooks".into());
         }
-        if get_query(client, &v1, pass, path, msg).await {
-            return true;
+        // versioned form: /api/v10/webhooks/<id>/<token>
+        let versioned = segs.get(1).and_then(|v| v.strip_prefix('v')).is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()));
+        let rest = if versioned { segs.get(2..).unwrap_or_default() } else { segs.get(1..).unwrap_or_default() };
+        let ok = segs.first() == Some(&"api")
+            && rest.len() == 3
+            && rest[0] == "webhooks"
+            && !rest[1].is_empty() && rest[1].chars().all(|c| c.is_ascii_digit())
+            && !rest[2].is_empty();
+        if !ok {
+            return Err("malformed Discord webhook URL: expected https://discord.com/api/webhooks/<id>/<token>".into());
         }
+        return Ok(parsed.to_string());
     }
-    false
+    if !allow_custom {
+        return Err(format!("'{}' is not a Discord host; set allow_custom_webhook to use a proxy", host));
+    }
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(format!("unsupported webhook scheme '{}'", parsed.scheme()));
+    }
+    Ok(parsed.to_string())
 }
 
-/* ----------------------- Tauri commands ----------------------- */
-
 #[tauri::command]
-fn get_config(state: State<'_, AppState>) -> ApiConfig {
-    state.config.lock().clone()
+fn validate_discord_webhook(url: String, allow_custom: Option<bool>) -> Result<String, String> {
+    check_discord_webhook(&url, allow_custom.unwrap_or(false))
 }
 
-#[tauri::command]
-fn set_config(
-    state: State<'_, AppState>,
-    mut base_url: String,
-    password: Option<String>,
-    restart_times: Option<Vec<String>>,
-    start_cmd: Option<String>,
-    backup_dir: Option<String>,
-    backup_dest_dir: Option<String>,
-    discord_webhook: Option<String>,
-    allow_actions: Option<bool>,
-) -> Result<(), String> {
-    // normalize URL
-    base_url = base_url.trim().to_string();
-    if base_url.is_empty() {
-        return Err("base_url is empty".into());
-    }
-    if !base_url.starts_with("http://") && !base_url.starts_with("https://") {
-        base_url = format!("http://{}", base_url);
+// Embed plus plain message content. Mentions only ping from the content, never from an embed.
+async fn discord_embed_with_content(hook: &DiscordHook, content: &str, desc: &str, color: u32) {
+    let mut payload = embed_payload(desc, color);
+    payload["content"] = Value::from(content);
+    let _ = hook.client.post(&hook.url).json(&payload).send().await;
+}
+
+async fn discord_embed(hook: &DiscordHook, desc: &str, color: u32) {
+    // quiet hours mute routine info posts; successes and errors still go out
+    if color == COLOR_INFO {
+        if let Some((start, end)) = hook.quiet {
+            if in_quiet_window(Local::now().time(), start, end) {
+                return;
+            }
+        }
     }
+    let _ = hook.client
+        .post(&hook.url)
+        .json(&embed_payload(desc, color))
+        .send()
+        .await;
+}
 
-    // update config under lock, then take a snapshot and drop the lock
-    let snapshot: ApiConfig = {
-        let mut cfg = state.config.lock();
-        cfg.base_url = base_url;
-        if password.is_some() { cfg.password = password; }
-        if let Some(t) = restart_times { cfg.restart_times = t; }
-        if start_cmd.is_some() { cfg.start_cmd = start_cmd; }
-        if backup_dir.is_some() { cfg.backup_dir = backup_dir; }
-        if backup_dest_dir.is_some() { cfg.backup_dest_dir = backup_dest_dir; }
-        if let Some(v) = allow_actions { cfg.allow_actions = v; }
-        if discord_webhook.is_some() { cfg.discord_webhook = discord_webhook; }
-        cfg.clone()
+// Representative message/color for each notification type, used for previews.
+fn sample_event(event_type: &str) -> Option<(&'static str, u32)> {
+    let sample = match event_type {
+        "config" => ("Config updated. Base: http://127.0.0.1:8212 | Restarts: 03:00, 15:00 | Actions: enabled", COLOR_INFO),
+        "save" => ("Auto save completed.", COLOR_SUCCESS),
+        "backup" => ("Auto backup created: C:\\palworld\\_backups\\backup-20240101-030000.zip", COLOR_SUCCESS),
+        "backup_failed" => ("Auto backup failed: backup source not found", COLOR_ERROR),
+        "capacity" => ("Server near capacity (29/32)", COLOR_ALERT),
+        "restart" => ("Auto-restart executing.", COLOR_INFO),
+        "update" => ("Server updated to v0.3.0", COLOR_SUCCESS),
+        "join" => ("Player joined: ExamplePlayer", COLOR_INFO),
+        "leave" => ("Player left: ExamplePlayer", COLOR_INFO),
+        "broadcast" => ("Broadcast sent: Welcome to the server!", COLOR_SUCCESS),
+        "kick" => ("Kick succeeded: steam_76561198000000000", COLOR_SUCCESS),
+        "ban" => ("Ban succeeded: steam_76561198000000000", COLOR_SUCCESS),
+        _ => return None,
     };
+    Some(sample)
+}
 
-    // start/restart scheduler (only if actions allowed)
-    if snapshot.allow_actions {
-        spawn_scheduler(state.sched.clone(), &snapshot);
-    } else {
-        // cancel existing scheduler
-        let _ = state.sched.fetch_add(1, Ordering::SeqCst);
+/* ----------------------- live events (WebSocket) ----------------------- */
+
+// Fan-out of JSON events to connected WebSocket clients; sends are dropped when nobody listens.
+static LIVE_EVENTS: Lazy<tokio::sync::broadcast::Sender<String>> =
+    Lazy::new(|| tokio::sync::broadcast::channel(256).0);
+const CRASH_CONFIRM_POLLS: usize = 3;
+
+fn publish_event(kind: &str, data: Value) {
+    if LIVE_EVENTS.receiver_count() == 0 {
+        return;
     }
-    save_config(&snapshot);
-    // Discord log: config updated
-    if let Some(h) = snapshot.discord_webhook.clone() {
-        let base = snapshot.base_url.clone();
-        let times = if snapshot.restart_times.is_empty() { "(none)".to_string() } else { snapshot.restart_times.join(", ") };
-        let actions = if snapshot.allow_actions { "enabled" } else { "disabled" };
-        tauri::async_runtime::spawn(async move {
-            discord_embed(&h, &format!("Config updated. Base: {} | Restarts: {} | Actions: {}", base, times, actions), COLOR_INFO).await;
-        });
+    let mut ev = serde_json::json!({ "type": kind, "at": Utc::now().to_rfc3339() });
+    if let (Some(obj), Value::Object(extra)) = (ev.as_object_mut(), data) {
+        obj.extend(extra);
     }
-    // start autosave and backup background tasks
-    if snapshot.allow_actions {
-        spawn_autosave(state.autosave_gen.clone(), &snapshot);
-        spawn_backup(state.backup_gen.clone(), &snapshot);
+    let _ = LIVE_EVENTS.send(ev.to_string());
+}
+
+// Up/down bookkeeping shared by the /info poll, restarts and shutdowns.
+#[derive(Default)]
+struct ServerWatch {
+    // last known REST reachability, so up/down is only published on transitions
+    up: Mutex<Option<bool>>,
+    // up/down transitions for the availability report (uptime.json)
+    uptime: Mutex<Vec<UpDownEvent>>,
+    // set once a shutdown is sent, so the following downtime is not treated as a crash
+    expected_down: AtomicBool,
+    // failed polls in a row; one dropped /info is not a crash, CRASH_CONFIRM_POLLS of them are
+    down_polls: AtomicUsize,
+    // set while the server is up; cleared once the crash backup for this outage has fired
+    crash_armed: AtomicBool,
+}
+
+fn note_server_reachable(rest: &RestState, watch: &ServerWatch, cfg: &ApiConfig, up: bool) {
+    let prev = watch.up.lock().replace(up);
+    if prev != Some(up) {
+        publish_event(if up { "server_up" } else { "server_down" }, Value::Null);
+        if up {
+            watch.expected_down.store(false, Ordering::SeqCst);
+        }
+        log_up_down(&watch.uptime, up, prev.is_none());
     } else {
-        let _ = state.autosave_gen.fetch_add(1, Ordering::SeqCst);
-        let _ = state.backup_gen.fetch_add(1, Ordering::SeqCst);
+        touch_up_down(&watch.uptime);
+    }
+    if up {
+        watch.down_polls.store(0, Ordering::SeqCst);
+        watch.crash_armed.store(true, Ordering::SeqCst);
+    } else if watch.down_polls.fetch_add(1, Ordering::SeqCst) + 1 >= CRASH_CONFIRM_POLLS
+        && watch.crash_armed.swap(false, Ordering::SeqCst)
+        && !watch.expected_down.load(Ordering::SeqCst)
+    {
+        on_unexpected_down(rest, cfg);
     }
+}
 
-    Ok(())
+/* ----------------------- availability ----------------------- */
+const UPTIME_FILE: &str = "uptime.json";
+const UPTIME_LOG_DAYS: i64 = 90;
+const DEFAULT_AVAILABILITY_HOURS: u64 = 24 * 7;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct UpDownEvent {
+    at: DateTime<Utc>,
+    up: bool,
+    // first observation after the app started; the gap before it was not monitored
+    #[serde(default)]
+    first: bool,
+    // last poll that confirmed this state, so a span cut short by an app exit still counts
+    #[serde(default)]
+    confirmed: Option<DateTime<Utc>>,
 }
 
-// Try several shutdown payload shapes; return true on first success.
-async fn attempt_shutdown(base: &str, pass: &str, hook: Option<String>, reason: &str) -> bool {
-    let client = reqwest::Client::new();
-    let url = format!("{}/shutdown", v1_base(base));
-    let bodies = [
-        serde_json::json!({ "waittime": 1, "message": reason }),
-        serde_json::json!({ "seconds": 1,  "message": reason }),
-        serde_json::json!({ "time": 1,     "message": reason }),
-        serde_json::json!({ "duration": 1, "message": reason }),
-    ];
+fn log_up_down(uptime: &Mutex<Vec<UpDownEvent>>, up: bool, first: bool) {
+    let now = Utc::now();
+    let mut log = uptime.lock();
+    log.push(UpDownEvent { at: now, up, first, confirmed: Some(now) });
+    let cutoff = now - chrono::Duration::days(UPTIME_LOG_DAYS);
+    // keep the last event before the cutoff so the state at the window start is known
+    let keep_from = log.iter().rposition(|e| e.at < cutoff).unwrap_or(0);
+    log.drain(..keep_from);
+    save_data(UPTIME_FILE, &*log);
+}
 
-    // JSON bodies first
-    for (i, b) in bodies.iter().enumerate() {
-        let res = client
-            .post(&url)
-            .basic_auth("admin", Some(pass))
-            .json(b)
-            .send()
-            .await;
-        let ok = res.as_ref().map(|r| r.status().is_success()).unwrap_or(false);
-        if let Some(h) = hook.clone() {
-            let msg = match &res {
-                Ok(r) => format!("Shutdown attempt {} -> {}", i + 1, r.status()),
-                Err(e) => format!("Shutdown attempt {} error: {}", i + 1, e),
-            };
-            let _ = discord_embed(&h, &msg, if ok { COLOR_SUCCESS } else { COLOR_ERROR }).await;
+// Polls that see no change only move `confirmed` forward; written at most once a minute.
+fn touch_up_down(uptime: &Mutex<Vec<UpDownEvent>>) {
+    let now = Utc::now();
+    let mut log = uptime.lock();
+    let Some(last) = log.last_mut() else { return };
+    let saved = last.confirmed.unwrap_or(last.at);
+    last.confirmed = Some(now);
+    if now - saved >= chrono::Duration::seconds(60) {
+        save_data(UPTIME_FILE, &*log);
+    }
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+struct AvailabilityReport {
+    window_hours: u64,
+    // None when nothing in the window was monitored
+    uptime_percent: Option<f64>,
+    up_secs: i64,
+    down_secs: i64,
+    unmonitored_secs: i64,
+    outages: u32,
+    longest_outage_secs: i64,
+}
+
+// Up/down time inside the window. Each event's state lasts until the next event; before a
+// `first` event (app restart) it only lasts until it was last confirmed, and the rest of the
+// gap counts as unmonitored, as does anything before the log.
+fn availability_over(events: &[UpDownEvent], now: DateTime<Utc>, window_hours: u64) -> AvailabilityReport {
+    let start = now - chrono::Duration::hours(window_hours as i64);
+    let (mut up_secs, mut down_secs, mut outages, mut longest) = (0i64, 0i64, 0u32, 0i64);
+    for (i, e) in events.iter().enumerate() {
+        let end = match events.get(i + 1) {
+            Some(n) if n.first => e.confirmed.unwrap_or(e.at).min(n.at),
+            Some(n) => n.at,
+            None => now,
+        }
+        .min(now);
+        let secs = (end - e.at.max(start)).num_seconds();
+        if secs <= 0 {
+            continue;
+        }
+        if e.up {
+            up_secs += secs;
+        } else {
+            down_secs += secs;
+            outages += 1;
+            longest = longest.max(secs);
         }
-        if ok { return true; }
     }
-    // Final attempt without body but with CL:0
-    let res = client
-        .post(&url)
-        .basic_auth("admin", Some(pass))
-        .header(CONTENT_LENGTH, "0")
-        .send()
-        .await;
-    let ok = res.as_ref().map(|r| r.status().is_success()).unwrap_or(false);
-    if let Some(h) = hook {
-        let msg = match res {
-            Ok(r) => format!("Shutdown attempt (no body) -> {}", r.status()),
-            Err(e) => format!("Shutdown attempt (no body) error: {}", e),
-        };
-        let _ = discord_embed(&h, &msg, if ok { COLOR_SUCCESS } else { COLOR_ERROR }).await;
+    let total = (now - start).num_seconds();
+    let monitored = up_secs + down_secs;
+    AvailabilityReport {
+        window_hours,
+        uptime_percent: (monitored > 0).then(|| (up_secs as f64 * 10000.0 / monitored as f64).round() / 100.0),
+        up_secs,
+        down_secs,
+        unmonitored_secs: (total - monitored).max(0),
+        outages,
+        longest_outage_secs: longest,
     }
-    ok
 }
 
-// Send staged restart warnings at 60, 30, 20, 10, and 5 seconds.
-// Sleeps between stages so that total wait equals `total` seconds.
-async fn warn_countdown(
-    client: &reqwest::Client,
-    base: &str,
-    pass: &str,
-    total: u64,
-    hook: Option<String>,
-) {
-    let mut checkpoints = vec![60u64, 30, 20, 10, 5];
-    checkpoints.retain(|&c| c <= total && c > 0);
-    checkpoints.sort_by(|a, b| b.cmp(a)); // descending
+// Uptime percentage, outage count and longest outage over the last `window_hours` (default 7 days).
+#[tauri::command]
+fn availability(state: State<'_, AppState>, window_hours: Option<u64>) -> AvailabilityReport {
+    let hours = window_hours.filter(|h| *h > 0).unwrap_or(DEFAULT_AVAILABILITY_HOURS);
+    availability_over(&state.watch.uptime.lock(), Utc::now(), hours.min(UPTIME_LOG_DAYS as u64 * 24))
+}
 
-    let mut remaining = total;
-    for cp in checkpoints {
-        if remaining > cp {
-            tokio::time::sleep(Duration::from_secs(remaining - cp)).await;
-            remaining = cp;
+fn spawn_ws_server(ws: Arc<AtomicUsize>, beats: TaskBeats, badge: Arc<Mutex<BadgeStatus>>, cfg: &ApiConfig) {
+    let my_id = ws.fetch_add(1, Ordering::SeqCst) + 1;
+    let Some(addr) = cfg.ws_listen.clone().filter(|a| !a.trim().is_empty()) else { return };
+    tauri::async_runtime::spawn(async move {
+        task_started(&beats, "live_events", my_id, 60);
+        // the previous listener may hold the port for a moment after a config change
+        let mut listener = None;
+        for _ in 0..10 {
+            if ws.load(Ordering::SeqCst) != my_id { task_ended(&beats, "live_events", my_id); return; }
+            match tokio::net::TcpListener::bind(addr.trim()).await {
+                Ok(l) => { listener = Some(l); break; }
+                Err(_) => tokio::time::sleep(Duration::from_millis(500)).await,
+            }
+        }
+        let Some(listener) = listener else {
+            audit("live_events", &addr, false, Some("could not listen".into()));
+            task_failed(&beats, "live_events", my_id, format!("live events off: could not listen on {}", addr));
+            return;
+        };
+        while ws.load(Ordering::SeqCst) == my_id {
+            task_ran(&beats, "live_events");
+            // short accept timeout so a config change releases the port promptly
+            let Ok(Ok((stream, _))) = tokio::time::timeout(Duration::from_millis(500), listener.accept()).await else { continue };
+            tauri::async_runtime::spawn(serve_ws_client(stream, ws.clone(), my_id, badge.clone()));
+        }
+        task_ended(&beats, "live_events", my_id);
+    });
+}
+
+async fn serve_ws_client(stream: tokio::net::TcpStream, ws: Arc<AtomicUsize>, my_id: usize, badge: Arc<Mutex<BadgeStatus>>) {
+    use futures_util::{SinkExt as _, StreamExt as _};
+    use tokio::sync::broadcast::error::RecvError;
+    use tokio_tungstenite::tungstenite::Message;
+
+    // plain GET /badge.svg on the same port serves the status badge instead of a WebSocket
+    let mut head = [0u8; 16];
+    if let Ok(n) = stream.peek(&mut head).await {
+        if head[..n].starts_with(b"GET /badge.svg") {
+            serve_badge(stream, &badge).await;
+            return;
+        }
+    }
+    let Ok(mut sock) = tokio_tungstenite::accept_async(stream).await else { return };
+    let mut rx = LIVE_EVENTS.subscribe();
+    loop {
+        tokio::select! {
+            ev = rx.recv() => match ev {
+                Ok(text) => if sock.send(Message::Text(text)).await.is_err() { break },
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => break,
+            },
+            // read-only feed: incoming frames are ignored apart from close
+            incoming = sock.next() => match incoming {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            },
+            _ = tokio::time::sleep(Duration::from_secs(1)) => {
+                if ws.load(Ordering::SeqCst) != my_id { break; }
+            }
         }
-        let msg = if cp == 5 {
-            "Log off now".to_string()
+    }
+    let _ = sock.close(None).await;
+}
+
+/* ----------------------- status badge ----------------------- */
+// (online, players, max players) from the last /info poll; None until the first poll.
+type BadgeStatus = Option<(bool, usize, Option<usize>)>;
+
+// Shields-style badge, e.g. "Palworld | online, 12/32 players".
+fn render_status_badge(status: BadgeStatus) -> String {
+    let (value, color) = match status {
+        None => ("unknown".to_string(), "#9f9f9f"),
+        Some((false, _, _)) => ("offline".to_string(), "#e05d44"),
+        Some((true, n, Some(max))) => (format!("online, {}/{} players", n, max), "#4c1"),
+        Some((true, n, None)) => (format!("online, {} players", n), "#4c1"),
+    };
+    let label = "Palworld";
+    // rough Verdana 11px advance plus padding
+    let width = |s: &str| s.chars().count() * 7 + 10;
+    let (lw, vw) = (width(label), width(&value));
+    format!(
+        concat!(
+            r##"<svg xmlns="http://www.w3.org/2000/svg" width="{total}" height="20" role="img" aria-label="{label}: {value}">"##,
+            r##"<rect width="{lw}" height="20" fill="#555"/><rect x="{lw}" width="{vw}" height="20" fill="{color}"/>"##,
+            r##"<g fill="#fff" text-anchor="middle" font-family="Verdana,Geneva,sans-serif" font-size="11">"##,
+            r##"<text x="{lx}" y="14">{label}</text><text x="{vx}" y="14">{value}</text></g></svg>"##
+        ),
+        total = lw + vw,
+        lw = lw,
+        vw = vw,
+        lx = lw / 2,
+        vx = lw + vw / 2,
+        color = color,
+        label = label,
+        value = value,
+    )
+}
+
+async fn serve_badge(mut stream: tokio::net::TcpStream, badge: &Mutex<BadgeStatus>) {
+    use tokio::io::{AsyncReadExt as _, AsyncWriteExt as _};
+    // drain the request head; nothing in it matters
+    let mut buf = [0u8; 2048];
+    let _ = tokio::time::timeout(Duration::from_secs(2), stream.read(&mut buf)).await;
+    let svg = render_status_badge(*badge.lock());
+    let resp = format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: image/svg+xml\r\nCache-Control: no-cache\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        svg.len(),
+        svg
+    );
+    let _ = stream.write_all(resp.as_bytes()).await;
+    let _ = stream.shutdown().await;
+}
+
+// The status badge SVG from the last poll; also served at http://<ws_listen>/badge.svg.
+#[tauri::command]
+fn status_badge_svg(state: State<'_, AppState>) -> String {
+    render_status_badge(*state.badge.lock())
+}
+
+/* ----------------------- zip helpers (backups) ----------------------- */
+// `progress(done, total)` is called after each file; total comes from a quick pre-walk.
+fn zip_directory<F: FnMut(usize, usize)>(src: &Path, dest_zip: &Path, mut progress: F) -> anyhow::Result<()> {
+    if !src.exists() {
+        anyhow::bail!("backup source not found: {}", src.display());
+    }
+    let file = File::create(dest_zip)?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    let src_abs = src.canonicalize().unwrap_or_else(|_| src.to_path_buf());
+    let backups_dir = src_abs.join("_backups");
+    let total = walkdir::WalkDir::new(&src_abs)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|e| e.file_type().is_file() && !e.path().starts_with(&backups_dir))
+        .count();
+    let mut done = 0usize;
+
+    for entry in walkdir::WalkDir::new(&src_abs).into_iter().filter_map(Result::ok) {
+        let path = entry.path();
+        let name_rel = path.strip_prefix(&src_abs).unwrap_or(path);
+        if name_rel.as_os_str().is_empty() { continue; }
+        // skip our backups output folder
+        if path.starts_with(&backups_dir) { continue; }
+        if path.is_dir() {
+            let name = format!("{}/", name_rel.to_string_lossy().replace('\\', "/"));
+            let _ = zip.add_directory(name, options);
         } else {
-            format!("Restart in {} seconds.", cp)
+            let name = name_rel.to_string_lossy().replace('\\', "/");
+            if let Ok(mut f) = File::open(path) {
+                let _ = zip.start_file(name, options);
+                let _ = io::copy(&mut f, &mut zip);
+            }
+            done += 1;
+            progress(done, total.max(done));
+        }
+    }
+    zip.finish()?;
+    Ok(())
+}
+
+// Resolve an archive entry name under `dest`, refusing anything that could escape it
+// (`..`, absolute paths, drive prefixes). Backslashes count as separators too.
+fn safe_entry_path(dest: &Path, name: &str) -> Option<PathBuf> {
+    let normalized = name.replace('\\', "/");
+    let mut out = dest.to_path_buf();
+    let mut has_part = false;
+    for c in Path::new(&normalized).components() {
+        match c {
+            Component::Normal(part) => {
+                out.push(part);
+                has_part = true;
+            }
+            Component::CurDir => {}
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => return None,
+        }
+    }
+    has_part.then_some(out)
+}
+
+// Extract `archive` (or only the entries named in `only`) into `dest`. Every entry is validated
+// before anything is written, so a single malicious path rejects the whole archive. Returns the
+// number of files written.
+fn safe_extract(archive: &Path, dest: &Path, only: Option<&HashSet<String>>) -> anyhow::Result<usize> {
+    let mut zip = zip::ZipArchive::new(File::open(archive)?)?;
+    let mut targets = Vec::with_capacity(zip.len());
+    for i in 0..zip.len() {
+        let entry = zip.by_index(i)?;
+        let Some(target) = safe_entry_path(dest, entry.name()) else {
+            anyhow::bail!("unsafe path in archive: {}", entry.name());
         };
-        let _ = announce_multi(client, base, pass, &msg).await;
-        if let Some(h) = hook.clone() {
-            let _ = discord_embed(&h, &msg, COLOR_INFO).await;
+        let skip = only.is_some_and(|names| !names.contains(entry.name()));
+        targets.push((target, entry.is_dir() || skip, skip));
+    }
+    std::fs::create_dir_all(dest)?;
+    let mut written = 0usize;
+    for (i, (target, no_file, skip)) in targets.into_iter().enumerate() {
+        if no_file {
+            if !skip { std::fs::create_dir_all(&target)?; }
+            continue;
         }
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut entry = zip.by_index(i)?;
+        let mut out = File::create(&target)?;
+        io::copy(&mut entry, &mut out)?;
+        written += 1;
     }
-    if remaining > 0 {
-        tokio::time::sleep(Duration::from_secs(remaining)).await;
+    Ok(written)
+}
+
+/* ----------------------- selective restore ----------------------- */
+// A backup in the destination folder, by bare file name (no path parts).
+fn backup_archive_path(cfg: &ApiConfig, filename: &str) -> Result<PathBuf, String> {
+    let name = filename.trim();
+    if name.is_empty() || name.contains(['/', '\\']) || name.contains("..") || !name.to_ascii_lowercase().ends_with(".zip") {
+        return Err(format!("'{}' is not a backup file name", filename));
+    }
+    let (_, dest_root) = resolve_backup_paths(cfg, None, None)?;
+    let path = dest_root.join(name);
+    if !path.is_file() {
+        return Err(format!("backup not found: {}", path.display()));
+    }
+    Ok(path)
+}
+
+// File entries of one backup, in archive order.
+#[tauri::command]
+fn list_backup_contents(state: State<'_, AppState>, filename: String) -> Result<Vec<String>, String> {
+    let cfg = state.config.lock().clone();
+    let path = backup_archive_path(&cfg, &filename)?;
+    let zip = zip::ZipArchive::new(File::open(&path).map_err(|e| e.to_string())?).map_err(|e| e.to_string())?;
+    Ok(zip.file_names().filter(|n| !n.ends_with('/')).map(str::to_string).collect())
+}
+
+#[derive(Serialize)]
+struct RestoreReport {
+    restored: usize,
+    // pre-restore-<ts>.zip holding the files that were overwritten; None when none existed
+    snapshot: Option<String>,
+}
+
+// Zip the files of `entries` that currently exist under `src` so a partial restore can be undone.
+fn snapshot_files(src: &Path, entries: &HashSet<String>, dest_zip: &Path) -> anyhow::Result<usize> {
+    let existing: Vec<(&String, PathBuf)> = entries
+        .iter()
+        .filter_map(|e| Some((e, safe_entry_path(src, e)?)))
+        .filter(|(_, p)| p.is_file())
+        .collect();
+    if existing.is_empty() {
+        return Ok(0);
+    }
+    let mut zip = zip::ZipWriter::new(File::create(dest_zip)?);
+    let options = zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    for (name, path) in &existing {
+        zip.start_file(name.replace('\\', "/"), options)?;
+        io::copy(&mut File::open(path)?, &mut zip)?;
+    }
+    zip.finish()?;
+    Ok(existing.len())
+}
+
+// Restore only the named entries of a backup into the save folder (e.g. one corrupted player
+// file). The files about to be overwritten are zipped to pre-restore-<ts>.zip first. A running
+// server keeps the world in memory and would write over the restored files, so it must be stopped.
+#[tauri::command]
+async fn restore_files(state: State<'_, AppState>, filename: String, entries: Vec<String>) -> Result<RestoreReport, String> {
+    let cfg = state.config.lock().clone();
+    if !cfg.allow_actions { return Err("actions disabled".into()); }
+    if entries.is_empty() { return Err("no entries selected".into()); }
+    if server_is_up(&state.rest, &cfg.base_url, &admin_login(&cfg)).await {
+        return Err("the server is running; stop it before restoring files".into());
+    }
+    let archive = backup_archive_path(&cfg, &filename)?;
+    let (src, dest_root) = resolve_backup_paths(&cfg, None, None)?;
+    let wanted: HashSet<String> = entries.into_iter().collect();
+    let snapshot_path = dest_root.join(format!("pre-restore-{}.zip", Local::now().format("%Y%m%d-%H%M%S")));
+    let archive_name = archive.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let result = tokio::task::spawn_blocking(move || -> anyhow::Result<RestoreReport> {
+        let zip = zip::ZipArchive::new(File::open(&archive)?)?;
+        let names: HashSet<&str> = zip.file_names().collect();
+        let mut missing: Vec<&str> = wanted.iter().map(String::as_str).filter(|e| !names.contains(e)).collect();
+        if !missing.is_empty() {
+            missing.sort();
+            anyhow::bail!("not in {}: {}", archive.display(), missing.join(", "));
+        }
+        drop(zip);
+        let snapshot = (snapshot_files(&src, &wanted, &snapshot_path)? > 0).then(|| snapshot_path.to_string_lossy().to_string());
+        prune_prefixed_backups(&dest_root, "pre-restore-", PRE_RESTORE_KEEP);
+        let restored = safe_extract(&archive, &src, Some(&wanted))?;
+        Ok(RestoreReport { restored, snapshot })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string());
+    audit("restore_files", &archive_name, result.is_ok(), result.as_ref().err().cloned());
+    if let Some(h) = discord_hook(&state.rest, &cfg) {
+        let (msg, color) = match &result {
+            Ok(r) => (format!("Restored {} file(s) from {}.", r.restored, archive_name), COLOR_SUCCESS),
+            Err(e) => (format!("Partial restore from {} failed: {}", archive_name, e), COLOR_ERROR),
+        };
+        discord_embed(&h, &msg, color).await;
+    }
+    result
+}
+
+/* ----------------------- backup naming ----------------------- */
+const DEFAULT_BACKUP_NAME_TEMPLATE: &str = "backup-{date}-{time}";
+const BACKUP_PLACEHOLDERS: [&str; 4] = ["{date}", "{time}", "{server}", "{players}"];
+// Names of the automatic safety archives; each has its own keep count (PRE_RESTART_KEEP, ...).
+const RESERVED_BACKUP_PREFIXES: [&str; 3] = ["pre-restart-", "crash-", "pre-restore-"];
+
+fn backup_template(cfg: &ApiConfig) -> &str {
+    cfg.backup_name_template.as_deref().unwrap_or(DEFAULT_BACKUP_NAME_TEMPLATE)
+}
+
+fn with_zip_ext(name: &str) -> String {
+    if name.to_ascii_lowercase().ends_with(".zip") { name.to_string() } else { format!("{}.zip", name) }
+}
+
+// Placeholder values end up in a file name: keep [A-Za-z0-9_-], replace the rest.
+fn sanitize_file_part(s: &str) -> String {
+    let out: String = s
+        .trim()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    if out.is_empty() { "unknown".into() } else { out }
+}
+
+fn render_backup_name(template: &str, now: DateTime<Local>, server: &str, players: usize) -> String {
+    with_zip_ext(template)
+        .replace("{date}", &now.format("%Y%m%d").to_string())
+        .replace("{time}", &now.format("%H%M%S").to_string())
+        .replace("{server}", &sanitize_file_part(server))
+        .replace("{players}", &players.to_string())
+}
+
+// Anchored regex matching every name the template can produce; pruning only touches these.
+fn backup_name_regex(template: &str) -> regex::Regex {
+    let t = with_zip_ext(template);
+    let mut pat = String::from("^");
+    let mut rest = t.as_str();
+    while let Some((i, ph)) = BACKUP_PLACEHOLDERS
+        .iter()
+        .filter_map(|p| rest.find(p).map(|i| (i, *p)))
+        .min_by_key(|(i, _)| *i)
+    {
+        pat.push_str(&regex::escape(&rest[..i]));
+        pat.push_str(match ph {
+            "{date}" => r"\d{8}",
+            "{time}" => r"\d{6}",
+            "{players}" => r"\d+",
+            _ => r"[A-Za-z0-9_-]+",
+        });
+        rest = &rest[i + ph.len()..];
+    }
+    pat.push_str(&regex::escape(rest));
+    pat.push('$');
+    regex::Regex::new(&pat).expect("escaped backup template")
+}
+
+// Archive the retention pruning may touch. A {server} value can make a template name look like a
+// safety archive ("crash-20240101-..."), so those prefixes are excluded explicitly.
+fn is_template_backup(name: &str, pattern: &regex::Regex) -> bool {
+    pattern.is_match(name) && !RESERVED_BACKUP_PREFIXES.iter().any(|p| name.starts_with(p))
+}
+
+fn validate_backup_template(t: &str) -> Result<(), String> {
+    // {date} and {time} keep names unique so a new archive never overwrites an older one
+    if !t.contains("{date}") || !t.contains("{time}") {
+        return Err("backup name template must contain both {date} and {time}".into());
+    }
+    if t.contains("..") || t.chars().any(|c| matches!(c, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|') || c.is_control()) {
+        return Err(format!("backup name template '{}' contains characters not allowed in a file name", t));
+    }
+    let literal = BACKUP_PLACEHOLDERS.iter().fold(t.to_string(), |acc, p| acc.replace(p, ""));
+    if literal.contains('{') || literal.contains('}') {
+        return Err(format!("unknown placeholder in '{}': use {{date}}, {{time}}, {{server}}, {{players}}", t));
+    }
+    if let Some(prefix) = RESERVED_BACKUP_PREFIXES.iter().find(|p| t.to_ascii_lowercase().starts_with(*p)) {
+        return Err(format!("backup name template '{}' starts with '{}', which is reserved for automatic safety archives", t, prefix));
+    }
+    Ok(())
+}
+
+// File name for the next automatic/manual backup; /info is only queried when the template needs it.
+async fn next_backup_name(rest: &RestState, cfg: &ApiConfig) -> String {
+    let template = backup_template(cfg);
+    let (mut server, mut players) = ("unknown".to_string(), 0);
+    if template.contains("{server}") || template.contains("{players}") {
+        if let Ok(info) = fetch_server_info(rest, cfg).await {
+            server = info.name;
+            players = info.players_online;
+        }
+    }
+    render_backup_name(template, Local::now(), &server, players)
+}
+
+/* ----------------------- save size history ----------------------- */
+const SAVE_SIZES_FILE: &str = "save_sizes.json";
+// past this the oldest SAVE_SIZE_ARCHIVE_BATCH move to a "save_sizes-<timestamp>.jsonl.gz" segment
+const SAVE_SIZE_MAX_SAMPLES: usize = 5000;
+const SAVE_SIZE_ARCHIVE_BATCH: usize = 1000;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct SaveSizeSample {
+    at: DateTime<Utc>,
+    bytes: u64,
+}
+
+// Uncompressed size of the save folder, skipping our own _backups output.
+fn dir_size(src: &Path) -> u64 {
+    let src_abs = src.canonicalize().unwrap_or_else(|_| src.to_path_buf());
+    let backups_dir = src_abs.join("_backups");
+    walkdir::WalkDir::new(&src_abs)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|e| e.file_type().is_file() && !e.path().starts_with(&backups_dir))
+        .filter_map(|e| e.metadata().ok())
+        .map(|m| m.len())
+        .sum()
+}
+
+// Append one sample per successful backup so the save-size trend can be charted.
+fn record_save_size(src: &Path) {
+    let mut samples: Vec<SaveSizeSample> = load_data(SAVE_SIZES_FILE).unwrap_or_default();
+    samples.push(SaveSizeSample { at: Utc::now(), bytes: dir_size(src) });
+    if samples.len() > SAVE_SIZE_MAX_SAMPLES {
+        let old: Vec<SaveSizeSample> = samples.drain(..SAVE_SIZE_ARCHIVE_BATCH).collect();
+        let _ = archive_records("save_sizes", &old);
+    }
+    save_data(SAVE_SIZES_FILE, &samples);
+}
+
+fn prune_old_backups(dir: &Path, days: u64, pattern: &regex::Regex) -> anyhow::Result<usize> {
+    remove_backups_older_than(dir, days, pattern).map(|(n, _)| n)
+}
+
+// Keep the newest `max` template-named archives by modified time; returns how many were deleted.
+fn prune_backups_by_count(dir: &Path, max: usize, pattern: &regex::Regex) -> usize {
+    let archives = template_backups(dir, pattern);
+    let excess = archives.len().saturating_sub(max);
+    archives.into_iter().take(excess).filter(|(path, _, _)| std::fs::remove_file(path).is_ok()).count()
+}
+
+// Delete template-named archives older than `days` (0 = all); returns (files, bytes) removed.
+fn remove_backups_older_than(dir: &Path, days: u64, pattern: &regex::Regex) -> anyhow::Result<(usize, u64)> {
+    let (mut removed, mut freed) = (0usize, 0u64);
+    if !dir.exists() { return Ok((0, 0)); }
+    let cutoff = std::time::SystemTime::now()
+        .checked_sub(Duration::from_secs(days.saturating_mul(86_400)))
+        .unwrap_or(std::time::SystemTime::UNIX_EPOCH);
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if path.is_file() {
+            let name = path.file_name().and_then(|s| s.to_str()).unwrap_or("");
+            // only archives named by the backup template; anything else in the folder is left alone
+            if !is_template_backup(name, pattern) { continue; }
+            let meta = entry.metadata().ok();
+            let modified = meta
+                .as_ref()
+                .and_then(|m| m.modified().ok())
+                .unwrap_or(std::time::SystemTime::UNIX_EPOCH);
+            if modified < cutoff && std::fs::remove_file(&path).is_ok() {
+                removed += 1;
+                freed += meta.map(|m| m.len()).unwrap_or(0);
+            }
+        }
+    }
+    Ok((removed, freed))
+}
+
+// Free bytes on the disk holding `path` (longest matching mount point); None if unknown.
+fn free_space(path: &Path) -> Option<u64> {
+    let path = path.canonicalize().ok()?;
+    let disks = sysinfo::Disks::new_with_refreshed_list();
+    disks
+        .list()
+        .iter()
+        .filter(|d| path.starts_with(d.mount_point()))
+        .max_by_key(|d| d.mount_point().as_os_str().len())
+        .map(|d| d.available_space())
+}
+
+// After a template change, archives named by the old template no longer match the prune pattern
+// and would be kept forever; say how many there are so they can be removed by hand.
+fn orphaned_backups_warning(cfg: &ApiConfig, old_template: &str) -> Option<String> {
+    let (_, dest_root) = resolve_backup_paths(cfg, None, None).ok()?;
+    let new_pattern = backup_name_regex(backup_template(cfg));
+    let orphaned = template_backups(&dest_root, &backup_name_regex(old_template))
+        .into_iter()
+        .filter(|(p, _, _)| !new_pattern.is_match(&p.file_name().map(|f| f.to_string_lossy().to_string()).unwrap_or_default()))
+        .count();
+    (orphaned > 0).then(|| {
+        format!(
+            "{} backup(s) in {} are named by the previous template '{}' and will no longer be pruned automatically",
+            orphaned,
+            dest_root.display(),
+            old_template
+        )
+    })
+}
+
+// Template-named archives in `dir`, oldest first, with their sizes.
+fn template_backups(dir: &Path, pattern: &regex::Regex) -> Vec<(PathBuf, std::time::SystemTime, u64)> {
+    let Ok(rd) = std::fs::read_dir(dir) else { return Vec::new() };
+    let mut out: Vec<_> = rd
+        .filter_map(Result::ok)
+        .filter(|e| is_template_backup(&e.file_name().to_string_lossy(), pattern))
+        .filter_map(|e| {
+            let meta = e.metadata().ok().filter(|m| m.is_file())?;
+            Some((e.path(), meta.modified().unwrap_or(std::time::SystemTime::UNIX_EPOCH), meta.len()))
+        })
+        .collect();
+    out.sort_by_key(|(_, modified, _)| *modified);
+    out
+}
+
+#[derive(Serialize, Debug)]
+struct BackupSpaceReport {
+    needed_bytes: u64,
+    free_before: Option<u64>,
+    free_after: Option<u64>,
+    freed_bytes: u64,
+    removed: usize,
+    ok: bool,
+}
+
+// Make room for the next backup before zipping. The size estimate is the newest archive (or the
+// uncompressed save folder when there is none). When free space is short only the configured
+// pruning runs early (age, then backup_max_count); nothing else is deleted to make room. `ok` is
+// false when that still isn't enough and the backup should be skipped; unknown free space counts as ok.
+fn ensure_backup_space(
+    src: &Path,
+    dest_root: &Path,
+    pattern: &regex::Regex,
+    retention_days: u64,
+    max_count: Option<usize>,
+) -> BackupSpaceReport {
+    let needed_bytes = template_backups(dest_root, pattern).last().map(|b| b.2).unwrap_or_else(|| dir_size(src));
+    let free_before = free_space(dest_root);
+    let mut report = BackupSpaceReport { needed_bytes, free_before, free_after: free_before, freed_bytes: 0, removed: 0, ok: true };
+    let Some(mut free) = free_before else { return report };
+    if free < needed_bytes {
+        if let Ok((n, bytes)) = remove_backups_older_than(dest_root, retention_days, pattern) {
+            report.removed += n;
+            report.freed_bytes += bytes;
+            free = free.saturating_add(bytes);
+        }
+    }
+    if let Some(max) = max_count.filter(|_| free < needed_bytes) {
+        let before: u64 = template_backups(dest_root, pattern).iter().map(|b| b.2).sum();
+        report.removed += prune_backups_by_count(dest_root, max, pattern);
+        let after: u64 = template_backups(dest_root, pattern).iter().map(|b| b.2).sum();
+        report.freed_bytes += before.saturating_sub(after);
+        free = free.saturating_add(before.saturating_sub(after));
+    }
+    report.free_after = free_space(dest_root).or(Some(free));
+    report.ok = report.free_after.is_none_or(|f| f >= needed_bytes);
+    report
+}
+
+fn backup_space_message(r: &BackupSpaceReport) -> String {
+    let mb = |b: u64| b as f64 / 1_048_576.0;
+    if r.ok {
+        format!("Low disk space: removed {} old backup(s), freed {:.1} MB before backing up.", r.removed, mb(r.freed_bytes))
+    } else {
+        format!(
+            "Not enough disk space for a backup: need ~{:.1} MB, {:.1} MB free after removing {} old backup(s) ({:.1} MB).",
+            mb(r.needed_bytes),
+            mb(r.free_after.unwrap_or(0)),
+            r.removed,
+            mb(r.freed_bytes)
+        )
+    }
+}
+
+// Run the pre-backup space check now and report what it freed.
+#[tauri::command]
+async fn check_backup_space(state: State<'_, AppState>) -> Result<BackupSpaceReport, String> {
+    let cfg = state.config.lock().clone();
+    if !cfg.allow_actions { return Err("actions disabled".into()); }
+    let (src, dest_root) = resolve_backup_paths(&cfg, None, None)?;
+    let pattern = backup_name_regex(backup_template(&cfg));
+    let retention = backup_retention_days(&cfg);
+    let max_count = cfg.backup_max_count.filter(|n| *n > 0);
+    let report = tokio::task::spawn_blocking(move || {
+        let _ = std::fs::create_dir_all(&dest_root);
+        ensure_backup_space(&src, &dest_root, &pattern, retention, max_count)
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+    if report.removed > 0 || !report.ok {
+        audit("backup_space", "", report.ok, Some(backup_space_message(&report)));
+        if let Some(h) = discord_hook(&state.rest, &cfg) {
+            discord_embed(&h, &backup_space_message(&report), if report.ok { COLOR_INFO } else { COLOR_ERROR }).await;
+        }
+    }
+    Ok(report)
+}
+
+/* ----------------------- save helpers ----------------------- */
+// Upper bound on how long a backup waits for its pre-backup save.
+const PRE_BACKUP_SAVE_TIMEOUT_SECS: u64 = 30;
+
+async fn request_save(rest: &RestState, client: &reqwest::Client, base: &str, login: &AdminLogin) -> anyhow::Result<reqwest::StatusCode> {
+    client
+        .post(format!("{}/save", v1_base(base)))
+        .timeout(rest.save_timeout())
+        .admin_auth(login)
+        .header(CONTENT_LENGTH, "0")
+        .header(CONNECTION, "close")
+        .header(ACCEPT, "*/*")
+        .header(USER_AGENT, "curl/8.13.0")
+        .send_limited(rest)
+        .await
+        .map(|r| r.status())
+}
+
+// Save the world before zipping so the archive reflects current state. Best-effort:
+// a failed or slow save is reported but never blocks the backup itself.
+async fn pre_backup_save(rest: &RestState, base: &str, login: &AdminLogin, hook: Option<DiscordHook>) {
+    if base.trim().is_empty() { return; }
+    let client = match rest.save_client() {
+        Ok(c) => c,
+        Err(_) => return,
+    };
+    let res = tokio::time::timeout(
+        Duration::from_secs(PRE_BACKUP_SAVE_TIMEOUT_SECS),
+        request_save(rest, &client, base, login),
+    )
+    .await;
+    if let Some(h) = hook {
+        let (msg, color) = match res {
+            Ok(Ok(s)) if s.is_success() => ("Pre-backup save completed.".to_string(), COLOR_INFO),
+            Ok(Ok(s)) => (format!("Pre-backup save failed: {} (backing up anyway)", s), COLOR_ERROR),
+            Ok(Err(e)) => (format!("Pre-backup save error: {} (backing up anyway)", e), COLOR_ERROR),
+            Err(_) => (format!("Pre-backup save timed out after {}s (backing up anyway)", PRE_BACKUP_SAVE_TIMEOUT_SECS), COLOR_ERROR),
+        };
+        discord_embed(&h, &msg, color).await;
+    }
+}
+
+/* ----------------------- background tasks ----------------------- */
+const AUTOSAVE_INTERVAL_SECS: u64 = 15 * 60;
+const DEFAULT_BACKUP_INTERVAL_MINS: u64 = 30;
+const DEFAULT_BACKUP_RETENTION_DAYS: u64 = 3;
+// Seconds before a restart/event at which in-game warnings go out.
+const COUNTDOWN_CHECKPOINTS: [u64; 5] = [60, 30, 20, 10, 5];
+const SHUTDOWN_WAIT_SECS: u64 = 120;
+
+fn spawn_daily_summary(rest: &RestState, summary: Arc<AtomicUsize>, beats: TaskBeats, daily: Arc<Mutex<DailyStats>>, cfg: &ApiConfig) {
+    let my_id = summary.fetch_add(1, Ordering::SeqCst) + 1;
+    let Some(hook) = discord_hook(rest, cfg) else { return };
+    let Some(at) = cfg
+        .daily_summary_time
+        .as_deref()
+        .and_then(|t| NaiveTime::parse_from_str(t.trim(), "%H:%M").ok())
+    else {
+        return;
+    };
+    tauri::async_runtime::spawn(async move {
+        task_started(&beats, "daily_summary", my_id, DAY_TASK_MAX_GAP_SECS);
+        loop {
+            let now = Local::now();
+            let Some(next) = next_fire_from(now, &[at]) else { break };
+            tokio::time::sleep(Duration::from_secs((next - now).num_seconds().max(1) as u64)).await;
+            if summary.load(Ordering::SeqCst) != my_id { break; }
+            task_ran(&beats, "daily_summary");
+            let stats = std::mem::take(&mut *daily.lock());
+            // failures are whatever the audit log recorded as failed over the past day
+            let to = Utc::now();
+            let errors = tokio::task::spawn_blocking(move || {
+                let mut n = 0;
+                let _ = for_each_audit_entry(to - chrono::Duration::days(1), to, |e| {
+                    n += usize::from(!e.ok);
+                    Ok(())
+                });
+                n
+            })
+            .await
+            .unwrap_or(0);
+            let msg = format!(
+                "Daily summary\nPeak players: {}\nUnique players seen: {}\nRestarts: {}\nBackups created: {}\nErrors: {}",
+                stats.peak_players,
+                stats.unique_players.len(),
+                stats.restarts,
+                stats.backups,
+                errors,
+            );
+            discord_embed(&hook, &msg, COLOR_REPORT).await;
+        }
+        task_ended(&beats, "daily_summary", my_id);
+    });
+}
+
+// Wait before the first autosave/backup. initial_delay_secs only applies to the first task since
+// launch (generation 1); a respawn from a later config change waits the full interval, so saving
+// settings with initial_delay_secs = 0 doesn't run a backup every time.
+fn first_wait(cfg: &ApiConfig, my_id: usize, interval: Duration) -> Duration {
+    match cfg.initial_delay_secs {
+        Some(secs) if my_id == 1 => Duration::from_secs(secs),
+        _ => interval,
     }
 }
 
-
+fn spawn_autosave(rest: &RestState, autosave: Arc<AtomicUsize>, beats: TaskBeats, cfg: &ApiConfig) {
+    let my_id = autosave.fetch_add(1, Ordering::SeqCst) + 1;
+    let base = cfg.base_url.clone();
+    let login = admin_login(cfg);
+    let hook = discord_hook(rest, cfg);
+    let interval = Duration::from_secs(AUTOSAVE_INTERVAL_SECS);
+    let mut wait = first_wait(cfg, my_id, interval);
+    if base.trim().is_empty() { return; }
+    let rest = rest.clone();
+    tauri::async_runtime::spawn(async move {
+        let client = match rest.save_client() {
+            Ok(c) => c,
+            Err(_) => return,
+        };
+        task_started(&beats, "autosave", my_id, 2 * wait.max(interval).as_secs());
+        loop {
+            // 15 minutes (first run after initial_delay_secs when set)
+            tokio::time::sleep(wait).await;
+            wait = interval;
+            if autosave.load(Ordering::SeqCst) != my_id { break; }
+            task_ran(&beats, "autosave");
+            // Discord log start (info)
+            if let Some(h) = hook.clone() { discord_embed(&h, "Auto save started.", COLOR_INFO).await; }
+            // Save request
+            let _ = request_save(&rest, &client, &base, &login).await;
+            if let Some(h) = hook.clone() { discord_embed(&h, "Auto save completed.", COLOR_SUCCESS).await; }
+        }
+        task_ended(&beats, "autosave", my_id);
+    });
+}
+
+// Standalone saves at fixed local times (save_schedule); no countdown, no restart.
+fn spawn_save_schedule(rest: &RestState, gen: Arc<AtomicUsize>, beats: TaskBeats, cfg: &ApiConfig) {
+    let my_id = gen.fetch_add(1, Ordering::SeqCst) + 1;
+    let times = parse_times_hhmm(cfg.save_schedule.as_deref().unwrap_or_default());
+    let base = cfg.base_url.clone();
+    let login = admin_login(cfg);
+    let hook = discord_hook(rest, cfg);
+    if times.is_empty() || base.trim().is_empty() { return; }
+    let rest = rest.clone();
+    tauri::async_runtime::spawn(async move {
+        let Ok(client) = rest.save_client() else { return };
+        task_started(&beats, "save_schedule", my_id, DAY_TASK_MAX_GAP_SECS);
+        loop {
+            let now = Local::now();
+            let Some(next) = next_fire_from(now, &times) else { break };
+            tokio::time::sleep(Duration::from_secs((next - now).num_seconds().max(1) as u64)).await;
+            if gen.load(Ordering::SeqCst) != my_id { break; }
+            task_ran(&beats, "save_schedule");
+            let (msg, color) = match request_save(&rest, &client, &base, &login).await {
+                Ok(s) if s.is_success() => (format!("Scheduled save ({}) completed.", next.format("%H:%M")), COLOR_SUCCESS),
+                Ok(s) => (format!("Scheduled save ({}) failed: {}", next.format("%H:%M"), s), COLOR_ERROR),
+                Err(e) => (format!("Scheduled save ({}) error: {}", next.format("%H:%M"), e), COLOR_ERROR),
+            };
+            if let Some(h) = hook.clone() { discord_embed(&h, &msg, color).await; }
+        }
+        task_ended(&beats, "save_schedule", my_id);
+    });
+}
+
+// Palworld has no separate MOTD setting; the server description shown in the browser stands in.
+const MOTD_SETTING_KEY: &str = "ServerDescription";
+
+fn parse_motd_schedule(entries: &[(String, String)]) -> Vec<(NaiveTime, String)> {
+    let mut out: Vec<(NaiveTime, String)> = entries
+        .iter()
+        .filter_map(|(t, text)| Some((NaiveTime::parse_from_str(t.trim(), "%H:%M").ok()?, text.clone())))
+        .collect();
+    out.sort_by_key(|(t, _)| *t);
+    out
+}
+
+// The entry in effect at `now`: the latest one at or before it, else the last one from yesterday.
+fn current_motd(entries: &[(NaiveTime, String)], now: NaiveTime) -> Option<&(NaiveTime, String)> {
+    entries.iter().rev().find(|(t, _)| *t <= now).or(entries.last())
+}
+
+async fn apply_motd(rest: &RestState, cfg: &ApiConfig, slot: NaiveTime, text: &str) {
+    let res = api_post_value(rest, cfg, "settings", Some(serde_json::json!({ MOTD_SETTING_KEY: text }))).await;
+    let (msg, color) = match &res {
+        Ok(_) => (format!("MOTD changed ({}): {}", slot.format("%H:%M"), text), COLOR_INFO),
+        Err(e) => (format!("Scheduled MOTD ({}) failed: {}", slot.format("%H:%M"), e), COLOR_ERROR),
+    };
+    if let Some(h) = discord_hook(rest, cfg) { discord_embed(&h, &msg, color).await; }
+}
+
+// Post the scheduled description at each entry's time; the same embed reports success or failure.
+// On start the entry already in effect is applied (when the server shows something else), and
+// servers without POST /settings stop the task once instead of failing at every slot. One that
+// can't be reached right now keeps the task; the next slot tries again.
+fn spawn_motd_schedule(rest: &RestState, gen: Arc<AtomicUsize>, beats: TaskBeats, cfg: &ApiConfig) {
+    let my_id = gen.fetch_add(1, Ordering::SeqCst) + 1;
+    let entries = parse_motd_schedule(cfg.motd_schedule.as_deref().unwrap_or_default());
+    if entries.is_empty() || cfg.base_url.trim().is_empty() { return; }
+    let times: Vec<NaiveTime> = entries.iter().map(|(t, _)| *t).collect();
+    let cfg = cfg.clone();
+    let rest = rest.clone();
+    tauri::async_runtime::spawn(async move {
+        task_started(&beats, "motd_schedule", my_id, DAY_TASK_MAX_GAP_SECS);
+        if let Ok(false) = settings_writes_supported(&rest, &cfg).await {
+            let e = "this server does not accept POST /settings";
+            audit("motd_schedule", &cfg.base_url, false, Some(e.into()));
+            task_failed(&beats, "motd_schedule", my_id, format!("MOTD schedule off: {}", e));
+            return;
+        }
+        if gen.load(Ordering::SeqCst) != my_id {
+            task_ended(&beats, "motd_schedule", my_id);
+            return;
+        }
+        if let Some((slot, text)) = current_motd(&entries, Local::now().time()) {
+            let shown = api_get_value(&rest, &cfg, "settings").await.ok().and_then(|v| v.get(MOTD_SETTING_KEY).cloned());
+            if shown.as_ref().and_then(Value::as_str) != Some(text.as_str()) {
+                apply_motd(&rest, &cfg, *slot, text).await;
+            }
+        }
+        loop {
+            let now = Local::now();
+            let Some(next) = next_fire_from(now, &times) else { break };
+            tokio::time::sleep(Duration::from_secs((next - now).num_seconds().max(1) as u64)).await;
+            if gen.load(Ordering::SeqCst) != my_id { break; }
+            task_ran(&beats, "motd_schedule");
+            let Some((_, text)) = entries.iter().find(|(t, _)| *t == next.time()) else { continue };
+            apply_motd(&rest, &cfg, next.time(), text).await;
+        }
+        task_ended(&beats, "motd_schedule", my_id);
+    });
+}
+
+#[tauri::command]
+fn get_motd_schedule(state: State<'_, AppState>) -> Vec<(String, String)> {
+    state.config.lock().motd_schedule.clone().unwrap_or_default()
+}
+
+// Replace the MOTD rotation; an empty list turns it off. Entries are stored in time order, and
+// a non-empty list is refused when the server can't write settings.
+#[tauri::command]
+async fn set_motd_schedule(state: State<'_, AppState>, entries: Vec<(String, String)>) -> Result<Vec<(String, String)>, String> {
+    let mut cleaned = Vec::with_capacity(entries.len());
+    for (t, text) in entries {
+        let time = NaiveTime::parse_from_str(t.trim(), "%H:%M")
+            .map_err(|_| format!("invalid MOTD time '{}': use HH:MM 24-hour format", t))?;
+        if text.trim().is_empty() {
+            return Err(format!("MOTD text for {} is empty", t.trim()));
+        }
+        cleaned.push((time, text.trim().to_string()));
+    }
+    cleaned.sort_by_key(|(t, _)| *t);
+    if cleaned.windows(2).any(|w| w[0].0 == w[1].0) {
+        return Err("two MOTD entries share the same time".into());
+    }
+    if !cleaned.is_empty() {
+        let cfg = state.config.lock().clone();
+        check_settings_writable(&state.rest, &cfg).await.map_err(|e| format!("MOTD schedule not saved: {}", e))?;
+    }
+    let stored: Vec<(String, String)> = cleaned.into_iter().map(|(t, text)| (t.format("%H:%M").to_string(), text)).collect();
+    let snapshot = {
+        let mut cfg = state.config.lock();
+        cfg.motd_schedule = Some(stored.clone()).filter(|s| !s.is_empty());
+        cfg.clone()
+    };
+    save_config(&snapshot);
+    if snapshot.allow_actions {
+        spawn_motd_schedule(&state.rest, state.motd_gen.clone(), state.task_beats.clone(), &snapshot);
+    } else {
+        let _ = state.motd_gen.fetch_add(1, Ordering::SeqCst);
+    }
+    audit("set_motd_schedule", "", true, Some(format!("{} entr(ies)", stored.len())));
+    Ok(stored)
+}
+
+/* ----------------------- settings events ----------------------- */
+const SETTINGS_EVENTS_FILE: &str = "settings_events.json";
+// A start or revert with failures is retried this soon instead of at the next boundary.
+const SETTINGS_EVENT_RETRY_SECS: i64 = 300;
+
+// A recurring window of settings changes, e.g. 2x XP from "Fri 18:00" to "Sun 23:59".
+// start/end are "Ddd HH:MM" (weekly) or "HH:MM" (daily); both must use the same form.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct SettingsEvent {
+    name: String,
+    start: String,
+    end: String,
+    changes: HashMap<String, Value>,
+}
+
+// Seconds into the week (weekly form) or day (daily form), plus the period length.
+fn event_boundary(s: &str) -> Option<(i64, i64)> {
+    let s = s.trim();
+    let (day, time) = match s.split_once(char::is_whitespace) {
+        Some((d, t)) => (Some(d.trim().parse::<Weekday>().ok()?), t.trim()),
+        None => (None, s),
+    };
+    let t = NaiveTime::parse_from_str(time, "%H:%M").ok()?;
+    let secs = t.num_seconds_from_midnight() as i64;
+    Some(match day {
+        Some(d) => (d.num_days_from_monday() as i64 * 86_400 + secs, 7 * 86_400),
+        None => (secs, 86_400),
+    })
+}
+
+fn validate_settings_event(e: &SettingsEvent) -> Result<((i64, i64), i64), String> {
+    if e.name.trim().is_empty() { return Err("settings event name is empty".into()); }
+    if e.changes.is_empty() { return Err(format!("settings event '{}' changes nothing", e.name)); }
+    let bad = |s: &str| format!("invalid time '{}' in '{}': use \"Fri 18:00\" or \"18:00\"", s, e.name);
+    let (start, p1) = event_boundary(&e.start).ok_or_else(|| bad(&e.start))?;
+    let (end, p2) = event_boundary(&e.end).ok_or_else(|| bad(&e.end))?;
+    if p1 != p2 { return Err(format!("'{}' mixes a weekday and a daily time", e.name)); }
+    if start == end { return Err(format!("'{}' starts and ends at the same time", e.name)); }
+    Ok(((start, end), p1))
+}
+
+// Position of `now` in the period (weekly or daily), in seconds.
+fn period_pos(now: DateTime<Local>, period: i64) -> i64 {
+    let day = now.time().num_seconds_from_midnight() as i64;
+    if period == 86_400 { day } else { now.weekday().num_days_from_monday() as i64 * 86_400 + day }
+}
+
+// Inside [start, end), wrapping past the end of the period.
+fn event_active_at(pos: i64, (start, end): (i64, i64)) -> bool {
+    if start < end { pos >= start && pos < end } else { pos >= start || pos < end }
+}
+
+// Failures already posted, by (event name, true for the start / false for the revert), with
+// the message; a retry that fails the same way stays quiet.
+type ReportedFailures = HashMap<(String, bool), String>;
+
+// True when this failure is new for the event and boundary, and records it.
+fn first_report(reported: &mut ReportedFailures, name: &str, start: bool, msg: &str) -> bool {
+    let key = (name.to_string(), start);
+    if reported.get(&key).is_some_and(|m| m == msg) { return false; }
+    reported.insert(key, msg.to_string());
+    true
+}
+
+// Bring the server in line with the schedule: start events whose window is open, revert
+// events that ended or were removed. Returns false if any change failed.
+async fn reconcile_settings_events(
+    rest: &RestState,
+    cfg: &ApiConfig,
+    events: &[SettingsEvent],
+    active: &Mutex<HashMap<String, HashMap<String, Value>>>,
+    reported: &mut ReportedFailures,
+) -> bool {
+    let now = Local::now();
+    let mut ok = true;
+    let hook = discord_hook(rest, cfg);
+    let open: HashSet<&str> = events
+        .iter()
+        .filter(|e| validate_settings_event(e).is_ok_and(|(win, period)| event_active_at(period_pos(now, period), win)))
+        .map(|e| e.name.as_str())
+        .collect();
+    // a start failure is forgotten once its window closes, a revert failure once it succeeds
+    reported.retain(|(name, start), _| if *start { open.contains(name.as_str()) } else { active.lock().contains_key(name) });
+    let running: Vec<String> = active.lock().keys().cloned().collect();
+    for name in running {
+        if open.contains(name.as_str()) { continue; }
+        let Some(prior) = active.lock().get(&name).cloned() else { continue };
+        let mut failed = Vec::new();
+        for (key, value) in &prior {
+            if api_post_value(rest, cfg, "settings", Some(serde_json::json!({ key.as_str(): value }))).await.is_err() {
+                failed.push(key.clone());
+            }
+        }
+        let msg = if failed.is_empty() { format!("Event ended: {}", name) } else { format!("Event {} ended, but reverting {} failed", name, failed.join(", ")) };
+        if failed.is_empty() {
+            let mut map = active.lock();
+            map.remove(&name);
+            save_data(SETTINGS_EVENTS_FILE, &*map);
+            reported.remove(&(name.clone(), false));
+        } else {
+            ok = false;
+            if !first_report(reported, &name, false, &msg) { continue; }
+        }
+        if failed.is_empty() { let _ = announce_multi(rest, &rest.client(), cfg, &msg).await; }
+        audit("settings_event_end", &name, failed.is_empty(), None);
+        if let Some(h) = hook.clone() { discord_embed(&h, &msg, if failed.is_empty() { COLOR_INFO } else { COLOR_ERROR }).await; }
+    }
+    for e in events {
+        let Ok((win, period)) = validate_settings_event(e) else { continue };
+        if !event_active_at(period_pos(now, period), win) || active.lock().contains_key(&e.name) { continue; }
+        let current = match api_get_value(rest, cfg, "settings").await {
+            Ok(v) => v,
+            Err(err) => {
+                ok = false;
+                let msg = format!("Event {} could not start: {}", e.name, err);
+                if first_report(reported, &e.name, true, &msg) {
+                    if let Some(h) = hook.clone() { discord_embed(&h, &msg, COLOR_ERROR).await; }
+                }
+                continue;
+            }
+        };
+        // without every prior value the event could never be reverted, so it doesn't start
+        let missing: Vec<&str> = e.changes.keys().filter(|k| current.get(k.as_str()).is_none()).map(|k| k.as_str()).collect();
+        if !missing.is_empty() {
+            ok = false;
+            let msg = format!("Event {} could not start: /settings has no current value for {}", e.name, missing.join(", "));
+            if first_report(reported, &e.name, true, &msg) {
+                audit("settings_event_start", &e.name, false, Some(msg.clone()));
+                if let Some(h) = hook.clone() { discord_embed(&h, &msg, COLOR_ERROR).await; }
+            }
+            continue;
+        }
+        let prior: HashMap<String, Value> = e.changes.keys().map(|k| (k.clone(), current[k.as_str()].clone())).collect();
+        let mut applied = Vec::new();
+        let mut failed = Vec::new();
+        for (key, value) in &e.changes {
+            match api_post_value(rest, cfg, "settings", Some(serde_json::json!({ key.as_str(): value }))).await {
+                Ok(_) => applied.push(key),
+                Err(_) => failed.push(key.clone()),
+            }
+        }
+        let summary: Vec<String> = e.changes.iter().map(|(k, v)| format!("{} = {}", k, v)).collect();
+        if failed.is_empty() {
+            // only a fully applied event is active; anything else is undone and retried
+            let mut map = active.lock();
+            map.insert(e.name.clone(), prior);
+            save_data(SETTINGS_EVENTS_FILE, &*map);
+        } else {
+            ok = false;
+            let mut stuck = Vec::new();
+            for key in applied {
+                if api_post_value(rest, cfg, "settings", Some(serde_json::json!({ key.as_str(): prior[key.as_str()] }))).await.is_err() {
+                    stuck.push(key.as_str());
+                }
+            }
+            let mut msg = format!("Event {} did not start: failed to set {}", e.name, failed.join(", "));
+            if !stuck.is_empty() {
+                msg.push_str(&format!("; undoing {} also failed, those keep the event value", stuck.join(", ")));
+            }
+            if first_report(reported, &e.name, true, &msg) {
+                audit("settings_event_start", &e.name, false, Some(msg.clone()));
+                if let Some(h) = hook.clone() { discord_embed(&h, &format!("{} (retrying)", msg), COLOR_ERROR).await; }
+            }
+            continue;
+        }
+        reported.remove(&(e.name.clone(), true));
+        let msg = format!("Event started: {} ({})", e.name, summary.join(", "));
+        let _ = announce_multi(rest, &rest.client(), cfg, &format!("Event started: {}", e.name)).await;
+        audit("settings_event_start", &e.name, true, None);
+        if let Some(h) = hook.clone() { discord_embed(&h, &msg, COLOR_SUCCESS).await; }
+    }
+    ok
+}
+
+// Events that change the same key must never be open at the same moment, or each would
+// restore the other's value. Checked minute by minute over a week (covers daily and weekly).
+fn settings_events_overlap(a: &SettingsEvent, b: &SettingsEvent) -> Option<String> {
+    let shared: Vec<&str> = a.changes.keys().filter(|k| b.changes.contains_key(*k)).map(|k| k.as_str()).collect();
+    if shared.is_empty() { return None; }
+    let ((wa, pa), (wb, pb)) = (validate_settings_event(a).ok()?, validate_settings_event(b).ok()?);
+    (0..7 * 86_400i64)
+        .step_by(60)
+        .any(|pos| event_active_at(pos % pa, wa) && event_active_at(pos % pb, wb))
+        .then(|| format!("'{}' and '{}' both change {} and their windows overlap", a.name, b.name, shared.join(", ")))
+}
+
+// Sleeps until the next event boundary (or a retry), then reconciles. Runs once right away so
+// an event whose window is already open starts, and one that ended while the app was closed reverts.
+fn spawn_settings_schedule(
+    rest: &RestState,
+    gen: Arc<AtomicUsize>,
+    beats: TaskBeats,
+    active: Arc<Mutex<HashMap<String, HashMap<String, Value>>>>,
+    cfg: &ApiConfig,
+) {
+    let my_id = gen.fetch_add(1, Ordering::SeqCst) + 1;
+    let events: Vec<SettingsEvent> = cfg.settings_schedule.clone().unwrap_or_default();
+    if cfg.base_url.trim().is_empty() || (events.is_empty() && active.lock().is_empty()) { return; }
+    let boundaries: Vec<(i64, i64)> = events
+        .iter()
+        .filter_map(|e| validate_settings_event(e).ok())
+        .flat_map(|((s, e), p)| [(s, p), (e, p)])
+        .collect();
+    let cfg = cfg.clone();
+    let rest = rest.clone();
+    tauri::async_runtime::spawn(async move {
+        task_started(&beats, "settings_schedule", my_id, 7 * 86_400);
+        if let Ok(false) = settings_writes_supported(&rest, &cfg).await {
+            let e = "this server does not accept POST /settings";
+            audit("settings_schedule", &cfg.base_url, false, Some(e.into()));
+            task_failed(&beats, "settings_schedule", my_id, format!("settings events off: {}", e));
+            return;
+        }
+        let mut reported = ReportedFailures::new();
+        loop {
+            if gen.load(Ordering::SeqCst) != my_id { break; }
+            task_ran(&beats, "settings_schedule");
+            let ok = reconcile_settings_events(&rest, &cfg, &events, &active, &mut reported).await;
+            let now = Local::now();
+            let next = boundaries
+                .iter()
+                .map(|&(b, p)| {
+                    let d = (b - period_pos(now, p)).rem_euclid(p);
+                    if d == 0 { p } else { d }
+                })
+                .min();
+            let wait = match (next, ok) {
+                (Some(d), true) => d,
+                (Some(d), false) => d.min(SETTINGS_EVENT_RETRY_SECS),
+                (None, true) => break,
+                (None, false) => SETTINGS_EVENT_RETRY_SECS,
+            };
+            tokio::time::sleep(Duration::from_secs(wait.max(1) as u64)).await;
+        }
+        task_ended(&beats, "settings_schedule", my_id);
+    });
+}
+
+#[tauri::command]
+fn get_settings_schedule(state: State<'_, AppState>) -> Vec<SettingsEvent> {
+    state.config.lock().settings_schedule.clone().unwrap_or_default()
+}
+
+// Replace the event list; an empty list turns it off. Running events that were removed are
+// reverted by the next reconcile, which happens right away.
+#[tauri::command]
+async fn set_settings_schedule(state: State<'_, AppState>, mut events: Vec<SettingsEvent>) -> Result<Vec<SettingsEvent>, String> {
+    for e in &mut events {
+        e.name = e.name.trim().to_string();
+    }
+    let mut names = HashSet::new();
+    for e in &events {
+        validate_settings_event(e)?;
+        if !names.insert(e.name.as_str()) {
+            return Err(format!("two settings events are named '{}'", e.name));
+        }
+    }
+    for (i, a) in events.iter().enumerate() {
+        if let Some(clash) = events[i + 1..].iter().find_map(|b| settings_events_overlap(a, b)) {
+            return Err(clash);
+        }
+    }
+    if !events.is_empty() {
+        let cfg = state.config.lock().clone();
+        check_settings_writable(&state.rest, &cfg).await.map_err(|e| format!("settings schedule not saved: {}", e))?;
+    }
+    let snapshot = {
+        let mut cfg = state.config.lock();
+        cfg.settings_schedule = Some(events.clone()).filter(|e| !e.is_empty());
+        cfg.clone()
+    };
+    save_config(&snapshot);
+    if snapshot.allow_actions {
+        spawn_settings_schedule(&state.rest, state.settings_event_gen.clone(), state.task_beats.clone(), state.settings_events.clone(), &snapshot);
+    } else {
+        let _ = state.settings_event_gen.fetch_add(1, Ordering::SeqCst);
+    }
+    audit("set_settings_schedule", "", true, Some(format!("{} event(s)", events.len())));
+    Ok(events)
+}
+
+// A run of failed backups is escalated once it reaches this length.
+const BACKUP_FAILURE_ALERT_AFTER: u32 = 3;
+
+#[derive(Clone, Debug, Default, Serialize)]
+struct BackupStreak {
+    consecutive_failures: u32,
+    last_success: Option<DateTime<Local>>,
+    last_error: Option<String>,
+}
+
+// Count one backup outcome (automatic or manual). Posts a single escalation when failures
+// reach BACKUP_FAILURE_ALERT_AFTER in a row, and a recovery note on the next success.
+async fn note_backup_result(streak: &Mutex<BackupStreak>, hook: Option<DiscordHook>, res: Result<(), String>) {
+    let alert = {
+        let mut s = streak.lock();
+        match res {
+            Ok(()) => {
+                let failed = std::mem::take(&mut s.consecutive_failures);
+                s.last_success = Some(Local::now());
+                s.last_error = None;
+                (failed >= BACKUP_FAILURE_ALERT_AFTER)
+                    .then(|| (format!("Backups recovered after {} failures in a row.", failed), COLOR_SUCCESS))
+            }
+            Err(e) => {
+                s.consecutive_failures += 1;
+                s.last_error = Some(e);
+                let last = s.last_success.map(|t| t.format("%Y-%m-%d %H:%M").to_string()).unwrap_or_else(|| "never (since the app started)".into());
+                (s.consecutive_failures == BACKUP_FAILURE_ALERT_AFTER).then(|| {
+                    (
+                        format!(
+                            "Backups have failed {} times in a row. Last successful backup: {}. Last error: {}",
+                            s.consecutive_failures,
+                            last,
+                            s.last_error.as_deref().unwrap_or("")
+                        ),
+                        COLOR_ERROR,
+                    )
+                })
+            }
+        }
+    };
+    if let (Some((msg, color)), Some(h)) = (alert, hook) {
+        let title = if color == COLOR_ERROR { "Backups failing" } else { "Backups recovered" };
+        discord_rich_embed(&h, serde_json::json!({ "title": title, "description": msg, "color": color })).await;
+    }
+}
+
+#[tauri::command]
+fn backup_failure_status(state: State<'_, AppState>) -> BackupStreak {
+    state.backup_streak.lock().clone()
+}
+
+fn backup_interval_secs(cfg: &ApiConfig) -> u64 {
+    cfg.backup_interval_minutes.filter(|m| *m > 0).unwrap_or(DEFAULT_BACKUP_INTERVAL_MINS).saturating_mul(60)
+}
+
+fn backup_retention_days(cfg: &ApiConfig) -> u64 {
+    cfg.backup_retention_days.filter(|d| *d > 0).unwrap_or(DEFAULT_BACKUP_RETENTION_DAYS)
+}
+
+fn spawn_backup(
+    rest: &RestState,
+    backup: Arc<AtomicUsize>,
+    beats: TaskBeats,
+    streak: Arc<Mutex<BackupStreak>>,
+    daily: Arc<Mutex<DailyStats>>,
+    cfg: &ApiConfig,
+) {
+    let my_id = backup.fetch_add(1, Ordering::SeqCst) + 1;
+    let src_opt = cfg.backup_dir.clone();
+    let dest_opt = cfg.backup_dest_dir.clone();
+    let hook = discord_hook(rest, cfg);
+    let save_first = cfg.backup_save_first;
+    let base = cfg.base_url.clone();
+    let login = admin_login(cfg);
+    if src_opt.is_none() { return; }
+    let src = PathBuf::from(src_opt.unwrap());
+    let dest_root = if let Some(d) = dest_opt { PathBuf::from(d) } else { src.join("_backups") };
+    let interval = Duration::from_secs(backup_interval_secs(cfg));
+    let retention = backup_retention_days(cfg);
+    let max_count = cfg.backup_max_count.filter(|n| *n > 0);
+    let mut wait = first_wait(cfg, my_id, interval);
+    let pattern = backup_name_regex(backup_template(cfg));
+    let cfg = cfg.clone();
+    let rest = rest.clone();
+    tauri::async_runtime::spawn(async move {
+        task_started(&beats, "backup", my_id, 2 * wait.max(interval).as_secs());
+        loop {
+            // backup interval (first run after initial_delay_secs when set)
+            tokio::time::sleep(wait).await;
+            wait = interval;
+            if backup.load(Ordering::SeqCst) != my_id { break; }
+            task_ran(&beats, "backup");
+            if save_first { pre_backup_save(&rest, &base, &login, hook.clone()).await; }
+            // Prepare output
+            let _ = std::fs::create_dir_all(&dest_root);
+            let space = {
+                let (src, dest_root, pattern) = (src.clone(), dest_root.clone(), pattern.clone());
+                match tokio::task::spawn_blocking(move || ensure_backup_space(&src, &dest_root, &pattern, retention, max_count)).await {
+                    Ok(r) => r,
+                    Err(e) => {
+                        note_backup_result(&streak, hook.clone(), Err(e.to_string())).await;
+                        continue;
+                    }
+                }
+            };
+            if space.removed > 0 || !space.ok {
+                if let Some(h) = hook.clone() {
+                    discord_embed(&h, &backup_space_message(&space), if space.ok { COLOR_INFO } else { COLOR_ERROR }).await;
+                }
+                if !space.ok {
+                    note_backup_result(&streak, hook.clone(), Err(backup_space_message(&space))).await;
+                    continue;
+                }
+            }
+            let dest = dest_root.join(next_backup_name(&rest, &cfg).await);
+            // Run zip
+            let result = zip_directory(&src, &dest, |_, _| {});
+            if result.is_ok() {
+                record_save_size(&src);
+                daily.lock().backups += 1;
+            }
+            note_backup_result(&streak, hook.clone(), result.as_ref().map(|_| ()).map_err(|e| e.to_string())).await;
+            if let Some(h) = hook.clone() {
+                match result {
+                    Ok(()) => {
+                        discord_embed(&h, &format!("Auto backup created: {}", dest.display()), COLOR_SUCCESS).await;
+                        match prune_old_backups(&dest_root, retention, &pattern) {
+                            Ok(n) if n > 0 => discord_embed(&h, &format!("Pruned {} backup(s) older than {} days.", n, retention), COLOR_INFO).await,
+                            Ok(_) => {}
+                            Err(e) => discord_embed(&h, &format!("Prune old backups failed: {}", e), COLOR_ERROR).await,
+                        }
+                        if let Some(max) = max_count {
+                            let n = prune_backups_by_count(&dest_root, max, &pattern);
+                            if n > 0 {
+                                discord_embed(&h, &format!("Pruned {} backup(s) beyond the newest {}.", n, max), COLOR_INFO).await;
+                            }
+                        }
+                    }
+                    Err(e) => discord_embed(&h, &format!("Auto backup failed: {}", e), COLOR_ERROR).await,
+                }
+            } else {
+                let _ = prune_old_backups(&dest_root, retention, &pattern);
+                if let Some(max) = max_count { prune_backups_by_count(&dest_root, max, &pattern); }
+            }
+        }
+        task_ended(&beats, "backup", my_id);
+    });
+}
+
+// Recurring in-game messages ("Join our Discord!"), keyed by id and persisted across launches.
+const RECURRING_FILE: &str = "recurring.json";
+const MIN_RECURRING_INTERVAL_SECS: u64 = 60;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct RecurringAnnounce {
+    id: String,
+    message: String,
+    interval_secs: u64,
+    // rotating messages (set_announce_pool); when set, `message` is unused
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pool: Vec<String>,
+    // index of the next pool message, persisted so a restart continues the rotation
+    #[serde(default)]
+    next: usize,
+}
+
+const ANNOUNCE_POOL_ID: &str = "pool";
+
+// Message for this tick; pool entries advance and persist their index.
+fn next_recurring_message(recurring: &Mutex<HashMap<String, RecurringAnnounce>>, id: &str) -> Option<String> {
+    let mut map = recurring.lock();
+    let entry = map.get_mut(id)?;
+    if entry.pool.is_empty() {
+        return Some(entry.message.clone());
+    }
+    let msg = entry.pool[entry.next % entry.pool.len()].clone();
+    entry.next = (entry.next + 1) % entry.pool.len();
+    save_data(RECURRING_FILE, &*map);
+    Some(msg)
+}
+
+// One generation covers every loop: any start/stop/config change respawns the whole set.
+fn spawn_recurring_announces(rest: &RestState, gen: Arc<AtomicUsize>, recurring: Arc<Mutex<HashMap<String, RecurringAnnounce>>>, cfg: &ApiConfig) {
+    let my_id = gen.fetch_add(1, Ordering::SeqCst) + 1;
+    if !cfg.allow_actions { return; }
+    let entries: Vec<(String, u64)> = recurring.lock().values().map(|e| (e.id.clone(), e.interval_secs)).collect();
+    for (id, interval_secs) in entries {
+        let gen = gen.clone();
+        let recurring = recurring.clone();
+        let cfg = cfg.clone();
+        let rest = rest.clone();
+        tauri::async_runtime::spawn(async move {
+            loop {
+                tokio::time::sleep(Duration::from_secs(interval_secs)).await;
+                if gen.load(Ordering::SeqCst) != my_id { break; }
+                let Some(msg) = next_recurring_message(&recurring, &id) else { break };
+                let _ = announce_multi(&rest, &rest.client(), &cfg, &msg).await;
+            }
+        });
+    }
+}
+
+fn restart_recurring_announces(state: &AppState, cfg: &ApiConfig) {
+    spawn_recurring_announces(&state.rest, state.recurring_gen.clone(), state.recurring.clone(), cfg);
+}
+
+const DEFAULT_ADMIN_USERNAME: &str = "admin";
+
+fn admin_username(cfg: &ApiConfig) -> &str {
+    cfg.admin_username.as_deref().map(str::trim).filter(|u| !u.is_empty()).unwrap_or(DEFAULT_ADMIN_USERNAME)
+}
+
+// Basic auth login handed to the REST helpers; an empty password sends no auth (admin_auth).
+#[derive(Clone, Debug)]
+struct AdminLogin {
+    user: String,
+    pass: String,
+}
+
+fn admin_login(cfg: &ApiConfig) -> AdminLogin {
+    AdminLogin { user: admin_username(cfg).to_string(), pass: auth_password(cfg) }
+}
+
+// Password to authenticate with; empty means "send no auth at all".
+fn auth_password(cfg: &ApiConfig) -> String {
+    if cfg.auth_required == Some(false) {
+        return String::new();
+    }
+    cfg.password.clone().unwrap_or_default()
+}
+
+// Basic auth header for fallback_password, when it is set and differs from the primary.
+fn fallback_auth(cfg: &ApiConfig) -> Option<String> {
+    if cfg.auth_required == Some(false) || cfg.fallback_password == cfg.password {
+        return None;
+    }
+    build_basic_header(admin_username(cfg), cfg.fallback_password.as_deref().unwrap_or(""))
+}
+
+// The primary password got a 401 and the fallback worked: make the fallback the primary in
+// config.json. The config watcher then applies it like any external edit; a promotion that
+// already happened (the file no longer holds the old pair) is left alone.
+async fn promote_fallback_password(rest: &RestState, cfg: &ApiConfig) {
+    let Some(mut saved) = load_saved_config() else { return };
+    if saved.base_url != cfg.base_url || saved.password != cfg.password || saved.fallback_password != cfg.fallback_password {
+        return;
+    }
+    saved.password = saved.fallback_password.take();
+    save_config(&saved);
+    audit("promote_fallback_password", &cfg.base_url, true, None);
+    if let Some(h) = discord_hook(rest, cfg) {
+        discord_embed(&h, "The admin password was rejected; the fallback password worked and is now the primary.", COLOR_INFO).await;
+    }
+}
+
+fn build_basic_header(username: &str, password: &str) -> Option<String> {
+    if password.is_empty() {
+        return None;
+    }
+    let creds = format!("{}:{}", username, password);
+    Some(format!("Basic {}", B64.encode(creds.as_bytes())))
+}
+
+// Some local servers run the REST API without auth and reject an empty password,
+// so basic auth is only attached when there's something to send.
+trait AdminAuth {
+    fn admin_auth(self, login: &AdminLogin) -> Self;
+}
+impl AdminAuth for reqwest::RequestBuilder {
+    fn admin_auth(self, login: &AdminLogin) -> Self {
+        if login.pass.is_empty() { self } else { self.basic_auth(&login.user, Some(&login.pass)) }
+    }
+}
+
+const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 8;
+
+// Shared by every REST call to the game server; lives in AppState and is handed to the helpers
+// and background tasks that send requests (cheap to clone).
+#[derive(Clone)]
+struct RestState {
+    // (permits, semaphore) bounding requests in flight across every command and task. Swapped
+    // for a new one when the limit changes; requests already waiting finish on the old one.
+    limit: Arc<RwLock<(usize, Arc<tokio::sync::Semaphore>)>>,
+    // failed api_get_value/api_post_value calls, newest last (RECENT_ERRORS_MAX); None for
+    // probes, whose failures are the answer rather than an issue
+    errors: Option<Arc<Mutex<VecDeque<ErrorRecord>>>>,
+    // HTTP prefs from the config and the shared client built with them, so polls reuse pooled
+    // connections; rebuilt by set_http_prefs when the prefs change
+    http: Arc<RwLock<(HttpPrefs, reqwest::Client)>>,
+    // broadcast health across every announce_multi caller
+    announce: Arc<Mutex<AnnounceStatus>>,
+}
+
+impl Default for RestState {
+    fn default() -> Self {
+        let n = DEFAULT_MAX_CONCURRENT_REQUESTS;
+        Self {
+            limit: Arc::new(RwLock::new((n, Arc::new(tokio::sync::Semaphore::new(n))))),
+            errors: Some(Arc::default()),
+            http: Arc::new(RwLock::new((DEFAULT_HTTP_PREFS, build_http_client(DEFAULT_HTTP_PREFS)))),
+            announce: Arc::default(),
+        }
+    }
+}
+
+impl RestState {
+    fn set_limit(&self, cfg: &ApiConfig) {
+        let n = cfg.max_concurrent_requests.filter(|n| *n > 0).unwrap_or(DEFAULT_MAX_CONCURRENT_REQUESTS);
+        let mut limit = self.limit.write();
+        if limit.0 != n {
+            *limit = (n, Arc::new(tokio::sync::Semaphore::new(n)));
+        }
+    }
+
+    // Same request limit, but failures are not added to recent_errors.
+    fn probe(&self) -> RestState {
+        RestState { errors: None, ..self.clone() }
+    }
+
+    // Cheap handle to the shared client (reqwest::Client is an Arc internally).
+    fn client(&self) -> reqwest::Client {
+        self.http.read().1.clone()
+    }
+
+    // Fresh client for the save/restart paths.
+    fn save_client(&self) -> reqwest::Result<reqwest::Client> {
+        client_builder(self.http.read().0, true).build()
+    }
+
+    fn timeout_secs(&self) -> u64 {
+        self.http.read().0 .2
+    }
+
+    fn save_timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout_secs().max(SAVE_REQUEST_TIMEOUT_SECS))
+    }
+
+    fn note_error(&self, method: &str, path: &str, e: &anyhow::Error) {
+        let Some(errors) = &self.errors else { return };
+        let mut errors = errors.lock();
+        if errors.len() == RECENT_ERRORS_MAX {
+            errors.pop_front();
+        }
+        errors.push_back(ErrorRecord { at: Utc::now(), operation: format!("{} {}", method, path), error: ApiError::classify(e) });
+    }
+}
+
+// REST calls to the game server send through this instead of send() (Discord posts don't).
+// Errors go through request_error, so every timeout reads "timed out after Ns".
+trait SendLimited {
+    async fn send_limited(self, rest: &RestState) -> anyhow::Result<reqwest::Response>;
+}
+impl SendLimited for reqwest::RequestBuilder {
+    async fn send_limited(self, rest: &RestState) -> anyhow::Result<reqwest::Response> {
+        let (client, req) = self.build_split();
+        let req = req?;
+        let timeout_secs = req.timeout().map(Duration::as_secs).unwrap_or_else(|| rest.timeout_secs());
+        let limit = rest.limit.read().1.clone();
+        let _permit = limit.acquire_owned().await;
+        client.execute(req).await.map_err(|e| request_error(e, timeout_secs))
+    }
+}
+
+fn candidate_urls(base: &str, path: &str) -> Vec<String> {
+    let p = path.trim_start_matches('/');
+    let b = base.trim_end_matches('/');
+    let mut v = Vec::new();
+    v.push(format!("{}/{}", b, p)); // if base already has /v1/api
+    if !b.ends_with("/v1/api") {
+        v.push(format!("{}/v1/api/{}", b, p));
+    }
+    v
+}
+
+// Errors from the data commands. Serialized as their message so the frontend's string handling
+// keeps working; NotConfigured has a fixed text the UI can match to show setup instead of an error.
+// The other variants keep the failure class for recent_errors.
+#[derive(Clone, Debug, thiserror::Error)]
+enum ApiError {
+    #[error("not configured: set the server base URL in Settings")]
+    NotConfigured,
+    #[error("{0}")]
+    Timeout(String),
+    #[error("{0}")]
+    Connect(String),
+    #[error("{message}")]
+    Http { status: u16, message: String },
+    #[error("{0}")]
+    Decode(String),
+    #[error("{0}")]
+    Other(String),
+}
+
+impl ApiError {
+    fn kind(&self) -> &'static str {
+        match self {
+            ApiError::NotConfigured => "not_configured",
+            ApiError::Timeout(_) => "timeout",
+            ApiError::Connect(_) => "connect",
+            ApiError::Http { .. } => "http_status",
+            ApiError::Decode(_) => "decode",
+            ApiError::Other(_) => "other",
+        }
+    }
+
+    fn status(&self) -> Option<u16> {
+        match self {
+            ApiError::Http { status, .. } => Some(*status),
+            _ => None,
+        }
+    }
+
+    fn classify(e: &anyhow::Error) -> Self {
+        if let Some(api) = e.downcast_ref::<ApiError>() {
+            return api.clone();
+        }
+        let message = e.to_string();
+        match error_kind(e) {
+            "timeout" => ApiError::Timeout(message),
+            "connect" => ApiError::Connect(message),
+            "decode" => ApiError::Decode(message),
+            "http_status" => match error_status(e) {
+                Some(status) => ApiError::Http { status: status.as_u16(), message },
+                None => ApiError::Other(message),
+            },
+            _ => ApiError::Other(message),
+        }
+    }
+}
+
+impl Serialize for ApiError {
+    fn serialize<S: serde::Serializer>(&self, s: S) -> std::result::Result<S::Ok, S::Error> {
+        s.serialize_str(&self.to_string())
+    }
+}
+
+impl From<anyhow::Error> for ApiError {
+    fn from(e: anyhow::Error) -> Self {
+        ApiError::classify(&e)
+    }
+}
+
+#[tauri::command]
+fn is_configured(state: State<'_, AppState>) -> bool {
+    !state.config.lock().base_url.trim().is_empty()
+}
+
+// Try a base URL and credentials against /info without touching the saved config, so the
+// settings form can check them first. A 401 gets its own message: wrong password, not wrong URL.
+#[tauri::command]
+async fn test_connection(
+    state: State<'_, AppState>,
+    base_url: String,
+    password: Option<String>,
+    admin_username: Option<String>,
+) -> Result<ServerInfo, String> {
+    let cfg = ApiConfig {
+        base_url: normalize_base_url(&base_url),
+        password: password.filter(|p| !p.is_empty()),
+        admin_username,
+        field_mappings: state.config.lock().field_mappings.clone(),
+        ..ApiConfig::default()
+    };
+    if cfg.base_url.is_empty() {
+        return Err("base_url is empty".into());
+    }
+    match api_get_value(&state.rest.probe(), &cfg, "info").await {
+        Ok(v) => Ok(coerce_server_info(&v, cfg.field_mappings.as_ref())),
+        Err(e) => Err(connection_error_message(&e)),
+    }
+}
+
+fn connection_error_message(e: &anyhow::Error) -> String {
+    let text = e.to_string();
+    if error_status(e) == Some(reqwest::StatusCode::UNAUTHORIZED) {
+        return "unauthorized (401): the server rejected the username or password".into();
+    }
+    match error_kind(e) {
+        "http_status" => format!("the server answered with an error: {}", text),
+        "timeout" => format!("{}; check the URL, or raise request_timeout_secs if the server is just slow", text),
+        "connect" => format!("could not connect (refused or unreachable); check the URL and port: {}", text),
+        "decode" => format!("the server answered but not with the REST API's JSON; check the URL: {}", text),
+        _ => format!("connection failed: {}", text),
+    }
+}
+
+/* ----------------------- recent errors ----------------------- */
+const RECENT_ERRORS_MAX: usize = 200;
+
+#[derive(Clone, Debug)]
+struct ErrorRecord {
+    at: DateTime<Utc>,
+    operation: String, // "GET players", "POST announce", ...
+    error: ApiError,
+}
+
+// The error is spelled out as kind/status/message; ApiError itself serializes as its message.
+impl Serialize for ErrorRecord {
+    fn serialize<S: serde::Serializer>(&self, s: S) -> std::result::Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+        let mut st = s.serialize_struct("ErrorRecord", 5)?;
+        st.serialize_field("at", &self.at)?;
+        st.serialize_field("operation", &self.operation)?;
+        st.serialize_field("kind", self.error.kind())?;
+        st.serialize_field("status", &self.error.status())?;
+        st.serialize_field("message", &self.error.to_string())?;
+        st.end()
+    }
+}
+
+fn error_kind(e: &anyhow::Error) -> &'static str {
+    match e.downcast_ref::<reqwest::Error>() {
+        Some(r) if r.is_timeout() => "timeout",
+        Some(r) if r.is_connect() => "connect",
+        Some(r) if r.is_decode() => "decode",
+        Some(r) if r.is_status() => "http_status",
+        Some(_) => "request",
+        None if e.is::<TimedOut>() => "timeout",
+        None if e.is::<HttpStatusError>() => "http_status",
+        None => "other",
+    }
+}
+
+// The last `limit` (default 50) REST failures, newest first, for an issues panel.
+#[tauri::command]
+fn recent_errors(state: State<'_, AppState>, limit: Option<usize>) -> Vec<ErrorRecord> {
+    let Some(errors) = &state.rest.errors else { return Vec::new() };
+    errors.lock().iter().rev().take(limit.unwrap_or(50)).cloned().collect()
+}
+
+async fn api_get_value(rest: &RestState, cfg: &ApiConfig, path: &str) -> Result<Value> {
+    if cfg.base_url.trim().is_empty() {
+        return Err(ApiError::NotConfigured.into());
+    }
+    let client = rest.client();
+    let auth = build_basic_header(admin_username(cfg), &auth_password(cfg));
+    let fallback = fallback_auth(cfg);
+    let urls = candidate_urls(&cfg.base_url, path);
+    let send = |url: &str, auth: Option<&String>| {
+        let mut req = client.get(url);
+        if let Some(h) = auth {
+            req = req.header("Authorization", h);
+        }
+        req.send_limited(rest)
+    };
+
+    let mut last_err: Option<anyhow::Error> = None;
+    for url in urls {
+        let mut res = send(&url, auth.as_ref()).await;
+        if fallback.is_some() && matches!(&res, Ok(r) if r.status() == reqwest::StatusCode::UNAUTHORIZED) {
+            res = send(&url, fallback.as_ref()).await;
+            if res.as_ref().is_ok_and(|r| r.status().is_success()) {
+                promote_fallback_password(rest, cfg).await;
+            }
+        }
+        match res {
+            Ok(resp) if resp.status().is_success() => {
+                return Ok(resp.json::<Value>().await?);
+            }
+            Ok(resp) => last_err = Some(HttpStatusError { method: "GET", url, status: resp.status() }.into()),
+            Err(e) => last_err = Some(e),
+        }
+    }
+    let err = last_err.unwrap_or_else(|| anyhow::anyhow!("no URL worked"));
+    rest.note_error("GET", path, &err);
+    Err(err)
+}
+
+async fn api_post_value(
+    rest: &RestState,
+    cfg: &ApiConfig,
+    path: &str,
+    body: Option<serde_json::Value>,
+) -> Result<serde_json::Value> {
+    if cfg.base_url.trim().is_empty() {
+        return Err(ApiError::NotConfigured.into());
+    }
+    let client = rest.client();
+    let auth = build_basic_header(admin_username(cfg), &auth_password(cfg));
+    let fallback = fallback_auth(cfg);
+    let urls = candidate_urls(&cfg.base_url, path);
+    let send = |url: &str, auth: Option<&String>| {
+        let mut req = client.post(url);
+        if let Some(h) = auth {
+            req = req.header(reqwest::header::AUTHORIZATION, h);
+        }
+        match &body {
+            Some(b) => {
+                req = req.json(b);
+            }
+            None => {
+                // many servers require CL:0 for /save
+                req = req.header(reqwest::header::CONTENT_LENGTH, "0");
+            }
+        }
+        req.send_limited(rest)
+    };
+
+    let mut last_err: Option<anyhow::Error> = None;
+    for url in urls {
+        let mut res = send(&url, auth.as_ref()).await;
+        if fallback.is_some() && matches!(&res, Ok(r) if r.status() == reqwest::StatusCode::UNAUTHORIZED) {
+            res = send(&url, fallback.as_ref()).await;
+            if res.as_ref().is_ok_and(|r| r.status().is_success()) {
+                promote_fallback_password(rest, cfg).await;
+            }
+        }
+        match res {
+            Ok(resp) if resp.status().is_success() => {
+                return Ok(resp
+                    .json::<serde_json::Value>()
+                    .await
+                    .unwrap_or(serde_json::Value::Null));
+            }
+            Ok(resp) => last_err = Some(HttpStatusError { method: "POST", url, status: resp.status() }.into()),
+            Err(e) => last_err = Some(e),
+        }
+    }
+    let err = last_err.unwrap_or_else(|| anyhow::anyhow!("no URL worked"));
+    rest.note_error("POST", path, &err);
+    Err(err)
+}
+
+fn s_alt(v: &Value, keys: &[&str]) -> Option<String> {
+    for k in keys {
+        if let Some(s) = v.get(*k).and_then(|x| x.as_str()) {
+            return Some(s.to_string());
+        }
+    }
+    None
+}
+fn u_alt(v: &Value, keys: &[&str]) -> Option<usize> {
+    for k in keys {
+        if let Some(n) = v.get(*k) {
+            if let Some(u) = n.as_u64() {
+                return Some(u as usize);
+            }
+            if let Some(f) = n.as_f64() {
+                return Some(f as usize);
+            }
+            if let Some(s) = n.as_str().and_then(|t| t.parse::<usize>().ok()) {
+                return Some(s);
+            }
+        }
+    }
+    None
+}
+fn u64_alt(v: &Value, keys: &[&str]) -> Option<u64> {
+    for k in keys {
+        if let Some(n) = v.get(*k) {
+            if let Some(u) = n.as_u64() {
+                return Some(u);
+            }
+            if let Some(f) = n.as_f64() {
+                return Some(f as u64);
+            }
+            if let Some(s) = n.as_str().and_then(|t| t.parse::<u64>().ok()) {
+                return Some(s);
+            }
+        }
+    }
+    None
+}
+
+// Config-provided aliases for a logical field first, then the built-in defaults.
+fn field_keys<'a>(fm: Option<&'a FieldMappings>, field: &str, defaults: &[&'a str]) -> Vec<&'a str> {
+    let mut keys: Vec<&str> = fm
+        .and_then(|m| m.get(field))
+        .map(|v| v.iter().map(String::as_str).collect())
+        .unwrap_or_default();
+    keys.extend_from_slice(defaults);
+    keys
+}
+
+fn coerce_server_info(v: &Value, fm: Option<&FieldMappings>) -> ServerInfo {
+    let root = v.get("data").unwrap_or(v);
+    let name = s_alt(root, &field_keys(fm, "server_name", &["servername", "name", "serverName"]))
+        .unwrap_or_else(|| "Unknown".into());
+    let map = s_alt(root, &field_keys(fm, "map", &["map", "world", "World"]));
+    let maxp = u_alt(root, &field_keys(fm, "max_players", &["max_players", "maxPlayers", "MaxPlayers"]));
+    let up = u64_alt(root, &field_keys(fm, "uptime", &["uptime", "uptimeSeconds", "Uptime"]));
+    let version_keys = field_keys(fm, "version", &["version", "serverVersion", "build", "Version"]);
+    // some builds report a bare build number
+    let version = s_alt(root, &version_keys).or_else(|| u64_alt(root, &version_keys).map(|n| n.to_string()));
+    let mut players_online =
+        u_alt(root, &field_keys(fm, "players_online", &["players_online", "playersOnline", "currentPlayers"])).unwrap_or(0);
+    if players_online == 0 {
+        if let Some(arr) = root.get("players").and_then(|x| x.as_array()) {
+            players_online = arr.len();
+        } else if let Some(obj) = root.get("players").and_then(|x| x.as_object()) {
+            players_online = obj.len();
+        }
+    }
+    ServerInfo {
+        name,
+        map,
+        players_online,
+        max_players: maxp,
+        uptime_seconds: up,
+        version,
+    }
+}
+// In-game day counter and clock; either may be missing depending on server version.
+#[derive(Debug, Default, Serialize)]
+struct GameTime {
+    day: Option<u64>,
+    time: Option<String>,
+}
+
+fn coerce_game_time(v: &Value, fm: Option<&FieldMappings>) -> GameTime {
+    let root = v.get("data").unwrap_or(v);
+    GameTime {
+        day: u64_alt(root, &field_keys(fm, "game_day", &["days", "day", "gameDay", "inGameDay"])),
+        time: s_alt(root, &field_keys(fm, "game_time", &["time", "gameTime", "inGameTime"])),
+    }
+}
+
+// "steam_7656..." or a bare standalone 17-digit SteamID64 (the regex crate has no look-around,
+// so the digit boundaries are matched explicitly).
+static STEAM_ID_RE: Lazy<regex::Regex> =
+    Lazy::new(|| regex::Regex::new(r"(?i)steam_?(\d{17})|(?:^|\D)(\d{17})(?:\D|$)").expect("valid steam id regex"));
+
+// Some servers use prefixes like "steam_7656..."; normalize to steam_<17-digits> when present.
+fn normalize_player_id(raw: &str) -> String {
+    let id = raw.trim();
+    STEAM_ID_RE
+        .captures(id)
+        .and_then(|caps| caps.get(1).or_else(|| caps.get(2)))
+        .map(|m| format!("steam_{}", m.as_str()))
+        .unwrap_or_else(|| id.to_string())
+}
+
+// Re-key a stored map through normalize_player_id. Ids saved before normalization existed
+// ("76561198..." vs "steam_76561198...") can collide; `merge` combines (kept, other).
+fn normalize_id_keys<V>(map: HashMap<String, V>, merge: impl Fn(V, V) -> V) -> (HashMap<String, V>, bool) {
+    let mut changed = false;
+    let mut out: HashMap<String, V> = HashMap::with_capacity(map.len());
+    for (raw, v) in map {
+        let id = normalize_player_id(&raw);
+        changed |= id != raw;
+        let v = match out.remove(&id) {
+            Some(kept) => merge(kept, v),
+            None => v,
+        };
+        out.insert(id, v);
+    }
+    (out, changed)
+}
+
+// Normalize the ids stored in tracker.json, watchlist.json and bans.json. Runs on every start
+// but only rewrites a file when one of its ids was not normalized yet.
+fn migrate_player_ids() {
+    if let Some(file) = load_data::<TrackerFile>(TRACKER_FILE) {
+        // the name of whichever spelling was seen last is the current one
+        let seen_at = |raw: &str| file.last_seen.get(raw).copied().unwrap_or(DateTime::<Utc>::MIN_UTC);
+        let names: HashMap<String, (DateTime<Utc>, String)> =
+            file.names.iter().map(|(id, n)| (id.clone(), (seen_at(id), n.clone()))).collect();
+        let (names, a) = normalize_id_keys(names, |kept, other| if other.0 > kept.0 { other } else { kept });
+        let (first_seen, b) = normalize_id_keys(file.first_seen, |x, y| x.min(y));
+        let (last_seen, c) = normalize_id_keys(file.last_seen, |x, y| x.max(y));
+        let mut sessions = file.sessions;
+        let mut d = false;
+        for s in sessions.iter_mut() {
+            let id = normalize_player_id(&s.id);
+            d |= id != s.id;
+            s.id = id;
+        }
+        if a || b || c || d {
+            let names = names.into_iter().map(|(id, (_, n))| (id, n)).collect();
+            save_data(TRACKER_FILE, &TrackerFile { first_seen, last_seen, names, sessions });
+        }
+    }
+    if let Some(list) = load_data::<HashMap<String, WatchEntry>>(WATCHLIST_FILE) {
+        let (mut list, changed) = normalize_id_keys(list, |kept, _| kept);
+        if changed {
+            for (id, e) in list.iter_mut() {
+                e.id = id.clone();
+            }
+            save_data(WATCHLIST_FILE, &list);
+        }
+    }
+    if let Some(bans) = load_data::<HashMap<String, BanRecord>>(BANS_FILE) {
+        // a permanent ban outlasts a temp one, and a later expiry an earlier one
+        let (mut bans, changed) = normalize_id_keys(bans, |kept, other| match (kept.expires_at, other.expires_at) {
+            (Some(k), Some(o)) if o > k => other,
+            (Some(_), None) => other,
+            _ => kept,
+        });
+        if changed {
+            for (id, b) in bans.iter_mut() {
+                b.id = id.clone();
+            }
+            save_data(BANS_FILE, &bans);
+        }
+    }
+}
+
+// Non-Steam ids the REST API reports: Xbox/Game Pass "gdk_<digits>" user ids and 32-hex playerIds.
+static OTHER_ID_RE: Lazy<regex::Regex> =
+    Lazy::new(|| regex::Regex::new(r"(?i)^(?:gdk_\d+|[0-9a-f]{32})$").expect("valid player id regex"));
+
+#[derive(Serialize, Debug, Default, PartialEq)]
+struct NormalizedIds {
+    ids: Vec<String>,
+    unparseable: Vec<String>,
+}
+
+fn normalize_id_list(raw: &str) -> NormalizedIds {
+    let mut out = NormalizedIds::default();
+    let mut seen = HashSet::new();
+    for token in raw.split(|c: char| c.is_whitespace() || matches!(c, ',' | ';')) {
+        let token = token.trim_matches(|c: char| matches!(c, '"' | '\'' | '(' | ')' | '[' | ']' | '<' | '>'));
+        if token.is_empty() { continue; }
+        let id = if STEAM_ID_RE.is_match(token) {
+            normalize_player_id(token)
+        } else if OTHER_ID_RE.is_match(token) {
+            token.to_string()
+        } else {
+            if !out.unparseable.iter().any(|u| u == token) { out.unparseable.push(token.to_string()); }
+            continue;
+        };
+        if seen.insert(id.clone()) { out.ids.push(id); }
+    }
+    out
+}
+
+// Clean up a pasted list of ids (from logs, chat, spreadsheets) for ban_many: split on
+// whitespace, commas and semicolons, canonicalize Steam ids, dedupe, and report what didn't parse.
+#[tauri::command]
+fn normalize_ids(raw: String) -> NormalizedIds {
+    normalize_id_list(&raw)
+}
+
+fn player_from_obj(v: &Value, fm: Option<&FieldMappings>) -> Option<Player> {
+    // Prefer explicit userId (often "steam_7656...") over numeric playerId/hex ids
+    let id_keys = field_keys(fm, "player_id", &["userId", "user_id", "steamId", "SteamID", "steam_id", "id", "playerId", "uid"]);
+    let id = s_alt(v, &id_keys).or_else(|| {
+        v.get("steamId")
+            .and_then(|x| x.as_u64().map(|n| n.to_string()))
+            .or_else(|| v.get("id").and_then(|x| x.as_u64().map(|n| n.to_string())))
+            .or_else(|| v.get("playerId").and_then(|x| x.as_u64().map(|n| n.to_string())))
+    })?;
+    let id = normalize_player_id(&id);
+    let name = s_alt(v, &field_keys(fm, "player_name", &["name", "playerName", "characterName", "displayName"]))
+        .unwrap_or_else(|| "Unknown".into());
+    let level = u_alt(v, &field_keys(fm, "level", &["level", "lvl"])).map(|x| x as u32);
+    let ping = u_alt(v, &field_keys(fm, "ping", &["ping", "latency"])).map(|x| x as u32);
+    // try to read connected seconds from common keys
+    let connected_seconds = field_keys(
+        fm,
+        "connected_seconds",
+        &["connected_seconds", "connectedSeconds", "sessionSeconds", "playTimeSec", "playTimeSeconds"],
+    )
+    .into_iter()
+    .find_map(|k| v.get(k).and_then(|x| x.as_i64()));
+    let platform = infer_platform(&id).to_string();
+    Some(Player {
+        id,
+        name,
+        level,
+        ping,
+        connected_seconds,
+        platform,
+    })
+}
+
+// Steam ids normalize to steam_<17 digits>; anything else is a Game Pass/Xbox (or unknown) id.
+fn infer_platform(id: &str) -> &'static str {
+    if STEAM_ID_RE.is_match(id) { "Steam" } else { "Other/Xbox" }
+}
+
+async fn server_is_up(rest: &RestState, base: &str, login: &AdminLogin) -> bool {
+    let client = rest.client();
+    for url in candidate_urls(base, "info") {
+        let req = client.get(&url).admin_auth(login);
+        if let Ok(resp) = req.send_limited(rest).await {
+            if resp.status().is_success() { return true; }
+        }
+    }
+    false
+}
+
+const DEFAULT_QUERY_PORT: u16 = 27015;
+const QUERY_TIMEOUT_SECS: u64 = 3;
+const QUERY_ATTEMPTS: usize = 2;
+// Steam A2S_INFO request; the server answers with an info (0x49) or challenge (0x41) packet.
+const A2S_INFO_REQUEST: &[u8] = b"\xFF\xFF\xFF\xFFTSource Engine Query\0";
+
+// Send A2S_INFO to the Steam query port on the REST host. The game itself only speaks UDP and
+// ignores stray packets, so an answer on the query port is the reachability signal players
+// see in the server browser. Ok(false) when nothing answers within the timeout.
+async fn steam_query_answers(base: &str, port: u16) -> Result<bool, String> {
+    let url = reqwest::Url::parse(base).map_err(|e| format!("invalid base_url: {}", e))?;
+    let host = url.host_str().ok_or("base_url has no host")?.trim_matches(|c| c == '[' || c == ']').to_string();
+    let addr = tokio::net::lookup_host((host.as_str(), port))
+        .await
+        .map_err(|e| format!("could not resolve {}: {}", host, e))?
+        .next()
+        .ok_or_else(|| format!("could not resolve {}", host))?;
+    let bind = if addr.is_ipv6() { "[::]:0" } else { "0.0.0.0:0" };
+    let socket = tokio::net::UdpSocket::bind(bind).await.map_err(|e| e.to_string())?;
+    socket.connect(addr).await.map_err(|e| e.to_string())?;
+    let mut buf = [0u8; 1400];
+    for _ in 0..QUERY_ATTEMPTS {
+        socket.send(A2S_INFO_REQUEST).await.map_err(|e| e.to_string())?;
+        match tokio::time::timeout(Duration::from_secs(QUERY_TIMEOUT_SECS), socket.recv(&mut buf)).await {
+            Ok(Ok(n)) if is_a2s_reply(&buf[..n]) => return Ok(true),
+            // ICMP port unreachable surfaces as a recv error: nothing is listening
+            Ok(Err(_)) => return Ok(false),
+            _ => {}
+        }
+    }
+    Ok(false)
+}
+
+fn is_a2s_reply(packet: &[u8]) -> bool {
+    packet.len() >= 5 && packet[..4] == [0xFF; 4] && matches!(packet[4], 0x49 | 0x41)
+}
+
+async fn wait_for_server_down(rest: &RestState, base: &str, login: &AdminLogin, max_secs: u64) -> bool {
+    let mut waited = 0u64;
+    loop {
+        if !server_is_up(rest, base, login).await { return true; }
+        if waited >= max_secs { return false; }
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        waited += 1;
+    }
+}
+fn coerce_players(v: &Value, fm: Option<&FieldMappings>) -> Vec<Player> {
+    dedup_players(coerce_players_raw(v, fm))
+}
+
+// Every entry of the response, before duplicates are merged.
+fn coerce_players_raw(v: &Value, fm: Option<&FieldMappings>) -> Vec<Player> {
+    let root = v.get("data").unwrap_or(v);
+    let collect = |vv: &Value| -> Vec<Player> {
+        if let Some(arr) = vv.as_array() {
+            arr.iter().filter_map(|p| player_from_obj(p, fm)).collect()
+        } else if let Some(obj) = vv.as_object() {
+            obj.values().filter_map(|p| player_from_obj(p, fm)).collect()
+        } else {
+            vec![]
+        }
+    };
+    if let Some(pl) = root.get("players") {
+        collect(pl)
+    } else {
+        collect(root)
+    }
+}
+
+// Some builds list one player twice (numeric and steam_ id forms); ids are already canonical
+// here, so merge entries sharing one, keeping the first position and any non-null fields.
+fn dedup_players(players: Vec<Player>) -> Vec<Player> {
+    let mut out: Vec<Player> = Vec::with_capacity(players.len());
+    let mut index: HashMap<String, usize> = HashMap::new();
+    for p in players {
+        match index.get(&p.id) {
+            Some(&i) => {
+                let kept = &mut out[i];
+                kept.level = kept.level.or(p.level);
+                kept.ping = kept.ping.filter(|n| *n > 0).or(p.ping).or(kept.ping);
+                kept.connected_seconds = kept.connected_seconds.or(p.connected_seconds);
+                if kept.name.is_empty() {
+                    kept.name = p.name;
+                }
+            }
+            None => {
+                index.insert(p.id.clone(), out.len());
+                out.push(p);
+            }
+        }
+    }
+    out
+}
+
+/* --------------------- announce helpers --------------------- */
+
+async fn post_json(rest: &RestState, client: &reqwest::Client, v1: &str, login: &AdminLogin, path: &str, msg: &str) -> bool {
+    client
+        .post(&format!("{}/{}", v1, path))
+        .admin_auth(login)
+        .header(CONTENT_TYPE, "application/json")
+        .body(format!(r#"{{"message":"{}"}}"#, msg))
+        .send_limited(rest)
+        .await
+        .map(|r| r.status().is_success())
+        .unwrap_or(false)
+}
+async fn post_text(rest: &RestState, client: &reqwest::Client, v1: &str, login: &AdminLogin, path: &str, msg: &str) -> bool {
+    client
+        .post(&format!("{}/{}", v1, path))
+        .admin_auth(login)
+        .header(CONTENT_TYPE, "text/plain; charset=utf-8")
+        .body(msg.to_string())
+        .send_limited(rest)
+        .await
+        .map(|r| r.status().is_success())
+        .unwrap_or(false)
+}
+async fn get_query(rest: &RestState, client: &reqwest::Client, v1: &str, login: &AdminLogin, path: &str, msg: &str) -> bool {
+    client
+        .get(&format!("{}/{path}?message={}", v1, encode(msg)))
+        .admin_auth(login)
+        .send_limited(rest)
+        .await
+        .map(|r| r.status().is_success())
+        .unwrap_or(false)
+}
+// Broadcast health across every announce_multi caller, surfaced by get_announce_status.
+#[derive(Clone, Debug, Default, Serialize)]
+struct AnnounceStatus {
+    consecutive_failures: u32,
+    failing: bool, // threshold reached and not yet recovered
+    last_success: Option<DateTime<Utc>>,
+    last_failure: Option<DateTime<Utc>>,
+}
+
+// Broadcast to the server in `cfg`; a streak of announce_failure_threshold failures is reported
+// to its webhook once, and again when broadcasts recover.
+async fn announce_multi(rest: &RestState, client: &reqwest::Client, cfg: &ApiConfig, msg: &str) -> bool {
+    let ok = announce_any_shape(rest, client, &cfg.base_url, &admin_login(cfg), msg).await;
+    let threshold = cfg.announce_failure_threshold.filter(|n| *n > 0);
+    // decide under the lock, post after releasing it
+    let alert = {
+        let mut st = rest.announce.lock();
+        if ok {
+            st.consecutive_failures = 0;
+            st.last_success = Some(Utc::now());
+            std::mem::take(&mut st.failing).then_some(("In-game broadcasts are working again.", COLOR_SUCCESS))
+        } else {
+            st.consecutive_failures += 1;
+            st.last_failure = Some(Utc::now());
+            let tripped = !st.failing && threshold.is_some_and(|n| st.consecutive_failures >= n);
+            st.failing |= tripped;
+            tripped.then_some(("In-game broadcasts are failing: every announce endpoint was rejected.", COLOR_ERROR))
+        }
+    };
+    if let (Some((desc, color)), Some(h)) = (alert, discord_hook(rest, cfg)) {
+        discord_embed(&h, desc, color).await;
+    }
+    ok
+}
+
+#[tauri::command]
+fn get_announce_status(state: State<'_, AppState>) -> AnnounceStatus {
+    state.rest.announce.lock().clone()
+}
+
+async fn announce_any_shape(rest: &RestState, client: &reqwest::Client, base: &str, login: &AdminLogin, msg: &str) -> bool {
+    let v1 = v1_base(base);
+    for path in ["announce", "broadcast"] {
+        if post_json(rest, client, &v1, login, path, msg).await {
+            return true;
+        }
+        if post_text(rest, client, &v1, login, path, msg).await {
+            return true;
+        }
+        if get_query(rest, client, &v1, login, path, msg).await {
+            return true;
+        }
+    }
+    false
+}
+
+/* ----------------------- Tauri commands ----------------------- */
+
+#[tauri::command]
+fn get_config(state: State<'_, AppState>) -> ApiConfig {
+    state.config.lock().clone()
+}
+
+// Factory reset: copy config.json to config.bak-<ts>.json, stop every background task and
+// pending restart, then start over from defaults. keep_server_connection carries over the
+// base URL and passwords so the app stays connected.
+#[tauri::command]
+fn reset_config(state: State<'_, AppState>, keep_server_connection: bool) -> Result<ApiConfig, String> {
+    let path = config_path().ok_or("no app data folder")?;
+    let backup = path.with_file_name(format!("config.bak-{}.json", Local::now().format("%Y%m%d-%H%M%S")));
+    if path.exists() {
+        std::fs::copy(&path, &backup).map_err(|e| format!("could not back up config.json: {}", e))?;
+    }
+    for gen in [
+        &state.sched,
+        &state.autosave_gen,
+        &state.save_sched_gen,
+        &state.motd_gen,
+        &state.settings_event_gen,
+        &state.backup_gen,
+        &state.summary_gen,
+        &state.countdown_gen,
+        &state.ws_gen,
+        &state.recurring_gen,
+    ] {
+        gen.fetch_add(1, Ordering::SeqCst);
+    }
+    RESTART_GEN.fetch_add(1, Ordering::SeqCst);
+
+    let snapshot = {
+        let mut cfg = state.config.lock();
+        let mut fresh = ApiConfig::default();
+        if keep_server_connection {
+            fresh.base_url = cfg.base_url.clone();
+            fresh.password = cfg.password.clone();
+            fresh.fallback_password = cfg.fallback_password.clone();
+            fresh.admin_username = cfg.admin_username.clone();
+        }
+        *cfg = fresh;
+        cfg.clone()
+    };
+    save_config(&snapshot);
+    apply_config(&state, &snapshot);
+    audit("reset_config", &backup.to_string_lossy(), true, Some(format!("kept connection: {}", keep_server_connection)));
+    Ok(snapshot)
+}
+
+// Whether mutating commands would currently be accepted, so the UI can gray them out up front.
+#[derive(Debug, Serialize)]
+struct ActionStatus {
+    allowed: bool,
+    reason: Option<String>,
+    // automation that is off while manual actions still work
+    restarts_suspended_until: Option<DateTime<Local>>, // only while in the future
+    autosave_enabled: bool,
+    backup_enabled: bool,
+}
+
+#[tauri::command]
+fn action_status(state: State<'_, AppState>) -> ActionStatus {
+    let cfg = state.config.lock();
+    let reason = if !cfg.allow_actions {
+        Some("Actions are disabled in settings (read-only mode).".to_string())
+    } else if cfg.base_url.trim().is_empty() {
+        Some("No server configured: set the base URL first.".to_string())
+    } else {
+        None
+    };
+    ActionStatus {
+        allowed: reason.is_none(),
+        reason,
+        restarts_suspended_until: cfg.restarts_suspended_until.map(|u| u.with_timezone(&Local)).filter(|u| *u > Local::now()),
+        autosave_enabled: cfg.autosave_enabled,
+        backup_enabled: cfg.backup_enabled,
+    }
+}
+
+// ApiConfig with every default resolved to the value actually in use.
+#[derive(Debug, Serialize)]
+struct EffectiveConfig {
+    base_url: String,
+    auth_enabled: bool,
+    admin_username: String,
+    request_timeout_secs: u64,
+    restart_times: Vec<String>, // only the entries that parse as HH:MM
+    restart_schedule: Option<HashMap<String, Vec<String>>>, // takes precedence over restart_times
+    start_cmd: Option<String>,
+    backup_dir: Option<String>,
+    backup_dest_dir: Option<String>, // <backup_dir>/_backups when not set explicitly
+    discord_webhook: Option<String>,
+    allow_actions: bool,
+    autosave_enabled: bool,
+    autosave_interval_secs: u64,
+    autosave_initial_delay_secs: u64,
+    backup_enabled: bool,
+    backup_interval_secs: u64,
+    backup_initial_delay_secs: u64,
+    backup_retention_days: u64,
+    backup_max_count: Option<usize>,
+    backup_save_first: bool,
+    backup_before_restart: bool,
+    backup_on_crash: bool,
+    backup_name_template: String,
+    countdown_checkpoints: Vec<u64>,
+    quiet_hours: Option<(String, String)>,
+    daily_summary_time: Option<String>,
+    profiles: usize,
+    query_port: u16,
+}
+
+#[tauri::command]
+fn get_effective_config(state: State<'_, AppState>) -> EffectiveConfig {
+    let cfg = state.config.lock().clone();
+    let auth_enabled = !auth_password(&cfg).is_empty();
+    let backup_dest_dir = cfg.backup_dest_dir.clone().or_else(|| {
+        cfg.backup_dir
+            .as_ref()
+            .map(|d| PathBuf::from(d).join("_backups").to_string_lossy().to_string())
+    });
+    let quiet_hours = quiet_hours(&cfg)
+        .map(|(a, b)| (a.format("%H:%M").to_string(), b.format("%H:%M").to_string()));
+    EffectiveConfig {
+        auth_enabled,
+        admin_username: admin_username(&cfg).to_string(),
+        request_timeout_secs: request_timeout_secs(&cfg),
+        restart_times: parse_times_hhmm(&cfg.restart_times)
+            .iter()
+            .map(|t| t.format("%H:%M").to_string())
+            .collect(),
+        restart_schedule: cfg.restart_schedule.clone(),
+        backup_dest_dir,
+        autosave_interval_secs: AUTOSAVE_INTERVAL_SECS,
+        autosave_initial_delay_secs: cfg.initial_delay_secs.unwrap_or(AUTOSAVE_INTERVAL_SECS),
+        backup_interval_secs: backup_interval_secs(&cfg),
+        backup_initial_delay_secs: cfg.initial_delay_secs.unwrap_or(backup_interval_secs(&cfg)),
+        backup_retention_days: backup_retention_days(&cfg),
+        backup_max_count: cfg.backup_max_count.filter(|n| *n > 0),
+        backup_name_template: backup_template(&cfg).to_string(),
+        countdown_checkpoints: COUNTDOWN_CHECKPOINTS.to_vec(),
+        quiet_hours,
+        daily_summary_time: cfg.daily_summary_time.clone(),
+        profiles: cfg.profiles.len(),
+        query_port: cfg.query_port.unwrap_or(DEFAULT_QUERY_PORT),
+        base_url: cfg.base_url,
+        start_cmd: cfg.start_cmd,
+        backup_dir: cfg.backup_dir,
+        discord_webhook: cfg.discord_webhook,
+        allow_actions: cfg.allow_actions,
+        autosave_enabled: cfg.autosave_enabled,
+        backup_enabled: cfg.backup_enabled,
+        backup_save_first: cfg.backup_save_first,
+        backup_before_restart: cfg.backup_before_restart,
+        backup_on_crash: cfg.backup_on_crash,
+    }
+}
+
+// set_config's argument. base_url is required; any other field left out keeps its current value.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct ConfigPatch {
+    base_url: String,
+    password: Option<String>,
+    restart_times: Option<Vec<String>>,
+    start_cmd: Option<String>,
+    backup_dir: Option<String>,
+    backup_dest_dir: Option<String>,
+    discord_webhook: Option<String>,
+    allow_actions: Option<bool>,
+    profiles: Option<Vec<ServerProfile>>,
+    backup_save_first: Option<bool>,
+    field_mappings: Option<FieldMappings>,
+    auth_required: Option<bool>,
+    quiet_hours_start: Option<String>,
+    quiet_hours_end: Option<String>,
+    daily_summary_time: Option<String>,
+    initial_delay_secs: Option<u64>,
+    ws_listen: Option<String>,
+    query_port: Option<u16>,
+    announce_failure_threshold: Option<u32>,
+    backup_name_template: Option<String>,
+    shell_override: Option<String>,
+    storage_limit_mb: Option<u64>,
+    backup_before_restart: Option<bool>,
+    capacity_alert_percent: Option<u8>,
+    restart_complete_message: Option<String>,
+    max_concurrent_requests: Option<usize>,
+    restart_schedule: Option<HashMap<String, Vec<String>>>,
+    save_schedule: Option<Vec<String>>,
+    force_http1: Option<bool>,
+    disable_keepalive: Option<bool>,
+    save_settle_secs: Option<u64>,
+    start_cmd_cwd: Option<String>,
+    server_process_name: Option<String>,
+    backup_on_crash: Option<bool>,
+    fallback_password: Option<String>,
+    backup_interval_minutes: Option<u64>,
+    backup_retention_days: Option<u64>,
+    backup_max_count: Option<usize>,
+    admin_username: Option<String>,
+    request_timeout_secs: Option<u64>,
+    allow_custom_webhook: Option<bool>,
+    ntp_check: Option<bool>,
+}
+
+#[tauri::command]
+fn set_config(state: State<'_, AppState>, patch: ConfigPatch) -> Result<Vec<String>, String> {
+    let ConfigPatch {
+        mut base_url,
+        password,
+        restart_times,
+        start_cmd,
+        backup_dir,
+        backup_dest_dir,
+        discord_webhook,
+        allow_actions,
+        profiles,
+        backup_save_first,
+        field_mappings,
+        auth_required,
+        quiet_hours_start,
+        quiet_hours_end,
+        daily_summary_time,
+        initial_delay_secs,
+        ws_listen,
+        query_port,
+        announce_failure_threshold,
+        backup_name_template,
+        shell_override,
+        storage_limit_mb,
+        backup_before_restart,
+        capacity_alert_percent,
+        restart_complete_message,
+        max_concurrent_requests,
+        restart_schedule,
+        save_schedule,
+        force_http1,
+        disable_keepalive,
+        save_settle_secs,
+        start_cmd_cwd,
+        server_process_name,
+        backup_on_crash,
+        fallback_password,
+        backup_interval_minutes,
+        backup_retention_days,
+        backup_max_count,
+        admin_username,
+        request_timeout_secs,
+        allow_custom_webhook,
+        ntp_check,
+    } = patch;
+    // normalize URL
+    base_url = normalize_base_url(&base_url);
+    if base_url.is_empty() {
+        return Err("base_url is empty".into());
+    }
+    // quiet hours: "" clears, anything else must be HH:MM
+    for t in [&quiet_hours_start, &quiet_hours_end].into_iter().flatten() {
+        if !t.trim().is_empty() && NaiveTime::parse_from_str(t.trim(), "%H:%M").is_err() {
+            return Err(format!("invalid quiet hours time '{}': use HH:MM 24-hour format", t));
+        }
+    }
+    if let Some(t) = &daily_summary_time {
+        if !t.trim().is_empty() && NaiveTime::parse_from_str(t.trim(), "%H:%M").is_err() {
+            return Err(format!("invalid daily summary time '{}': use HH:MM 24-hour format", t));
+        }
+    }
+    if let Some(map) = &restart_schedule {
+        validate_restart_schedule(map)?;
+    }
+    // webhook: "" clears; custom-domain proxies need allow_custom_webhook (saved with the config).
+    // The webhook already saved is not re-checked, so startup never fails on it.
+    let (saved_webhook, saved_allow_custom) = {
+        let c = state.config.lock();
+        (c.discord_webhook.clone(), c.allow_custom_webhook)
+    };
+    let discord_webhook = match discord_webhook.map(|h| h.trim().to_string()) {
+        Some(h) if h.is_empty() => Some(None),
+        Some(h) if saved_webhook.as_deref() == Some(h.as_str()) => Some(Some(h)),
+        Some(h) => Some(Some(check_discord_webhook(&h, allow_custom_webhook.unwrap_or(saved_allow_custom))?)),
+        None => None,
+    };
+    for t in save_schedule.iter().flatten() {
+        if NaiveTime::parse_from_str(t.trim(), "%H:%M").is_err() {
+            return Err(format!("invalid save time '{}': use HH:MM 24-hour format", t));
+        }
+    }
+    // basic auth splits user and password at the first ':'
+    if admin_username.as_deref().is_some_and(|u| u.contains(':')) {
+        return Err("admin_username must not contain ':'".into());
+    }
+    if request_timeout_secs == Some(0) {
+        return Err("request_timeout_secs must be at least 1".into());
+    }
+    if backup_interval_minutes == Some(0) {
+        return Err("backup_interval_minutes must be at least 1".into());
+    }
+    // 0 days would prune the archive that was just written
+    if backup_retention_days == Some(0) {
+        return Err("backup_retention_days must be at least 1".into());
+    }
+    if capacity_alert_percent.is_some_and(|p| p > 100) {
+        return Err("capacity_alert_percent must be between 1 and 100 (0 turns it off)".into());
+    }
+    if let Some(t) = &backup_name_template {
+        if !t.trim().is_empty() {
+            validate_backup_template(t.trim())?;
+        }
+    }
+    let mut path_warnings = Vec::new();
+    let (backup_dir, backup_dest_dir) = if backup_dir.is_some() || backup_dest_dir.is_some() {
+        let current = state.config.lock().clone();
+        check_backup_paths(backup_dir, backup_dest_dir, &current, &mut path_warnings)?
+    } else {
+        (None, None)
+    };
+    let profiles = match profiles {
+        Some(list) => {
+            let mut out = Vec::with_capacity(list.len());
+            for mut p in list {
+                p.base_url = normalize_base_url(&p.base_url);
+                if p.base_url.is_empty() {
+                    return Err(format!("profile '{}' has an empty base_url", p.name));
+                }
+                out.push(p);
+            }
+            Some(out)
+        }
+        None => None,
+    };
+    // only once every other check has passed
+    if let Some(d) = backup_dest_dir.as_deref().filter(|d| !d.trim().is_empty()) {
+        std::fs::create_dir_all(d).map_err(|e| format!("could not create backup destination {}: {}", d, e))?;
+    }
+
+    // update config under lock, then take a snapshot and drop the lock
+    let previous_template = backup_template(&state.config.lock()).to_string();
+    let snapshot: ApiConfig = {
+        let mut cfg = state.config.lock();
+        cfg.base_url = base_url;
+        if password.is_some() { cfg.password = password; }
+        if let Some(u) = admin_username { cfg.admin_username = Some(u.trim().to_string()).filter(|u| !u.is_empty()); }
+        if let Some(p) = fallback_password { cfg.fallback_password = Some(p).filter(|p| !p.is_empty()); }
+        if let Some(t) = restart_times { cfg.restart_times = t; }
+        if start_cmd.is_some() { cfg.start_cmd = start_cmd; }
+        if backup_dir.is_some() { cfg.backup_dir = backup_dir; }
+        if backup_dest_dir.is_some() { cfg.backup_dest_dir = backup_dest_dir; }
+        if let Some(v) = allow_actions { cfg.allow_actions = v; }
+        if let Some(h) = discord_webhook { cfg.discord_webhook = h; }
+        if let Some(v) = allow_custom_webhook { cfg.allow_custom_webhook = v; }
+        if let Some(p) = profiles { cfg.profiles = p; }
+        if let Some(v) = backup_save_first { cfg.backup_save_first = v; }
+        if field_mappings.is_some() { cfg.field_mappings = field_mappings; }
+        if auth_required.is_some() { cfg.auth_required = auth_required; }
+        if let Some(t) = quiet_hours_start { cfg.quiet_hours_start = Some(t).filter(|t| !t.trim().is_empty()); }
+        if let Some(t) = quiet_hours_end { cfg.quiet_hours_end = Some(t).filter(|t| !t.trim().is_empty()); }
+        if let Some(t) = daily_summary_time { cfg.daily_summary_time = Some(t).filter(|t| !t.trim().is_empty()); }
+        if initial_delay_secs.is_some() { cfg.initial_delay_secs = initial_delay_secs; }
+        if let Some(a) = ws_listen { cfg.ws_listen = Some(a).filter(|a| !a.trim().is_empty()); }
+        if query_port.is_some() { cfg.query_port = query_port; }
+        if announce_failure_threshold.is_some() { cfg.announce_failure_threshold = announce_failure_threshold; }
+        if backup_interval_minutes.is_some() { cfg.backup_interval_minutes = backup_interval_minutes; }
+        if backup_retention_days.is_some() { cfg.backup_retention_days = backup_retention_days; }
+        if backup_max_count.is_some() { cfg.backup_max_count = backup_max_count.filter(|n| *n > 0); }
+        if let Some(t) = backup_name_template { cfg.backup_name_template = Some(t.trim().to_string()).filter(|t| !t.is_empty()); }
+        if let Some(s) = shell_override { cfg.shell_override = Some(s.trim().to_string()).filter(|s| !s.is_empty()); }
+        if storage_limit_mb.is_some() { cfg.storage_limit_mb = storage_limit_mb; }
+        if let Some(v) = backup_before_restart { cfg.backup_before_restart = v; }
+        if let Some(v) = backup_on_crash { cfg.backup_on_crash = v; }
+        if capacity_alert_percent.is_some() { cfg.capacity_alert_percent = capacity_alert_percent.filter(|p| *p > 0); }
+        if let Some(m) = restart_complete_message { cfg.restart_complete_message = Some(m.trim().to_string()); }
+        if max_concurrent_requests.is_some() { cfg.max_concurrent_requests = max_concurrent_requests.filter(|n| *n > 0); }
+        if let Some(m) = restart_schedule { cfg.restart_schedule = Some(m).filter(|m| !m.is_empty()); }
+        if let Some(t) = save_schedule { cfg.save_schedule = Some(t).filter(|t| !t.is_empty()); }
+        if force_http1.is_some() { cfg.force_http1 = force_http1; }
+        if disable_keepalive.is_some() { cfg.disable_keepalive = disable_keepalive; }
+        if ntp_check.is_some() { cfg.ntp_check = ntp_check; }
+        if save_settle_secs.is_some() { cfg.save_settle_secs = save_settle_secs; }
+        if request_timeout_secs.is_some() { cfg.request_timeout_secs = request_timeout_secs; }
+        if let Some(d) = start_cmd_cwd { cfg.start_cmd_cwd = Some(d.trim().to_string()).filter(|d| !d.is_empty()); }
+        if let Some(n) = server_process_name { cfg.server_process_name = Some(n.trim().to_string()).filter(|n| !n.is_empty()); }
+        cfg.clone()
+    };
+
+    save_config(&snapshot);
+    apply_config(&state, &snapshot);
+    // schedule conflicts and a missing backup source or start folder are saved anyway, just reported back
+    let mut warnings = config_schedule_warnings(&snapshot);
+    warnings.extend(path_warnings);
+    if previous_template != backup_template(&snapshot) {
+        warnings.extend(orphaned_backups_warning(&snapshot, &previous_template));
+    }
+    if let Some(dir) = snapshot.start_cmd.as_deref().and_then(|c| start_cwd(c, snapshot.start_cmd_cwd.as_deref())) {
+        if !dir.is_dir() {
+            warnings.push(format!("start_cmd working directory {} does not exist; starts will fail", dir.display()));
+        }
+    }
+    Ok(warnings)
+}
+
+#[derive(Serialize)]
+struct SchedulerDebug {
+    times_by_day: Vec<(String, Vec<String>)>, // Mon..Sun, as the scheduler parsed them
+    next_fire: Option<DateTime<Local>>,
+    generation: usize,
+    active: bool, // a scheduler task of the current generation is running
+    allow_actions: bool,
+    last_fired: Option<DateTime<Local>>,
+    suspended_until: Option<DateTime<Local>>,
+}
+
+// What the restart scheduler thinks it should do, for "my restarts aren't happening" reports.
+#[tauri::command]
+fn scheduler_debug(state: State<'_, AppState>) -> SchedulerDebug {
+    let cfg = state.config.lock().clone();
+    let by_day = restart_times_by_day(&cfg);
+    let mut times_by_day = Vec::new();
+    let mut d = Weekday::Mon;
+    for _ in 0..7 {
+        let times = by_day.get(&d).map(Vec::as_slice).unwrap_or(&[]);
+        times_by_day.push((d.to_string(), times.iter().map(|t| t.format("%H:%M").to_string()).collect()));
+        d = d.succ();
+    }
+    let generation = state.sched.load(Ordering::SeqCst);
+    let status = state.sched_status.lock();
+    let now = Local::now();
+    let suspended_until = cfg.restarts_suspended_until.map(|u| u.with_timezone(&Local)).filter(|u| *u > now);
+    SchedulerDebug {
+        times_by_day,
+        next_fire: next_fire_by_day(suspended_until.unwrap_or(now), |d| by_day.get(&d).map(Vec::as_slice).unwrap_or(&[])),
+        suspended_until,
+        generation,
+        active: status.running_gen == Some(generation),
+        allow_actions: cfg.allow_actions,
+        last_fired: status.last_fired,
+    }
+}
+
+// "YYYY-MM-DD HH:MM" (or with a T) in local time, or a full RFC 3339 timestamp.
+fn parse_local_datetime(s: &str) -> Option<DateTime<Local>> {
+    let s = s.trim();
+    if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+        return Some(dt.with_timezone(&Local));
+    }
+    ["%Y-%m-%d %H:%M", "%Y-%m-%dT%H:%M"]
+        .iter()
+        .find_map(|f| chrono::NaiveDateTime::parse_from_str(s, f).ok())
+        .and_then(|n| Local.from_local_datetime(&n).earliest())
+}
+
+// Store the suspension, persist it and restart the scheduler so it takes effect.
+fn set_restart_suspension(state: &AppState, until: Option<DateTime<Local>>) {
+    let snapshot = {
+        let mut cfg = state.config.lock();
+        cfg.restarts_suspended_until = until.map(|u| u.with_timezone(&Utc));
+        cfg.clone()
+    };
+    save_config(&snapshot);
+    // only the scheduler reads the suspension; the other loops keep running untouched
+    spawn_scheduler(
+        &state.rest,
+        state.sched.clone(),
+        state.sched_status.clone(),
+        state.server_pid.clone(),
+        state.shutdown_shape.clone(),
+        state.watch.clone(),
+        state.daily.clone(),
+        state.greet_gen.clone(),
+        &snapshot,
+    );
+}
+
+// Skip scheduled restarts until `until`, then resume automatically; the schedule itself is kept.
+#[tauri::command]
+async fn suspend_scheduler_until(state: State<'_, AppState>, until: String) -> Result<String, String> {
+    if !state.config.lock().allow_actions { return Err("actions disabled".into()); }
+    let until = parse_local_datetime(&until)
+        .ok_or_else(|| format!("invalid date/time '{}': use YYYY-MM-DD HH:MM", until))?;
+    if until <= Local::now() {
+        return Err("suspension end must be in the future".into());
+    }
+    set_restart_suspension(&state, Some(until));
+    let when = until.format("%Y-%m-%d %H:%M").to_string();
+    audit("suspend_scheduler", &when, true, None);
+    let hook = discord_hook(&state.rest, &state.config.lock());
+    if let Some(h) = hook {
+        discord_embed(&h, &format!("Scheduled restarts suspended until {}.", when), COLOR_INFO).await;
+    }
+    Ok(until.to_rfc3339())
+}
+
+// End a suspension early.
+#[tauri::command]
+async fn resume_scheduler(state: State<'_, AppState>) -> Result<(), String> {
+    if !state.config.lock().allow_actions { return Err("actions disabled".into()); }
+    if state.config.lock().restarts_suspended_until.is_none() {
+        return Ok(());
+    }
+    set_restart_suspension(&state, None);
+    audit("resume_scheduler", "", true, None);
+    let hook = discord_hook(&state.rest, &state.config.lock());
+    if let Some(h) = hook {
+        discord_embed(&h, "Scheduled restarts resumed.", COLOR_INFO).await;
+    }
+    Ok(())
+}
+
+// Extra uptime tolerated past the expected restart: countdown, shutdown wait and boot all take time.
+const RESTART_HEALTH_GRACE_SECS: i64 = 900;
+
+#[derive(Serialize)]
+struct RestartHealth {
+    uptime_seconds: Option<u64>,
+    last_expected: Option<DateTime<Local>>,
+    since_expected_secs: Option<i64>,
+    restart_may_have_failed: bool,
+    message: String,
+}
+
+// Compare the server's uptime with the last scheduled restart: an uptime reaching back
+// before that restart means the server never actually went down.
+#[tauri::command]
+async fn restart_health(state: State<'_, AppState>) -> Result<RestartHealth, ApiError> {
+    let cfg = state.config.lock().clone();
+    let info = load_server_info(&state, &cfg).await?;
+    let by_day = restart_times_by_day(&cfg);
+    let now = Local::now();
+    let last_expected = if cfg.allow_actions {
+        prev_fire_by_day(now, |d| by_day.get(&d).map(Vec::as_slice).unwrap_or(&[]))
+    } else {
+        None
+    };
+    // skipped on purpose while suspended
+    let suspended = last_expected
+        .zip(cfg.restarts_suspended_until)
+        .is_some_and(|(at, until)| until.with_timezone(&Local) > at);
+    let since_expected_secs = last_expected.map(|at| (now - at).num_seconds());
+    let (failed, message) = match (info.uptime_seconds, last_expected, since_expected_secs) {
+        (None, _, _) => (false, "server does not report uptime".to_string()),
+        (_, None, _) | (_, _, None) => (false, "no scheduled restart to compare against".to_string()),
+        _ if suspended => (false, "last scheduled restart was skipped (scheduler suspended)".to_string()),
+        (Some(up), Some(at), Some(since)) if up as i64 > since + RESTART_HEALTH_GRACE_SECS => (
+            true,
+            format!(
+                "restart may have failed: uptime {}h {}m reaches back past the {} restart",
+                up / 3600,
+                up % 3600 / 60,
+                at.format("%a %H:%M")
+            ),
+        ),
+        _ => (false, "server restarted on schedule".to_string()),
+    };
+    if failed {
+        let first = {
+            let mut warned = state.restart_health_warned.lock();
+            let first = *warned != last_expected;
+            *warned = last_expected;
+            first
+        };
+        if first {
+            if let Some(h) = discord_hook(&state.rest, &cfg) {
+                discord_embed(&h, &format!("Warning: {}", message), COLOR_ERROR).await;
+            }
+        }
+    }
+    Ok(RestartHealth {
+        uptime_seconds: info.uptime_seconds,
+        last_expected,
+        since_expected_secs,
+        restart_may_have_failed: failed,
+        message,
+    })
+}
+
+// Inline check for one restart time entry; parse_times_hhmm silently drops anything that fails this.
+#[tauri::command]
+fn validate_restart_time(s: String) -> Result<(), String> {
+    NaiveTime::parse_from_str(s.trim(), "%H:%M")
+        .map(|_| ())
+        .map_err(|_| format!("invalid restart time '{}': use HH:MM 24-hour format", s))
+}
+
+// Time-of-day loops run at least daily; a little slack covers DST and slow runs.
+const DAY_TASK_MAX_GAP_SECS: u64 = 26 * 3600;
+// A weekday schedule may skip up to six days.
+const SCHEDULER_MAX_GAP_SECS: u64 = 8 * 86_400;
+
+#[derive(Serialize)]
+struct TaskHealth {
+    name: String,
+    expected: bool, // the current config wants this loop running
+    running: bool,  // a task of the current generation is alive
+    last_run: Option<DateTime<Local>>,
+    stale: bool,    // alive but hasn't run for longer than its interval allows
+    problem: Option<String>,
+}
+
+fn task_health_entry(name: &str, expected: bool, gen: usize, beat: Option<TaskBeat>) -> TaskHealth {
+    let beat = beat.unwrap_or_default();
+    let running = beat.running_gen == Some(gen);
+    let since = beat.last_run.or(beat.started).map(|t| (Local::now() - t).num_seconds().max(0) as u64);
+    let stale = running && beat.max_gap_secs > 0 && since.is_some_and(|s| s > beat.max_gap_secs);
+    let problem = if expected && !running {
+        Some(beat.failed.clone().unwrap_or_else(|| "should be running but no live task".to_string()))
+    } else if stale {
+        Some(format!("no run for {}s (expected within {}s)", since.unwrap_or(0), beat.max_gap_secs))
+    } else {
+        None
+    };
+    TaskHealth { name: name.to_string(), expected, running, last_run: beat.last_run, stale, problem }
+}
+
+// One row per background loop so silently dead tasks show up.
+#[tauri::command]
+fn task_health(state: State<'_, AppState>) -> Vec<TaskHealth> {
+    let cfg = state.config.lock().clone();
+    let has_base = !cfg.base_url.trim().is_empty();
+    let beats = state.task_beats.lock().clone();
+    let sched = {
+        let st = state.sched_status.lock();
+        let suspended = cfg.restarts_suspended_until.is_some_and(|u| u > Utc::now());
+        TaskBeat {
+            running_gen: st.running_gen,
+            started: None,
+            last_run: st.last_fired,
+            // a suspension legitimately pauses it
+            max_gap_secs: if suspended { 0 } else { SCHEDULER_MAX_GAP_SECS },
+            failed: None,
+        }
+    };
+    let scheduled = restart_times_by_day(&cfg).values().any(|t| !t.is_empty());
+    vec![
+        task_health_entry("scheduler", cfg.allow_actions && scheduled, state.sched.load(Ordering::SeqCst), Some(sched)),
+        task_health_entry(
+            "autosave",
+            cfg.allow_actions && cfg.autosave_enabled && has_base,
+            state.autosave_gen.load(Ordering::SeqCst),
+            beats.get("autosave").cloned(),
+        ),
+        task_health_entry(
+            "backup",
+            cfg.allow_actions && cfg.backup_enabled && cfg.backup_dir.is_some(),
+            state.backup_gen.load(Ordering::SeqCst),
+            beats.get("backup").cloned(),
+        ),
+        task_health_entry(
+            "save_schedule",
+            cfg.allow_actions && has_base && !parse_times_hhmm(cfg.save_schedule.as_deref().unwrap_or_default()).is_empty(),
+            state.save_sched_gen.load(Ordering::SeqCst),
+            beats.get("save_schedule").cloned(),
+        ),
+        task_health_entry(
+            "motd_schedule",
+            cfg.allow_actions && has_base && cfg.motd_schedule.as_ref().is_some_and(|m| !m.is_empty()),
+            state.motd_gen.load(Ordering::SeqCst),
+            beats.get("motd_schedule").cloned(),
+        ),
+        task_health_entry(
+            "settings_schedule",
+            cfg.allow_actions && has_base && cfg.settings_schedule.as_ref().is_some_and(|s| !s.is_empty()),
+            state.settings_event_gen.load(Ordering::SeqCst),
+            beats.get("settings_schedule").cloned(),
+        ),
+        task_health_entry(
+            "daily_summary",
+            cfg.discord_webhook.is_some() && cfg.daily_summary_time.is_some(),
+            state.summary_gen.load(Ordering::SeqCst),
+            beats.get("daily_summary").cloned(),
+        ),
+    ]
+}
+
+#[tauri::command]
+fn check_restart_schedule(state: State<'_, AppState>, times: Option<Vec<String>>) -> Vec<String> {
+    match times {
+        Some(t) => restart_schedule_warnings(&t),
+        None => config_schedule_warnings(&state.config.lock()),
+    }
+}
+
+// (Re)start background tasks for a freshly applied config; shared by set_config and the file watcher.
+fn apply_config(state: &AppState, snapshot: &ApiConfig) {
+    state.rest.set_limit(snapshot);
+    set_http_prefs(&state.rest, snapshot);
+    // a lowered limit applies right away
+    enforce_storage_limit(snapshot);
+    // start/restart scheduler (only if actions allowed)
+    if snapshot.allow_actions {
+        spawn_scheduler(
+            &state.rest,
+            state.sched.clone(),
+            state.sched_status.clone(),
+            state.server_pid.clone(),
+            state.shutdown_shape.clone(),
+            state.watch.clone(),
+            state.daily.clone(),
+            state.greet_gen.clone(),
+            snapshot,
+        );
+    } else {
+        // cancel existing scheduler
+        let _ = state.sched.fetch_add(1, Ordering::SeqCst);
+    }
+    // Discord log: config updated
+    if let Some(h) = discord_hook(&state.rest, snapshot) {
+        let base = snapshot.base_url.clone();
+        let times = match &snapshot.restart_schedule {
+            Some(m) => format!("per weekday ({} day(s))", m.values().filter(|t| !t.is_empty()).count()),
+            None if snapshot.restart_times.is_empty() => "(none)".to_string(),
+            None => snapshot.restart_times.join(", "),
+        };
+        let actions = if snapshot.allow_actions { "enabled" } else { "disabled" };
+        tauri::async_runtime::spawn(async move {
+            discord_embed(&h, &format!("Config updated. Base: {} | Restarts: {} | Actions: {}", base, times, actions), COLOR_INFO).await;
+        });
+    }
+    // start autosave and backup background tasks
+    if snapshot.allow_actions && snapshot.autosave_enabled {
+        spawn_autosave(&state.rest, state.autosave_gen.clone(), state.task_beats.clone(), snapshot);
+    } else {
+        let _ = state.autosave_gen.fetch_add(1, Ordering::SeqCst);
+    }
+    if snapshot.allow_actions {
+        spawn_save_schedule(&state.rest, state.save_sched_gen.clone(), state.task_beats.clone(), snapshot);
+        spawn_motd_schedule(&state.rest, state.motd_gen.clone(), state.task_beats.clone(), snapshot);
+        spawn_settings_schedule(&state.rest, state.settings_event_gen.clone(), state.task_beats.clone(), state.settings_events.clone(), snapshot);
+        spawn_ban_expiry(&state.rest, state.ban_expiry_gen.clone(), state.task_beats.clone(), state.bans.clone(), snapshot);
+    } else {
+        let _ = state.settings_event_gen.fetch_add(1, Ordering::SeqCst);
+        let _ = state.ban_expiry_gen.fetch_add(1, Ordering::SeqCst);
+        let _ = state.save_sched_gen.fetch_add(1, Ordering::SeqCst);
+        let _ = state.motd_gen.fetch_add(1, Ordering::SeqCst);
+    }
+    if snapshot.allow_actions && snapshot.backup_enabled {
+        spawn_backup(&state.rest, state.backup_gen.clone(), state.task_beats.clone(), state.backup_streak.clone(), state.daily.clone(), snapshot);
+    } else {
+        let _ = state.backup_gen.fetch_add(1, Ordering::SeqCst);
+    }
+    // reporting only, so not gated on allow_actions
+    spawn_daily_summary(&state.rest, state.summary_gen.clone(), state.task_beats.clone(), state.daily.clone(), snapshot);
+    spawn_ws_server(state.ws_gen.clone(), state.task_beats.clone(), state.badge.clone(), snapshot);
+    restart_recurring_announces(state, snapshot);
+}
+
+// Enable/disable only the autosave loop; the scheduler and backup loop are left alone.
+#[tauri::command]
+fn toggle_autosave(state: State<'_, AppState>, enabled: bool) -> Result<(), String> {
+    let snapshot = {
+        let mut cfg = state.config.lock();
+        cfg.autosave_enabled = enabled;
+        cfg.clone()
+    };
+    save_config(&snapshot);
+    if enabled && snapshot.allow_actions {
+        spawn_autosave(&state.rest, state.autosave_gen.clone(), state.task_beats.clone(), &snapshot);
+    } else {
+        let _ = state.autosave_gen.fetch_add(1, Ordering::SeqCst);
+    }
+    Ok(())
+}
+
+// Enable/disable only the backup loop; the scheduler and autosave loop are left alone.
+#[tauri::command]
+fn toggle_backup(state: State<'_, AppState>, enabled: bool) -> Result<(), String> {
+    let snapshot = {
+        let mut cfg = state.config.lock();
+        cfg.backup_enabled = enabled;
+        cfg.clone()
+    };
+    save_config(&snapshot);
+    if enabled && snapshot.allow_actions {
+        spawn_backup(&state.rest, state.backup_gen.clone(), state.task_beats.clone(), state.backup_streak.clone(), state.daily.clone(), &snapshot);
+    } else {
+        let _ = state.backup_gen.fetch_add(1, Ordering::SeqCst);
+    }
+    Ok(())
+}
+
+// Known /shutdown payloads: the delay key of a JSON body, or "none" for an empty POST with CL:0.
+const SHUTDOWN_SHAPES: [&str; 5] = ["waittime", "seconds", "time", "duration", "none"];
+
+fn shutdown_body(shape: usize, message: &str) -> Option<Value> {
+    match SHUTDOWN_SHAPES[shape] {
+        "none" => None,
+        key => Some(serde_json::json!({ key: 1, "message": message })),
+    }
+}
+
+// Shape indexes to try: the one that worked last time first, then the rest in order.
+fn shutdown_order(learned: Option<usize>) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..SHUTDOWN_SHAPES.len()).collect();
+    if let Some(i) = learned.filter(|i| *i < SHUTDOWN_SHAPES.len()) {
+        order.retain(|s| *s != i);
+        order.insert(0, i);
+    }
+    order
+}
+
+// Try the shutdown payload shapes (learned one first); return true on first success.
+async fn attempt_shutdown(rest: &RestState, watch: &ServerWatch, base: &str, login: &AdminLogin, hook: Option<DiscordHook>, reason: &str, learned: &Mutex<Option<usize>>) -> bool {
+    watch.expected_down.store(true, Ordering::SeqCst);
+    let client = rest.client();
+    let url = format!("{}/shutdown", v1_base(base));
+    let order = shutdown_order(*learned.lock());
+    for i in order {
+        let req = client.post(&url).admin_auth(login);
+        let req = match shutdown_body(i, reason) {
+            Some(b) => req.json(&b),
+            None => req.header(CONTENT_LENGTH, "0"),
+        };
+        let res = req.send_limited(rest).await;
+        let ok = res.as_ref().map(|r| r.status().is_success()).unwrap_or(false);
+        if let Some(h) = hook.clone() {
+            let msg = match &res {
+                Ok(r) => format!("Shutdown attempt ({}) -> {}", SHUTDOWN_SHAPES[i], r.status()),
+                Err(e) => format!("Shutdown attempt ({}) error: {}", SHUTDOWN_SHAPES[i], e),
+            };
+            let _ = discord_embed(&h, &msg, if ok { COLOR_SUCCESS } else { COLOR_ERROR }).await;
+        }
+        if ok {
+            *learned.lock() = Some(i);
+            return true;
+        }
+    }
+    false
+}
+
+// The /shutdown body shape learned from the last successful shutdown, if any.
+#[tauri::command]
+fn get_shutdown_shape(state: State<'_, AppState>) -> Option<String> {
+    state.shutdown_shape.lock().map(|i| SHUTDOWN_SHAPES[i].to_string())
+}
+
+// Warning checkpoints (seconds before the deadline) that fit within `total`, descending.
+fn countdown_checkpoints(total: u64) -> Vec<u64> {
+    let mut checkpoints = COUNTDOWN_CHECKPOINTS.to_vec();
+    checkpoints.retain(|&c| c <= total && c > 0);
+    checkpoints.sort_by(|a, b| b.cmp(a)); // descending
+    checkpoints
+}
+
+// Broadcast `message(cp)` at each checkpoint, sleeping so the total wait equals `total` seconds.
+// Returns false as soon as `gen` no longer matches (countdown canceled).
+async fn run_countdown<F: Fn(u64) -> String>(
+    rest: &RestState,
+    client: &reqwest::Client,
+    cfg: &ApiConfig,
+    total: u64,
+    hook: Option<DiscordHook>,
+    message: F,
+    gen: Option<(&AtomicUsize, usize)>,
+) -> bool {
+    let live = || match gen {
+        Some((g, id)) => g.load(Ordering::SeqCst) == id,
+        None => true,
+    };
+    let mut remaining = total;
+    for cp in countdown_checkpoints(total) {
+        if remaining > cp {
+            tokio::time::sleep(Duration::from_secs(remaining - cp)).await;
+            remaining = cp;
+        }
+        if !live() { return false; }
+        let msg = message(cp);
+        let _ = announce_multi(rest, client, cfg, &msg).await;
+        if let Some(h) = hook.clone() {
+            let _ = discord_embed(&h, &msg, COLOR_INFO).await;
+        }
+    }
+    if remaining > 0 {
+        tokio::time::sleep(Duration::from_secs(remaining)).await;
+    }
+    live()
+}
+
+// Send staged restart warnings at 60, 30, 20, 10, and 5 seconds.
+// Sleeps between stages so that total wait equals `total` seconds.
+async fn warn_countdown(rest: &RestState, client: &reqwest::Client, cfg: &ApiConfig, total: u64, hook: Option<DiscordHook>) {
+    run_countdown(rest, client, cfg, total, hook, restart_warning, None).await;
+}
+
+fn restart_warning(cp: u64) -> String {
+    if cp == 5 {
+        "Log off now".to_string()
+    } else {
+        format!("Restart in {} seconds.", cp)
+    }
+}
+
+// The restart warnings a `total_secs` lead would send, as (seconds after the countdown starts,
+// message). Nothing is broadcast.
+#[tauri::command]
+fn preview_countdown(total_secs: u64) -> Vec<(u64, String)> {
+    countdown_checkpoints(total_secs)
+        .into_iter()
+        .map(|cp| (total_secs - cp, restart_warning(cp)))
+        .collect()
+}
+
+
+async fn fetch_server_info(rest: &RestState, cfg: &ApiConfig) -> Result<ServerInfo> {
+    let v = api_get_value(rest, cfg, "info").await?;
+    let fm = cfg.field_mappings.as_ref();
+    let mut info = coerce_server_info(&v, fm);
+    if info.uptime_seconds.is_none() {
+        if let Ok(mv) = api_get_value(rest, cfg, "metrics").await {
+            if let Some(up) = u64_alt(&mv, &field_keys(fm, "uptime", &["uptime", "uptimeSeconds", "Uptime"])) {
+                info.uptime_seconds = Some(up);
+            }
+        }
+    }
+    Ok(info)
+}
+
+// The exact webhook payload a sample event of this type would produce; nothing is posted.
+#[tauri::command]
+fn preview_discord_embed(event_type: String) -> Result<Value, String> {
+    let (desc, color) = sample_event(event_type.trim())
+        .ok_or_else(|| format!("unknown event type: {}", event_type))?;
+    Ok(embed_payload(desc, color))
+}
+
+// "Test connection": hit /info with the configured auth and say whether the password, the URL
+// path, or the connection itself is the problem.
+#[tauri::command]
+async fn test_credentials(state: State<'_, AppState>) -> Result<(), String> {
+    let cfg = state.config.lock().clone();
+    if cfg.base_url.trim().is_empty() {
+        return Err(ApiError::NotConfigured.to_string());
+    }
+    let login = admin_login(&cfg);
+    let client = state.rest.client();
+    let (mut auth_fail, mut not_found, mut conn_err, mut other) = (None, false, None, None);
+    for url in candidate_urls(&cfg.base_url, "info") {
+        match client.get(&url).admin_auth(&login).send_limited(&state.rest).await {
+            Ok(resp) if resp.status().is_success() => return Ok(()),
+            Ok(resp) => match resp.status().as_u16() {
+                401 | 403 => auth_fail = Some(resp.status()),
+                404 => not_found = true,
+                _ => other = Some(format!("GET {} -> {}", url, resp.status())),
+            },
+            Err(e) => conn_err = Some(e.to_string()),
+        }
+    }
+    if let Some(status) = auth_fail {
+        return Err(if login.pass.is_empty() {
+            format!("Authentication required ({}): set the admin password", status)
+        } else {
+            format!("Authentication failed ({}): the admin password is wrong", status)
+        });
+    }
+    if let Some(o) = other {
+        return Err(format!("Unexpected response: {}", o));
+    }
+    if not_found {
+        return Err(format!("REST API not found under {} (404): check the base URL", cfg.base_url));
+    }
+    Err(format!(
+        "Could not connect to {}: {}",
+        cfg.base_url,
+        conn_err.unwrap_or_else(|| "no response".into())
+    ))
+}
+
+#[derive(Serialize)]
+struct UrlProbe {
+    url: String,
+    status: Option<u16>,
+    latency_ms: u64,
+    error: Option<String>,
+    json: bool,     // body parsed as JSON
+    selected: bool, // the URL api_get_value would use (first success)
+}
+
+// GET /info on every candidate URL, not stopping at the first success, so the UI can show
+// which URL form the server answers and how each of the others fails.
+#[tauri::command]
+async fn trace_connectivity(state: State<'_, AppState>) -> Result<Vec<UrlProbe>, String> {
+    let cfg = state.config.lock().clone();
+    if cfg.base_url.trim().is_empty() {
+        return Err(ApiError::NotConfigured.to_string());
+    }
+    let login = admin_login(&cfg);
+    let client = state.rest.client();
+    let mut out = Vec::new();
+    for url in candidate_urls(&cfg.base_url, "info") {
+        let started = std::time::Instant::now();
+        let res = client.get(&url).admin_auth(&login).send_limited(&state.rest).await;
+        let latency_ms = started.elapsed().as_millis() as u64;
+        let probe = match res {
+            Ok(resp) => {
+                let status = resp.status();
+                let json = resp.json::<Value>().await.is_ok();
+                UrlProbe {
+                    url,
+                    status: Some(status.as_u16()),
+                    latency_ms,
+                    error: (!status.is_success()).then(|| status.to_string()),
+                    json,
+                    selected: status.is_success() && !out.iter().any(|p: &UrlProbe| p.selected),
+                }
+            }
+            Err(e) => UrlProbe { url, status: None, latency_ms, error: Some(e.to_string()), json: false, selected: false },
+        };
+        out.push(probe);
+    }
+    Ok(out)
+}
+
+// Post a sample event to the configured webhook so admins can see it live.
+#[tauri::command]
+async fn test_discord_webhook(state: State<'_, AppState>, event_type: Option<String>) -> Result<(), String> {
+    let hook = state
+        .config
+        .lock()
+        .discord_webhook
+        .clone()
+        .ok_or_else(|| "discord webhook not configured".to_string())?;
+    let kind = event_type.unwrap_or_else(|| "config".into());
+    let (desc, color) = sample_event(kind.trim())
+        .ok_or_else(|| format!("unknown event type: {}", kind))?;
+    let resp = state.rest.client()
+        .post(&hook)
+        .json(&embed_payload(desc, color))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    if !resp.status().is_success() {
+        return Err(format!("webhook returned {}", resp.status()));
+    }
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_server_info(state: State<'_, AppState>) -> Result<ServerInfo, ApiError> {
+    let cfg = state.config.lock().clone();
+    load_server_info(&state, &cfg).await
+}
+
+// /info for the primary server plus the poll side effects (up/down and version change).
+async fn load_server_info(state: &AppState, cfg: &ApiConfig) -> Result<ServerInfo, ApiError> {
+    let rest = &state.rest;
+    let res = fetch_server_info(rest, cfg).await;
+    note_server_reachable(rest, &state.watch, cfg, res.is_ok());
+    *state.badge.lock() = Some(match &res {
+        Ok(info) => (true, info.players_online, info.max_players),
+        Err(_) => (false, 0, None),
+    });
+    let info = res?;
+    if let Some(v) = info.version.clone() {
+        // only a change between two known versions counts; the first poll just records it
+        let prev = state.last_version.lock().replace(v.clone());
+        if prev.is_some_and(|p| p != v) {
+            publish_event("server_updated", serde_json::json!({ "version": v }));
+            if let Some(h) = discord_hook(rest, cfg) {
+                discord_embed(&h, &format!("Server updated to {}", v), COLOR_SUCCESS).await;
+            }
+        }
+    }
+    if let Some(m) = info.map.clone().filter(|m| !m.trim().is_empty()) {
+        let prev = state.last_map.lock().replace(m.clone());
+        if let Some(p) = prev.filter(|p| *p != m) {
+            publish_event("world_changed", serde_json::json!({ "from": p, "to": m }));
+            if let Some(h) = discord_hook(rest, cfg) {
+                discord_embed(&h, &format!("World changed: {} -> {}", p, m), COLOR_ALERT).await;
+            }
+        }
+    }
+    // no max_players in the response means there is nothing to compare against
+    if let (Some(pct), Some(max)) = (cfg.capacity_alert_percent, info.max_players.filter(|m| *m > 0)) {
+        let near = info.players_online * 100 >= max * pct as usize;
+        let was_near = state.near_capacity.swap(near, Ordering::SeqCst);
+        if near && !was_near {
+            if let Some(h) = discord_hook(rest, cfg) {
+                discord_embed(&h, &format!("Server near capacity ({}/{})", info.players_online, max), COLOR_ALERT).await;
+            }
+        }
+    }
+    Ok(info)
+}
+
+// Does the server answer a Steam query? Tells "REST up but firewalled" apart from a healthy server.
+#[tauri::command]
+async fn check_query_port(state: State<'_, AppState>, port: Option<u16>) -> Result<bool, String> {
+    let cfg = state.config.lock().clone();
+    let port = port.or(cfg.query_port).unwrap_or(DEFAULT_QUERY_PORT);
+    steam_query_answers(&cfg.base_url, port).await
+}
+
+// Max number of profiles queried at the same time by the multi-server overview.
+const MULTI_SERVER_CONCURRENCY: usize = 4;
+
+// Query every configured profile's /info concurrently; one dead server doesn't block the rest.
+#[tauri::command]
+async fn get_all_server_info(
+    state: State<'_, AppState>,
+) -> Result<Vec<(String, Result<ServerInfo, String>)>, String> {
+    let cfg = state.config.lock().clone();
+    let limit = Arc::new(tokio::sync::Semaphore::new(MULTI_SERVER_CONCURRENCY));
+    let mut handles = Vec::with_capacity(cfg.profiles.len());
+    for p in &cfg.profiles {
+        let mut pcfg = cfg.clone();
+        pcfg.base_url = p.base_url.clone();
+        pcfg.password = p.password.clone();
+        pcfg.fallback_password = None;
+        pcfg.profiles.clear();
+        let limit = limit.clone();
+        let rest = state.rest.clone();
+        let handle = tauri::async_runtime::spawn(async move {
+            let _permit = limit.acquire_owned().await;
+            fetch_server_info(&rest, &pcfg).await.map_err(|e| e.to_string())
+        });
+        handles.push((p.name.clone(), handle));
+    }
+    let mut out = Vec::with_capacity(handles.len());
+    for (name, handle) in handles {
+        let res = handle.await.unwrap_or_else(|e| Err(e.to_string()));
+        out.push((name, res));
+    }
+    Ok(out)
+}
+
+#[tauri::command]
+async fn get_players(state: State<'_, AppState>) -> Result<Vec<Player>, ApiError> {
+    let cfg = state.config.lock().clone();
+    let v = api_get_value(&state.rest, &cfg, "players").await?;
+    Ok(process_players(&state, &cfg, &v).await)
+}
+
+#[derive(Debug, Serialize)]
+struct PlayerCounts {
+    raw: usize,     // entries in the /players response
+    coerced: usize, // players after duplicate ids were merged
+}
+
+// Shows whether the server lists anyone twice (raw > coerced means dedup kicked in).
+#[tauri::command]
+async fn get_raw_vs_coerced_count(state: State<'_, AppState>) -> Result<PlayerCounts, ApiError> {
+    let cfg = state.config.lock().clone();
+    let v = api_get_value(&state.rest, &cfg, "players").await?;
+    let raw = coerce_players_raw(&v, cfg.field_mappings.as_ref());
+    Ok(PlayerCounts { raw: raw.len(), coerced: dedup_players(raw).len() })
+}
+
+#[derive(Debug, Serialize)]
+struct Dashboard {
+    info: ServerInfo,
+    players: Vec<Player>,
+    fetched_at: DateTime<Utc>,
+}
+
+// /info and /players fetched concurrently; the tracker update and join/leave diff run once.
+#[tauri::command]
+async fn get_dashboard(state: State<'_, AppState>) -> Result<Dashboard, ApiError> {
+    let cfg = state.config.lock().clone();
+    load_dashboard(&state, &cfg).await
+}
+
+async fn load_dashboard(state: &AppState, cfg: &ApiConfig) -> Result<Dashboard, ApiError> {
+    let (info, players) = tokio::join!(load_server_info(state, cfg), api_get_value(&state.rest, cfg, "players"));
+    let info = info?;
+    let v = players?;
+    let players = process_players(state, cfg, &v).await;
+    Ok(Dashboard { info, players, fetched_at: Utc::now() })
+}
+
+// Discord caps an embed field value at 1024 characters.
+const EMBED_FIELD_MAX: usize = 1024;
+
+// One status embed (name, map, players, uptime, FPS, who's online) posted to the webhook on demand.
+// An unreachable server gets a "server offline" embed instead of an error.
+#[tauri::command]
+async fn post_status_to_discord(state: State<'_, AppState>) -> Result<(), String> {
+    let cfg = state.config.lock().clone();
+    let hook = discord_hook(&state.rest, &cfg).ok_or("discord webhook not configured")?;
+    let dash = match load_dashboard(&state, &cfg).await {
+        Ok(d) => d,
+        Err(ApiError::NotConfigured) => return Err(ApiError::NotConfigured.to_string()),
+        Err(e) => {
+            let embed = serde_json::json!({
+                "title": "Server offline",
+                "description": format!("{} is not reachable: {}", cfg.base_url, e),
+                "color": COLOR_ERROR,
+                "timestamp": Utc::now().to_rfc3339(),
+            });
+            discord_rich_embed(&hook, embed).await;
+            return Ok(());
+        }
+    };
+    let fm = cfg.field_mappings.as_ref();
+    let fps = api_get_value(&state.rest, &cfg, "metrics")
+        .await
+        .ok()
+        .and_then(|m| u64_alt(&m, &field_keys(fm, "server_fps", &["serverfps", "serverFps", "fps"])));
+    let info = &dash.info;
+    let count = match info.max_players {
+        Some(max) => format!("{}/{}", dash.players.len(), max),
+        None => dash.players.len().to_string(),
+    };
+    let uptime = info
+        .uptime_seconds
+        .map(|s| format!("{}h {}m", s / 3600, s % 3600 / 60))
+        .unwrap_or_else(|| "unknown".into());
+    let mut names: Vec<&str> = dash.players.iter().map(|p| p.name.as_str()).collect();
+    names.sort_unstable_by_key(|n| n.to_lowercase());
+    let mut online = if names.is_empty() { "Nobody online".to_string() } else { names.join(", ") };
+    if online.len() > EMBED_FIELD_MAX {
+        let mut cut = EMBED_FIELD_MAX - 4;
+        while !online.is_char_boundary(cut) {
+            cut -= 1;
+        }
+        online.truncate(cut);
+        online.push_str(" ...");
+    }
+    let embed = serde_json::json!({
+        "title": info.name,
+        "color": COLOR_REPORT,
+        "timestamp": dash.fetched_at.to_rfc3339(),
+        "fields": [
+            { "name": "Players", "value": count, "inline": true },
+            { "name": "Map", "value": info.map.clone().unwrap_or_else(|| "unknown".into()), "inline": true },
+            { "name": "Uptime", "value": uptime, "inline": true },
+            { "name": "FPS", "value": fps.map(|f| f.to_string()).unwrap_or_else(|| "unknown".into()), "inline": true },
+            { "name": "Online", "value": online, "inline": false },
+        ],
+    });
+    discord_rich_embed(&hook, embed).await;
+    Ok(())
+}
+
+// Coerce a /players response and run the poll side effects: daily stats, session tracking,
+// join/leave diff with Discord posts and live events.
+async fn process_players(state: &AppState, cfg: &ApiConfig, v: &Value) -> Vec<Player> {
+    let mut players = coerce_players(v, cfg.field_mappings.as_ref());
+    {
+        let mut daily = state.daily.lock();
+        daily.peak_players = daily.peak_players.max(players.len());
+        daily.unique_players.extend(players.iter().map(|p| p.id.clone()));
+    }
+    let first_timers: HashSet<String> = {
+        let mut tr = state.tracker.lock();
+        let brand_new = tr.update_with(&players);
+        tr.flush();
+        for p in players.iter_mut() {
+            if p.connected_seconds.is_none() {
+                p.connected_seconds = tr.connected_for(&p.id);
+            }
+        }
+        brand_new.into_iter().collect()
+    };
+    // join/leave detection + optional Discord webhook (use names when possible)
+    let (joined, left, names_current, names_prev, hook_opt) = {
+        let current_ids: HashSet<String> = players.iter().map(|p| p.id.clone()).collect();
+        let current_names: HashMap<String, String> = players
+            .iter()
+            .map(|p| (p.id.clone(), p.name.clone()))
+            .collect();
+        let mut last = state.last_players.lock();
+        let mut lastn = state.last_names.lock();
+        let prev_names = lastn.clone();
+        let joined: Vec<String> = current_ids.difference(&*last).cloned().collect();
+        let left: Vec<String> = last.difference(&current_ids).cloned().collect();
+        *last = current_ids.clone();
+        *lastn = current_names.clone();
+        (joined, left, current_names, prev_names, discord_hook(&state.rest, &state.config.lock()))
+    };
+    for id in joined {
+        let name = names_current.get(&id).cloned().unwrap_or(id.clone());
+        let first_time = first_timers.contains(&id);
+        publish_event("player_joined", serde_json::json!({ "id": id, "name": name, "first_time": first_time }));
+        let Some(hook) = &hook_opt else { continue };
+        let watched = state.watchlist.lock().get(&id).cloned();
+        if let Some(w) = watched {
+            let mut desc = format!("Watched player joined: {} ({})", name, id);
+            if let Some(note) = &w.note {
+                desc.push_str(&format!("\nNote: {}", note));
+            }
+            let content = w.discord_mention.as_deref().map(mention_markup).unwrap_or_default();
+            discord_embed_with_content(hook, &content, &desc, COLOR_REPORT).await;
+        } else if first_time {
+            discord_embed(hook, &format!("New player joined for the first time: {}", name), COLOR_SUCCESS).await;
+        } else {
+            discord_embed(hook, &format!("Player joined: {}", name), COLOR_INFO).await;
+        }
+    }
+    for id in left {
+        let name = names_prev.get(&id).cloned().unwrap_or(id.clone());
+        publish_event("player_left", serde_json::json!({ "id": id, "name": name }));
+        if let Some(hook) = &hook_opt {
+            discord_embed(hook, &format!("Player left: {}", name), COLOR_INFO).await;
+        }
+    }
+    players
+}
+
+const WATCHLIST_FILE: &str = "watchlist.json";
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct WatchEntry {
+    id: String,
+    note: Option<String>,
+    // "<@user>", "<@&role>", a bare user id, or "&role id"; pinged when the player joins
+    discord_mention: Option<String>,
+}
+
+fn mention_markup(m: &str) -> String {
+    let m = m.trim();
+    if m.starts_with('<') {
+        m.to_string()
+    } else if let Some(role) = m.strip_prefix('&') {
+        format!("<@&{}>", role.trim())
+    } else {
+        format!("<@{}>", m)
+    }
+}
+
+fn valid_mention(m: &str) -> bool {
+    let digits = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+    let m = m.trim();
+    if let Some(inner) = m.strip_prefix("<@").and_then(|s| s.strip_suffix('>')) {
+        return digits(inner.strip_prefix('&').or_else(|| inner.strip_prefix('!')).unwrap_or(inner));
+    }
+    digits(m.strip_prefix('&').unwrap_or(m).trim())
+}
+
+// Add or update a watchlist entry; its joins get their own Discord post, with an optional @mention.
+#[tauri::command]
+fn watch_player(
+    state: State<'_, AppState>,
+    player_id: String,
+    note: Option<String>,
+    discord_mention: Option<String>,
+) -> Result<WatchEntry, String> {
+    let id = normalize_player_id(&player_id);
+    if id.is_empty() { return Err("player id is empty".into()); }
+    let discord_mention = discord_mention.map(|m| m.trim().to_string()).filter(|m| !m.is_empty());
+    if let Some(m) = &discord_mention {
+        if !valid_mention(m) {
+            return Err(format!("invalid mention '{}': use a user id, &role id, <@user> or <@&role>", m));
+        }
+    }
+    let entry = WatchEntry { id: id.clone(), note: note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty()), discord_mention };
+    let mut list = state.watchlist.lock();
+    list.insert(id, entry.clone());
+    save_data(WATCHLIST_FILE, &*list);
+    Ok(entry)
+}
+
+#[tauri::command]
+fn unwatch_player(state: State<'_, AppState>, player_id: String) -> Result<(), String> {
+    let mut list = state.watchlist.lock();
+    if list.remove(&normalize_player_id(&player_id)).is_none() {
+        return Err(format!("{} is not on the watchlist", player_id));
+    }
+    save_data(WATCHLIST_FILE, &*list);
+    Ok(())
+}
+
+#[tauri::command]
+fn list_watchlist(state: State<'_, AppState>) -> Vec<WatchEntry> {
+    let mut out: Vec<WatchEntry> = state.watchlist.lock().values().cloned().collect();
+    out.sort_by(|a, b| a.id.cmp(&b.id));
+    out
+}
+
+#[derive(Serialize)]
+struct Readiness {
+    configured: bool,
+    ready: bool,
+    latency_ms: Option<u64>,
+    version: Option<String>,
+    error: Option<String>,
+}
+
+// Called once on frontend init: a first /info through the shared client opens the pooled
+// connection and fills the poll caches (up/down, badge, version, map), so the first dashboard
+// render doesn't pay for connection setup.
+#[tauri::command]
+async fn warmup(state: State<'_, AppState>) -> Result<Readiness, String> {
+    let cfg = state.config.lock().clone();
+    if cfg.base_url.trim().is_empty() {
+        return Ok(Readiness { configured: false, ready: false, latency_ms: None, version: None, error: None });
+    }
+    let started = std::time::Instant::now();
+    let res = load_server_info(&state, &cfg).await;
+    let latency_ms = Some(started.elapsed().as_millis() as u64);
+    Ok(match res {
+        Ok(info) => Readiness { configured: true, ready: true, latency_ms, version: info.version, error: None },
+        Err(e) => Readiness { configured: true, ready: false, latency_ms, version: None, error: Some(e.to_string()) },
+    })
+}
+
+// World/map name from the last poll; polls /info when nothing has been seen yet.
+#[tauri::command]
+async fn current_map(state: State<'_, AppState>) -> Result<Option<String>, ApiError> {
+    if let Some(m) = state.last_map.lock().clone() {
+        return Ok(Some(m));
+    }
+    let cfg = state.config.lock().clone();
+    Ok(load_server_info(&state, &cfg).await?.map)
+}
+
+// Online players per platform, from the last player poll.
+#[tauri::command]
+fn platform_breakdown(state: State<'_, AppState>) -> HashMap<String, usize> {
+    let mut out = HashMap::new();
+    for id in state.last_players.lock().iter() {
+        *out.entry(infer_platform(id).to_string()).or_insert(0) += 1;
+    }
+    out
+}
+
+#[tauri::command]
+fn player_durations(state: State<'_, AppState>) -> HashMap<String, i64> {
+    state
+        .tracker
+        .lock()
+        .seen
+        .iter()
+        .map(|(k, t)| (k.clone(), (Utc::now() - *t).num_seconds()))
+        .collect()
+}
+
+#[derive(Serialize)]
+struct StalePlayer {
+    id: String,
+    name: String,
+    silent_polls: u32,
+    connected_seconds: Option<i64>,
+}
+
+// Players still listed by the server but without a ping for many polls (likely disconnected ghosts).
+#[tauri::command]
+fn stale_players(state: State<'_, AppState>) -> Vec<StalePlayer> {
+    let tr = state.tracker.lock();
+    let names = state.last_names.lock();
+    let mut out: Vec<StalePlayer> = tr
+        .stale()
+        .into_iter()
+        .map(|(id, n)| StalePlayer {
+            name: names.get(&id).cloned().unwrap_or_else(|| id.clone()),
+            connected_seconds: tr.connected_for(&id),
+            silent_polls: n,
+            id,
+        })
+        .collect();
+    out.sort_by_key(|p| std::cmp::Reverse(p.silent_polls));
+    out
+}
+
+// Recent (unix seconds, ping) samples for one player, collected by the get_players poll.
+#[tauri::command]
+fn player_ping_history(state: State<'_, AppState>, player_id: String) -> Vec<(i64, u32)> {
+    state
+        .tracker
+        .lock()
+        .pings
+        .get(&player_id)
+        .map(|h| h.iter().copied().collect())
+        .unwrap_or_default()
+}
+
+const DEFAULT_ENGAGEMENT_WINDOW_DAYS: u32 = 7;
+
+#[derive(Debug, Serialize)]
+struct EngagementStats {
+    window_days: i64,
+    sessions: usize,               // finished sessions ending in the window
+    avg_session_secs: Option<i64>,
+    active_players: usize,         // played in the window, including anyone online now
+    new_players: usize,            // first ever seen in the window
+    returning_players: usize,
+    churned_players: usize,        // played in the previous window but not this one
+    churn_rate: Option<f64>,       // churned / players of the previous window
+}
+
+// Headline community numbers from the persisted session history.
+#[tauri::command]
+fn engagement_stats(state: State<'_, AppState>, window_days: Option<u32>) -> EngagementStats {
+    let days = window_days.filter(|d| *d > 0).unwrap_or(DEFAULT_ENGAGEMENT_WINDOW_DAYS);
+    state.tracker.lock().engagement(Utc::now(), chrono::Duration::days(days as i64))
+}
+
+// Restart suggestions look at this much session history, in 15-minute time-of-day slots.
+const SUGGEST_WINDOW_DAYS: i64 = 14;
+const SUGGEST_SLOT_MINS: u32 = 15;
+const SUGGEST_SLOTS: usize = (24 * 60 / SUGGEST_SLOT_MINS) as usize;
+
+// Player-seconds spent online in each time-of-day slot (in `tz`), summed over all spans.
+fn occupancy_by_slot<Tz: TimeZone>(spans: &[(DateTime<Utc>, DateTime<Utc>)], tz: &Tz) -> Vec<i64> {
+    let slot_secs = (SUGGEST_SLOT_MINS * 60) as i64;
+    let mut occ = vec![0i64; SUGGEST_SLOTS];
+    for (start, end) in spans {
+        let mut t = *start;
+        while t < *end {
+            let local = t.with_timezone(tz).time();
+            let secs_of_day = (local.hour() * 3600 + local.minute() * 60 + local.second()) as i64;
+            let into_slot = secs_of_day % slot_secs;
+            let step_end = (t + chrono::Duration::seconds(slot_secs - into_slot)).min(*end);
+            occ[(secs_of_day / slot_secs) as usize % SUGGEST_SLOTS] += (step_end - t).num_seconds();
+            t = step_end;
+        }
+    }
+    occ
+}
+
+// The `count` emptiest slots, spread out: picks are at least half the even spacing apart
+// (circularly), ties go to the earlier slot. Returned in time order.
+fn quietest_slots(occ: &[i64], count: usize) -> Vec<NaiveTime> {
+    let n = occ.len();
+    let min_gap = (n / count.max(1) / 2).max(1);
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by_key(|&i| (occ[i], i));
+    let mut picked: Vec<usize> = Vec::new();
+    for i in order {
+        if picked.len() == count { break; }
+        let far = picked.iter().all(|&p| {
+            let d = i.abs_diff(p);
+            d.min(n - d) >= min_gap
+        });
+        if far { picked.push(i); }
+    }
+    picked.sort();
+    picked
+        .into_iter()
+        .filter_map(|i| NaiveTime::from_hms_opt(i as u32 * SUGGEST_SLOT_MINS / 60, i as u32 * SUGGEST_SLOT_MINS % 60, 0))
+        .collect()
+}
+
+// HH:MM restart times in the lowest-population slots of the last two weeks, ready to paste
+// into restart_times.
+#[tauri::command]
+fn suggest_restart_times(state: State<'_, AppState>, count: usize) -> Result<Vec<String>, String> {
+    if count == 0 || count > 24 {
+        return Err("count must be between 1 and 24".into());
+    }
+    let now = Utc::now();
+    let cutoff = now - chrono::Duration::days(SUGGEST_WINDOW_DAYS);
+    let tr = state.tracker.lock();
+    let mut spans: Vec<(DateTime<Utc>, DateTime<Utc>)> =
+        tr.sessions.iter().filter(|s| s.end > cutoff).map(|s| (s.start.max(cutoff), s.end)).collect();
+    spans.extend(tr.session_start.values().map(|s| ((*s).max(cutoff), now)));
+    if spans.is_empty() {
+        return Err("no player sessions recorded yet; suggestions need some play history".into());
+    }
+    let occ = occupancy_by_slot(&spans, &Local);
+    Ok(quietest_slots(&occ, count).into_iter().map(|t| t.format("%H:%M").to_string()).collect())
+}
+
+#[derive(Serialize)]
+struct PlayerRecord {
+    id: String,
+    name: String,
+    online: bool,
+    last_seen: Option<DateTime<Utc>>,
+    first_seen: Option<DateTime<Utc>>,
+}
+
+// Online and past players whose name or id contains `query` (case-insensitive).
+// Exact matches come first, then prefix matches, then the most recently seen.
+#[tauri::command]
+fn search_players(state: State<'_, AppState>, query: String) -> Vec<PlayerRecord> {
+    let q = query.trim().to_lowercase();
+    if q.is_empty() {
+        return Vec::new();
+    }
+    let tr = state.tracker.lock();
+    let online = state.last_players.lock();
+    let mut names = tr.names.clone();
+    names.extend(state.last_names.lock().iter().map(|(k, v)| (k.clone(), v.clone())));
+    let rank = |r: &PlayerRecord| {
+        let (id, name) = (r.id.to_lowercase(), r.name.to_lowercase());
+        if id == q || name == q {
+            0
+        } else if id.starts_with(&q) || name.starts_with(&q) {
+            1
+        } else {
+            2
+        }
+    };
+    let mut out: Vec<PlayerRecord> = names
+        .into_iter()
+        .filter(|(id, name)| id.to_lowercase().contains(&q) || name.to_lowercase().contains(&q))
+        .map(|(id, name)| PlayerRecord {
+            online: online.contains(&id),
+            last_seen: tr.last_seen.get(&id).copied(),
+            first_seen: tr.first_seen.get(&id).copied(),
+            name,
+            id,
+        })
+        .collect();
+    out.sort_by(|a, b| {
+        rank(a)
+            .cmp(&rank(b))
+            .then(b.online.cmp(&a.online))
+            .then(b.last_seen.cmp(&a.last_seen))
+    });
+    out
+}
+
+const DEFAULT_RECENT_LEFT_SECS: u64 = 600;
+
+#[derive(Serialize)]
+struct RosterEntry {
+    id: String,
+    name: String,
+    online: bool,
+    joined_at: Option<DateTime<Utc>>,
+    left_at: Option<DateTime<Utc>>,
+}
+
+// Everyone online plus anyone who left within `left_within_secs` (default 10 minutes), so a
+// player who just quit can still be banned by id. Online first, then most recently left.
+#[tauri::command]
+fn recent_roster(state: State<'_, AppState>, left_within_secs: Option<u64>) -> Vec<RosterEntry> {
+    let cutoff = Utc::now() - chrono::Duration::seconds(left_within_secs.unwrap_or(DEFAULT_RECENT_LEFT_SECS) as i64);
+    let tr = state.tracker.lock();
+    let online = state.last_players.lock();
+    let current_names = state.last_names.lock();
+    let name_of = |id: &str| current_names.get(id).or_else(|| tr.names.get(id)).cloned().unwrap_or_else(|| id.to_string());
+    let mut out: Vec<RosterEntry> = online
+        .iter()
+        .map(|id| RosterEntry {
+            id: id.clone(),
+            name: name_of(id),
+            online: true,
+            joined_at: tr.session_start.get(id).copied(),
+            left_at: None,
+        })
+        .collect();
+    let mut left: HashMap<&str, &Session> = HashMap::new();
+    for s in tr.sessions.iter().rev().take_while(|s| s.end >= cutoff) {
+        if !online.contains(&s.id) {
+            left.entry(s.id.as_str()).or_insert(s);
+        }
+    }
+    out.extend(left.into_values().map(|s| RosterEntry {
+        id: s.id.clone(),
+        name: name_of(&s.id),
+        online: false,
+        joined_at: Some(s.start),
+        left_at: Some(s.end),
+    }));
+    out.sort_by(|a, b| b.online.cmp(&a.online).then(b.left_at.cmp(&a.left_at)).then(a.name.cmp(&b.name)));
+    out
+}
+
+// When this id was first ever seen by the app (persisted across restarts).
+#[tauri::command]
+fn player_first_seen(state: State<'_, AppState>, player_id: String) -> Option<DateTime<Utc>> {
+    state.tracker.lock().first_seen.get(&player_id).copied()
+}
+
+#[tauri::command]
+async fn announce_message(state: State<'_, AppState>, message: String) -> Result<(), String> {
+    let cfg = state.config.lock().clone();
+    let body = serde_json::json!({ "message": message });
+    let res = api_post_value(&state.rest, &cfg, "announce", Some(body)).await;
+    // Discord webhook log
+    if let Some(hook) = discord_hook(&state.rest, &cfg) {
+        match &res {
+            Ok(_) => discord_embed(&hook, &format!("Broadcast sent: {}", message), COLOR_SUCCESS).await,
+            Err(e) => discord_embed(&hook, &format!("Broadcast failed: {}", e), COLOR_ERROR).await,
+        }
+    }
+    res.map(|_| ()).map_err(|e| e.to_string())
+}
+
+#[derive(Serialize)]
+struct ResourceUsage {
+    pid: u32,
+    name: String,
+    cpu_percent: f32, // share of the whole machine, 0-100
+    memory_bytes: u64, // resident set size
+    server_fps: Option<u64>,
+    sampled_at: DateTime<Utc>,
+}
+
+// `name` matches with or without the .exe suffix, ignoring case.
+fn process_name_matches(actual: &str, name: &str) -> bool {
+    let strip = |s: &str| {
+        let s = s.trim().to_lowercase();
+        s.strip_suffix(".exe").map(str::to_string).unwrap_or(s)
+    };
+    strip(actual) == strip(name)
+}
+
+// CPU and memory of the server process (server_process_name), with /metrics FPS for charting.
+#[tauri::command]
+async fn server_resource_usage(state: State<'_, AppState>) -> Result<ResourceUsage, String> {
+    let cfg = state.config.lock().clone();
+    let name = cfg
+        .server_process_name
+        .clone()
+        .ok_or("set server_process_name (e.g. PalServer-Win64-Shipping-Cmd.exe) to track resources")?;
+    let wanted = name.clone();
+    let found = tokio::task::spawn_blocking(move || {
+        let mut sys = sysinfo::System::new();
+        // CPU usage is the delta between two refreshes
+        sys.refresh_processes();
+        std::thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+        sys.refresh_processes();
+        let cores = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1) as f32;
+        sys.processes()
+            .values()
+            .filter(|p| process_name_matches(p.name(), &wanted))
+            .max_by_key(|p| p.memory())
+            .map(|p| (p.pid().as_u32(), p.name().to_string(), p.cpu_usage() / cores, p.memory()))
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+    let (pid, pname, cpu_percent, memory_bytes) =
+        found.ok_or_else(|| format!("no running process named '{}' was found", name))?;
+    let server_fps = api_get_value(&state.rest, &cfg, "metrics")
+        .await
+        .ok()
+        .and_then(|m| u64_alt(&m, &field_keys(cfg.field_mappings.as_ref(), "server_fps", &["serverfps", "serverFps", "fps"])));
+    Ok(ResourceUsage { pid, name: pname, cpu_percent, memory_bytes, server_fps, sampled_at: Utc::now() })
+}
+
+// Utility: return raw /players JSON pretty-printed for debugging
+#[tauri::command]
+async fn dump_players_json(state: State<'_, AppState>) -> Result<String, String> {
+    let cfg = state.config.lock().clone();
+    api_get_value(&state.rest, &cfg, "players")
+        .await
+        .map(|v| serde_json::to_string_pretty(&v).unwrap_or_else(|_| v.to_string()))
+        .map_err(|e| e.to_string())
+}
+
+// Utility: return raw /info JSON pretty-printed for debugging
+#[tauri::command]
+async fn dump_info_json(state: State<'_, AppState>) -> Result<String, String> {
+    let cfg = state.config.lock().clone();
+    api_get_value(&state.rest, &cfg, "info")
+        .await
+        .map(|v| serde_json::to_string_pretty(&v).unwrap_or_else(|_| v.to_string()))
+        .map_err(|e| e.to_string())
+}
+
+// Utility: return raw /metrics JSON pretty-printed for debugging
+#[tauri::command]
+async fn dump_metrics_json(state: State<'_, AppState>) -> Result<String, String> {
+    let cfg = state.config.lock().clone();
+    api_get_value(&state.rest, &cfg, "metrics")
+        .await
+        .map(|v| serde_json::to_string_pretty(&v).unwrap_or_else(|_| v.to_string()))
+        .map_err(|e| e.to_string())
+}
+
+// Day/clock from /metrics, falling back to /info for whatever is missing. Only fails when
+// neither endpoint answers; absent fields come back as None.
+#[tauri::command]
+async fn get_game_time(state: State<'_, AppState>) -> Result<GameTime, String> {
+    let cfg = state.config.lock().clone();
+    let fm = cfg.field_mappings.as_ref();
+    let mut out = GameTime::default();
+    let mut last_err = None;
+    let mut reached = false;
+    for path in ["metrics", "info"] {
+        match api_get_value(&state.rest, &cfg, path).await {
+            Ok(v) => {
+                reached = true;
+                let found = coerce_game_time(&v, fm);
+                out.day = out.day.or(found.day);
+                out.time = out.time.or(found.time);
+                if out.day.is_some() && out.time.is_some() { break; }
+            }
+            Err(e) => last_err = Some(e.to_string()),
+        }
+    }
+    if !reached {
+        return Err(last_err.unwrap_or_else(|| "no response".into()));
+    }
+    Ok(out)
+}
+
+#[tauri::command]
+async fn force_save(state: State<'_, AppState>) -> Result<String, String> {
+    let cfg = state.config.lock().clone();
+    let base = cfg.base_url.clone();
+    let login = admin_login(&cfg);
+    if let Some(h) = discord_hook(&state.rest, &cfg) { discord_embed(&h, "Manual save requested.", COLOR_INFO).await; }
+
+    let save_url_for_log = format!("{}/save", v1_base(&base));
+    let return_url = save_url_for_log.clone();
+
+    if SAVING.swap(true, Ordering::SeqCst) {
+        return Ok("save already in progress".into());
+    }
+
+    let rest = state.rest.clone();
+    tauri::async_runtime::spawn(async move {
+        let client = match rest.save_client() {
+            Ok(c) => c,
+            Err(_) => {
+                SAVING.store(false, Ordering::SeqCst);
+                return;
+            }
+        };
+
+        let _ = announce_multi(&rest, &client, &cfg, "Saving world…").await;
+
+        let status_opt = request_save(&rest, &client, &base, &login).await.ok();
+
+        match status_opt {
+            Some(s) if s.is_success() => {
+                let _ = announce_multi(&rest, &client, &cfg, "Game saved").await;
+            }
+            Some(s) => {
+                let _ = announce_multi(&rest, &client, &cfg, &format!("Save failed: {s}")).await;
+            }
+            None => {
+                let _ = announce_multi(&rest, &client, &cfg, "Save error: request failed").await;
+            }
+        }
+
+        SAVING.store(false, Ordering::SeqCst);
+    });
+
+    Ok(format!("dispatched POST {}", return_url))
+}
+
+#[tauri::command]
+async fn shutdown_server(
+    state: State<'_, AppState>,
+    seconds: Option<u64>,
+    msg: Option<String>,
+) -> Result<(), String> {
+    let cfg = state.config.lock().clone();
+    let s = seconds.unwrap_or(60);
+    let m = msg.unwrap_or_else(|| "Server restarting...".into());
+    if let Some(h) = discord_hook(&state.rest, &cfg) { discord_embed(&h, &format!("Shutdown requested in {}s: {}", s, m), COLOR_INFO).await; }
+
+    let rest = state.rest.clone();
+    tauri::async_runtime::spawn({
+        let cfg = cfg.clone();
+        let m = m.clone();
+        let learned = state.shutdown_shape.clone();
+        let watch = state.watch.clone();
+        async move {
+            let client = rest.client();
+            let _ = announce_multi(&rest, &client, &cfg, &format!("{} in {} seconds.", m, s)).await;
+            if s > 1 { tokio::time::sleep(Duration::from_secs(s)).await; }
+            // After waiting, send minimal waittime accepted by some providers
+            watch.expected_down.store(true, Ordering::SeqCst);
+            let order = shutdown_order(*learned.lock());
+            for i in order {
+                if api_post_value(&rest, &cfg, "shutdown", shutdown_body(i, &m)).await.is_ok() {
+                    *learned.lock() = Some(i);
+                    break;
+                }
+            }
+            if let Some(h) = discord_hook(&rest, &cfg) { let _ = discord_embed(&h, "Shutdown command sent.", COLOR_INFO).await; }
+        }
+    });
+    Ok(())
+}
+
+// How long a freshly launched start_cmd is watched for an immediate exit.
+const START_CHECK_SECS: u64 = 15;
+
+// Whitespace-separated arguments; double quotes group (and are stripped).
+fn split_command_line(s: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut cur = String::new();
+    let (mut quoted, mut pending) = (false, false);
+    for ch in s.chars() {
+        match ch {
+            '"' => { quoted = !quoted; pending = true; }
+            c if c.is_whitespace() && !quoted => {
+                if pending { out.push(std::mem::take(&mut cur)); pending = false; }
+            }
+            c => { cur.push(c); pending = true; }
+        }
+    }
+    if pending { out.push(cur); }
+    out
+}
+
+// Program + args used to run start_cmd: shell_override when set ({cmd} is replaced, or the
+// command appended), .bat via cmd, .ps1 via PowerShell, anything else directly.
+fn start_invocation(c: &str, shell_override: Option<&str>) -> (String, Vec<String>) {
+    if let Some(mut parts) = shell_override.map(split_command_line).filter(|p| !p.is_empty()) {
+        let program = parts.remove(0);
+        let mut args: Vec<String> = parts.iter().map(|a| a.replace("{cmd}", c)).collect();
+        if !parts.iter().any(|a| a.contains("{cmd}")) {
+            args.push(c.to_string());
+        }
+        return (program, args);
+    }
+    let lower = c.trim().to_lowercase();
+    if lower.ends_with(".bat") || lower.ends_with(".cmd") {
+        ("cmd".into(), vec!["/C".into(), c.into()])
+    } else if lower.ends_with(".ps1") {
+        let args = ["-NoProfile", "-ExecutionPolicy", "Bypass", "-File", c];
+        ("powershell".into(), args.iter().map(|a| a.to_string()).collect())
+    } else {
+        (c.into(), vec![])
+    }
+}
+
+// Start scripts usually expect to run from the server folder: use start_cmd_cwd when set,
+// otherwise the folder containing the command. None (a bare program name) keeps the app's cwd.
+fn start_cwd(c: &str, configured: Option<&str>) -> Option<PathBuf> {
+    if let Some(d) = configured.map(str::trim).filter(|d| !d.is_empty()) {
+        return Some(PathBuf::from(d));
+    }
+    Path::new(c.trim().trim_matches('"'))
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .map(Path::to_path_buf)
+}
+
+// Launch start_cmd and record its PID. A failed spawn (bad path, missing cmd.exe) and a
+// launcher that exits non-zero right away are both reported instead of failing silently.
+async fn launch_start_cmd(
+    c: &str,
+    shell_override: Option<&str>,
+    cwd: Option<PathBuf>,
+    hook: Option<DiscordHook>,
+    pid_slot: Arc<Mutex<Option<u32>>>,
+) {
+    let (program, args) = start_invocation(c, shell_override);
+    // started through cmd/PowerShell/a custom shell rather than directly
+    let via_shell = program != c;
+    let mut command = Command::new(&program);
+    command.args(&args);
+    if let Some(dir) = &cwd {
+        if !dir.is_dir() {
+            *pid_slot.lock() = None;
+            let msg = format!("Start failed: working directory {} does not exist", dir.display());
+            audit("start_server", c, false, Some(msg.clone()));
+            if let Some(h) = hook { discord_embed(&h, &msg, COLOR_ERROR).await; }
+            return;
+        }
+        command.current_dir(dir);
+    }
+    let spawned = command.spawn();
+    let mut child = match spawned {
+        Ok(child) => child,
+        Err(e) => {
+            *pid_slot.lock() = None;
+            let msg = if via_shell {
+                format!("Start failed: could not launch {} for {}: {}", program, c, e)
+            } else {
+                format!("Start failed: could not launch {}: {}", c, e)
+            };
+            audit("start_server", c, false, Some(msg.clone()));
+            if let Some(h) = hook { discord_embed(&h, &msg, COLOR_ERROR).await; }
+            return;
+        }
+    };
+    *pid_slot.lock() = Some(child.id());
+    let c = c.to_string();
+    tauri::async_runtime::spawn(async move {
+        for _ in 0..START_CHECK_SECS * 4 {
+            tokio::time::sleep(Duration::from_millis(250)).await;
+            let status = match child.try_wait() {
+                Ok(Some(status)) => status,
+                Ok(None) => continue,
+                Err(_) => return,
+            };
+            if status.success() { return; } // e.g. a .bat that hands off via `start`
+            *pid_slot.lock() = None;
+            let code = status.code().map(|n| n.to_string()).unwrap_or_else(|| "unknown".into());
+            let msg = if via_shell {
+                format!("Start script {} failed with exit code {}", c, code)
+            } else {
+                format!("Server process {} exited with code {} right after starting", c, code)
+            };
+            audit("start_server", &c, false, Some(msg.clone()));
+            if let Some(h) = hook { discord_embed(&h, &msg, COLOR_ERROR).await; }
+            return;
+        }
+    });
+}
+
+const DEFAULT_SAVE_SETTLE_SECS: u64 = 5;
+// Extra wait allowed for a manual save_world still in flight once the settle time is up.
+const SAVE_SETTLE_MAX_EXTRA_SECS: u64 = 30;
+
+async fn wait_for_save_settle(settle: u64) {
+    tokio::time::sleep(Duration::from_secs(settle)).await;
+    let mut extra = 0;
+    while SAVING.load(Ordering::SeqCst) && extra < SAVE_SETTLE_MAX_EXTRA_SECS {
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        extra += 1;
+    }
+}
+
+// The full restart sequence shared by manual, triggered and scheduled restarts:
+// countdown, save, announce, shutdown, wait for the API to go down, start_cmd.
+// `gen` is checked after the countdown; returns false when the restart was canceled.
+async fn perform_restart(
+    rest: &RestState,
+    watch: &Arc<ServerWatch>,
+    daily: &Mutex<DailyStats>,
+    greet: &Arc<AtomicUsize>,
+    client: &reqwest::Client,
+    cfg: &ApiConfig,
+    lead: u64,
+    gen: Option<(&AtomicUsize, usize)>,
+    reason: &str,
+    pid_slot: Arc<Mutex<Option<u32>>>,
+    shutdown_shape: &Mutex<Option<usize>>,
+) -> bool {
+    let base = cfg.base_url.clone();
+    let login = admin_login(cfg);
+    let hook = discord_hook(rest, cfg);
+
+    warn_countdown(rest, client, cfg, lead, hook.clone()).await;
+    if let Some((g, id)) = gen {
+        if g.load(Ordering::SeqCst) != id {
+            return false;
+        }
+    }
+
+    // save (best-effort), then give the world time to flush before shutting down
+    let _ = request_save(rest, client, &base, &login).await;
+    let settle = cfg.save_settle_secs.unwrap_or(DEFAULT_SAVE_SETTLE_SECS);
+    if settle > 0 {
+        if let Some(h) = hook.clone() {
+            discord_embed(&h, &format!("Waiting {}s for the save to finish before shutdown...", settle), COLOR_INFO).await;
+        }
+        wait_for_save_settle(settle).await;
+    }
+    if cfg.backup_before_restart {
+        let (msg, color) = match pre_restart_backup(cfg).await {
+            Ok(p) => (format!("Pre-restart backup created: {}", p.display()), COLOR_SUCCESS),
+            Err(e) => (format!("Pre-restart backup failed: {} (restarting anyway)", e), COLOR_ERROR),
+        };
+        if let Some(h) = hook.clone() { discord_embed(&h, &msg, color).await; }
+    }
+
+    let _ = announce_multi(rest, client, cfg, "Restarting server…").await;
+    daily.lock().restarts += 1;
+    publish_event("restart_started", serde_json::json!({ "reason": reason }));
+    if let Some(h) = hook.clone() { discord_embed(&h, &format!("{} executing.", reason), COLOR_INFO).await; }
+    let _ = attempt_shutdown(rest, watch, &base, &login, hook.clone(), reason, shutdown_shape).await;
+
+    // wait for REST to go down (max SHUTDOWN_WAIT_SECS) before starting new instance
+    if let Some(h) = hook.clone() {
+        discord_embed(&h, &format!("Waiting for server to stop (up to {}s)...", SHUTDOWN_WAIT_SECS), COLOR_INFO).await;
+    }
+    let stopped = wait_for_server_down(rest, &base, &login, SHUTDOWN_WAIT_SECS).await;
+    if stopped {
+        note_server_reachable(rest, watch, cfg, false);
+    }
+    if let Some(h) = hook.clone() {
+        if stopped {
+            discord_embed(&h, "Server appears offline. Starting new instance...", COLOR_SUCCESS).await;
+        } else {
+            discord_embed(&h, &format!("Server did not stop in time ({}s). Starting anyway.", SHUTDOWN_WAIT_SECS), COLOR_ERROR).await;
+        }
+    }
+
+    if let Some(c) = &cfg.start_cmd {
+        if let Some(h) = hook.clone() { discord_embed(&h, &format!("Starting server via: {}", c), COLOR_INFO).await; }
+        // Start the Windows .bat / .ps1 / .exe
+        let cwd = start_cwd(c, cfg.start_cmd_cwd.as_deref());
+        launch_start_cmd(c, cfg.shell_override.as_deref(), cwd, hook.clone(), pid_slot).await;
+    }
+    publish_event("restart_completed", serde_json::json!({ "reason": reason, "stopped_cleanly": stopped }));
+    greet_when_back(rest, watch.clone(), greet.clone(), cfg);
+    true
+}
+
+const DEFAULT_RESTART_COMPLETE_MESSAGE: &str = "Server is back online!";
+const BACK_ONLINE_WAIT_SECS: u64 = 300;
+
+// Wait (in the background) for the API to answer again, then broadcast the welcome once.
+fn greet_when_back(rest: &RestState, watch: Arc<ServerWatch>, greet: Arc<AtomicUsize>, cfg: &ApiConfig) {
+    let msg = cfg.restart_complete_message.clone().unwrap_or_else(|| DEFAULT_RESTART_COMPLETE_MESSAGE.to_string());
+    let my_id = greet.fetch_add(1, Ordering::SeqCst) + 1;
+    if msg.trim().is_empty() { return; }
+    let cfg = cfg.clone();
+    let rest = rest.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut waited = 0;
+        while waited < BACK_ONLINE_WAIT_SECS {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            waited += 5;
+            if greet.load(Ordering::SeqCst) != my_id { return; }
+            if server_is_up(&rest, &cfg.base_url, &admin_login(&cfg)).await {
+                note_server_reachable(&rest, &watch, &cfg, true);
+                let _ = announce_multi(&rest, &rest.client(), &cfg, &msg).await;
+                return;
+            }
+        }
+    });
+}
+
+fn restart_client(rest: &RestState) -> Result<reqwest::Client, String> {
+    rest.save_client().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn restart_now(state: State<'_, AppState>, seconds: Option<u64>) -> Result<(), String> {
+    let cfg = state.config.lock().clone();
+    let lead = seconds.unwrap_or(60);
+    // single client used for all steps
+    let client = restart_client(&state.rest)?;
+
+    if let Some(hook) = discord_hook(&state.rest, &cfg) {
+        discord_embed(&hook, &format!("Manual restart scheduled in {} seconds.", lead), COLOR_INFO).await;
+    }
+    // cancel_restart bumps RESTART_GEN to abort before the shutdown is sent
+    let my_gen = RESTART_GEN.load(Ordering::SeqCst);
+    perform_restart(
+        &state.rest,
+        &state.watch,
+        &state.daily,
+        &state.greet_gen,
+        &client,
+        &cfg,
+        lead,
+        Some((&RESTART_GEN, my_gen)),
+        "Manual restart",
+        state.server_pid.clone(),
+        &state.shutdown_shape,
+    )
+    .await;
+    Ok(())
+}
+
+// Warning given by hard_restart: enough for one in-game broadcast, not for a full countdown.
+const HARD_RESTART_LEAD_SECS: u64 = 10;
+
+// Emergency restart: save right away, brief warning, then the usual shutdown/wait/start sequence.
+// Independent of restart_times and the scheduler; cancel_restart can still stop it during the warning.
+#[tauri::command]
+async fn hard_restart(state: State<'_, AppState>) -> Result<(), String> {
+    let cfg = state.config.lock().clone();
+    if !cfg.allow_actions { return Err("actions disabled".into()); }
+    let client = restart_client(&state.rest)?;
+    audit("hard_restart", &cfg.base_url, true, None);
+    if let Some(hook) = discord_hook(&state.rest, &cfg) {
+        discord_embed(&hook, &format!("Emergency restart: server going down in {} seconds.", HARD_RESTART_LEAD_SECS), COLOR_ERROR).await;
+    }
+    let _ = request_save(&state.rest, &client, &cfg.base_url, &admin_login(&cfg)).await;
+    let my_gen = RESTART_GEN.load(Ordering::SeqCst);
+    perform_restart(
+        &state.rest,
+        &state.watch,
+        &state.daily,
+        &state.greet_gen,
+        &client,
+        &cfg,
+        HARD_RESTART_LEAD_SECS,
+        Some((&RESTART_GEN, my_gen)),
+        "Emergency restart",
+        state.server_pid.clone(),
+        &state.shutdown_shape,
+    )
+    .await;
+    Ok(())
+}
+
+#[derive(Serialize)]
+struct SelfTestStep {
+    step: &'static str,
+    ok: bool,
+    // true when the step was only described, not performed
+    simulated: bool,
+    detail: String,
+}
+
+// Walk the restart sequence against the configured server without taking it down: the save is
+// really requested, while the countdown, shutdown and start are reported as what would be sent.
+#[tauri::command]
+async fn restart_self_test(state: State<'_, AppState>) -> Result<Vec<SelfTestStep>, String> {
+    let cfg = state.config.lock().clone();
+    let learned = *state.shutdown_shape.lock();
+    let client = restart_client(&state.rest)?;
+    let (base, login) = (cfg.base_url.clone(), admin_login(&cfg));
+    let mut steps = Vec::new();
+    let mut push = |step, ok, simulated, detail: String| steps.push(SelfTestStep { step, ok, simulated, detail });
+
+    push("config", !base.trim().is_empty() && cfg.allow_actions, false, if base.trim().is_empty() {
+        "base_url is not set".into()
+    } else if !cfg.allow_actions {
+        "actions are disabled, so the scheduler would not restart".into()
+    } else {
+        format!("server {}", base)
+    });
+    match fetch_server_info(&state.rest, &cfg).await {
+        Ok(info) => push("reachable", true, false, format!("/info answered (version {})", info.version.unwrap_or_else(|| "unknown".into()))),
+        Err(e) => push("reachable", false, false, format!("/info failed: {}", e)),
+    }
+    let warnings: Vec<String> = preview_countdown(TRIGGERED_RESTART_LEAD_SECS)
+        .into_iter()
+        .map(|(at, msg)| format!("+{}s \"{}\"", at, msg))
+        .collect();
+    push("countdown", true, true, format!("would broadcast: {}", warnings.join(", ")));
+    match request_save(&state.rest, &client, &base, &login).await {
+        Ok(status) => push("save", status.is_success(), false, format!("POST /save -> {}", status)),
+        Err(e) => push("save", false, false, format!("POST /save failed: {}", e)),
+    }
+    let settle = cfg.save_settle_secs.unwrap_or(DEFAULT_SAVE_SETTLE_SECS);
+    push("settle", true, true, format!("would wait {}s (up to {}s more while saving)", settle, SAVE_SETTLE_MAX_EXTRA_SECS));
+    if cfg.backup_before_restart {
+        match resolve_backup_paths(&cfg, None, None) {
+            Ok((src, dest)) => push("pre_restart_backup", true, true, format!("would zip {} into {}", src.display(), dest.display())),
+            Err(e) => push("pre_restart_backup", false, true, e),
+        }
+    }
+    let shapes: Vec<&str> = shutdown_order(learned).into_iter().map(|i| SHUTDOWN_SHAPES[i]).collect();
+    push("shutdown", true, true, format!("would POST /shutdown trying body shapes {}", shapes.join(", ")));
+    push("wait_down", true, true, format!("would wait up to {}s for the API to stop answering", SHUTDOWN_WAIT_SECS));
+    match cfg.start_cmd.as_deref().map(str::trim).filter(|c| !c.is_empty()) {
+        None => push("start", true, true, "no start_cmd; the server would have to come back on its own".into()),
+        Some(c) => {
+            let (program, args) = start_invocation(c, cfg.shell_override.as_deref());
+            let cwd = start_cwd(c, cfg.start_cmd_cwd.as_deref());
+            let cwd_ok = cwd.as_ref().is_none_or(|d| d.is_dir());
+            let mut detail = format!("would run {} {}", program, args.join(" "));
+            if let Some(d) = &cwd {
+                detail.push_str(&format!(" in {}{}", d.display(), if cwd_ok { "" } else { " (folder missing)" }));
+            }
+            push("start", cwd_ok, true, detail);
+        }
+    }
+    audit("restart_self_test", &base, steps.iter().all(|s| s.ok), None);
+    Ok(steps)
+}
+
+// Lead time used when a scheduled restart is triggered by hand (the longest warning checkpoint).
+const TRIGGERED_RESTART_LEAD_SECS: u64 = 60;
+
+// Run exactly what the scheduler would run at a restart time, right now.
+#[tauri::command]
+async fn trigger_scheduled_restart_now(state: State<'_, AppState>) -> Result<(), String> {
+    let cfg = state.config.lock().clone();
+    if !cfg.allow_actions { return Err("actions disabled".into()); }
+    let client = restart_client(&state.rest)?;
+    let my_gen = RESTART_GEN.load(Ordering::SeqCst);
+    perform_restart(
+        &state.rest,
+        &state.watch,
+        &state.daily,
+        &state.greet_gen,
+        &client,
+        &cfg,
+        TRIGGERED_RESTART_LEAD_SECS,
+        Some((&RESTART_GEN, my_gen)),
+        "Auto-restart",
+        state.server_pid.clone(),
+        &state.shutdown_shape,
+    )
+    .await;
+    Ok(())
+}
+// Validate backup paths passed to set_config and make them absolute. A missing source is only a
+// warning (an unmounted drive shouldn't block the whole config); one that is a file is an error.
+// The destination may not sit inside the source (apart from the default <source>/_backups,
+// which zipping skips); set_config creates it once everything else has passed. Empty strings
+// pass through.
+fn check_backup_paths(
+    src: Option<String>,
+    dest: Option<String>,
+    current: &ApiConfig,
+    warnings: &mut Vec<String>,
+) -> Result<(Option<String>, Option<String>), String> {
+    let absolute = |p: &str| std::path::absolute(p.trim()).map_err(|e| format!("invalid path '{}': {}", p, e));
+    let (src_raw, dest_raw) = (src.clone(), dest.clone());
+    let src = match src.filter(|s| !s.trim().is_empty()) {
+        Some(s) => {
+            let p = absolute(&s)?;
+            if !p.exists() {
+                warnings.push(format!("backup source {} does not exist; backups fail until it does", p.display()));
+            } else if !p.is_dir() {
+                return Err(format!("backup source {} is not a folder", p.display()));
+            }
+            Some(p)
+        }
+        None => None,
+    };
+    let dest = match dest.filter(|d| !d.trim().is_empty()) {
+        Some(d) => {
+            let p = absolute(&d)?;
+            if p.exists() && !p.is_dir() {
+                return Err(format!("backup destination {} is a file, not a folder", p.display()));
+            }
+            Some(p)
+        }
+        None => None,
+    };
+    let src_for_check = src.clone().or_else(|| current.backup_dir.as_deref().map(PathBuf::from));
+    let dest_for_check = dest.clone().or_else(|| current.backup_dest_dir.as_deref().map(PathBuf::from));
+    if let (Some(s), Some(d)) = (src_for_check, dest_for_check) {
+        // a destination that doesn't exist yet can't be canonicalized; compare it as given
+        let (s, d) = (s.canonicalize().unwrap_or(s), d.canonicalize().unwrap_or(d));
+        if d.starts_with(&s) && d != s.join("_backups") {
+            return Err(format!(
+                "backup destination {} is inside the source {}; backups would include earlier backups",
+                d.display(),
+                s.display()
+            ));
+        }
+    }
+    let text = |p: PathBuf| p.to_string_lossy().to_string();
+    Ok((src.map(text).or(src_raw), dest.map(text).or(dest_raw)))
+}
+
+/* ------------ optional stub for manual backup button ------------ */
+
+// Source folder and destination root for a manual backup, overrides first.
+fn resolve_backup_paths(
+    cfg: &ApiConfig,
+    src_override: Option<String>,
+    dest_override: Option<String>,
+) -> Result<(PathBuf, PathBuf), String> {
+    let src_s = src_override
+        .or(cfg.backup_dir.clone())
+        .ok_or_else(|| "backup source not configured".to_string())?;
+    let src = PathBuf::from(src_s);
+    if !src.exists() {
+        return Err(format!("backup source not found: {}", src.display()));
+    }
+    let dest_root = if let Some(d) = dest_override.or(cfg.backup_dest_dir.clone()) {
+        PathBuf::from(d)
+    } else {
+        src.join("_backups")
+    };
+    Ok((src, dest_root))
+}
+
+// Steam library roots: the default install locations plus any extra libraries listed in
+// steamapps/libraryfolders.vdf.
+fn steam_libraries() -> Vec<PathBuf> {
+    let mut roots = vec![
+        PathBuf::from(r"C:\Program Files (x86)\Steam"),
+        PathBuf::from(r"C:\Program Files\Steam"),
+        PathBuf::from(r"C:\steamcmd"),
+    ];
+    if let Some(home) = dirs::home_dir() {
+        roots.push(home.join(".steam").join("steam"));
+        roots.push(home.join(".local").join("share").join("Steam"));
+        roots.push(home.join("Steam"));
+        roots.push(home.join("steamcmd"));
+    }
+    let path_re = regex::Regex::new(r#""path"\s+"([^"]+)""#).expect("valid vdf regex");
+    let mut libs = roots.clone();
+    for root in &roots {
+        let Ok(vdf) = std::fs::read_to_string(root.join("steamapps").join("libraryfolders.vdf")) else { continue };
+        for caps in path_re.captures_iter(&vdf) {
+            libs.push(PathBuf::from(caps[1].replace("\\\\", "\\")));
+        }
+    }
+    libs
+}
+
+// Existing Palworld SaveGames folders in the usual places, for one-click backup_dir setup.
+#[tauri::command]
+fn detect_save_dir(state: State<'_, AppState>) -> Vec<String> {
+    let start_cmd = state.config.lock().start_cmd.clone();
+    let save_games = |install: &Path| install.join("Pal").join("Saved").join("SaveGames");
+    let mut candidates: Vec<PathBuf> = Vec::new();
+    // the launcher usually sits in (or a few levels below) the server install
+    if let Some(c) = start_cmd {
+        let exe = PathBuf::from(c.trim().trim_matches('"'));
+        for dir in exe.ancestors().skip(1).take(4) {
+            candidates.push(save_games(dir));
+        }
+    }
+    for lib in steam_libraries() {
+        let common = lib.join("steamapps").join("common");
+        candidates.push(save_games(&common.join("PalServer")));
+        candidates.push(save_games(&common.join("Palworld")));
+    }
+    // local game client saves (co-op / single player worlds)
+    if let Some(local) = dirs::data_local_dir() {
+        candidates.push(save_games(&local.join("Pal")));
+    }
+    let mut seen = HashSet::new();
+    candidates
+        .into_iter()
+        .filter(|p| p.is_dir())
+        .map(|p| p.to_string_lossy().to_string())
+        .filter(|p| seen.insert(p.clone()))
+        .collect()
+}
+
+// Explicit cleanup for disk emergencies: deletes automatic backups older than the given age,
+// or all of them. Named and pre-restart archives are not touched.
+#[tauri::command]
+async fn purge_backups(state: State<'_, AppState>, older_than_days: Option<u64>) -> Result<usize, String> {
+    let cfg = state.config.lock().clone();
+    if !cfg.allow_actions { return Err("actions disabled".into()); }
+    let dest_root = match (&cfg.backup_dest_dir, &cfg.backup_dir) {
+        (Some(d), _) => PathBuf::from(d),
+        (None, Some(s)) => PathBuf::from(s).join("_backups"),
+        (None, None) => return Err("backup destination not configured".into()),
+    };
+    let pattern = backup_name_regex(backup_template(&cfg));
+    let days = older_than_days.unwrap_or(0);
+    let (removed, freed) = remove_backups_older_than(&dest_root, days, &pattern).map_err(|e| e.to_string())?;
+    audit("purge_backups", &dest_root.to_string_lossy(), true, Some(format!("{} file(s), {} bytes", removed, freed)));
+    if let Some(h) = discord_hook(&state.rest, &cfg) {
+        let scope = if days == 0 { "all backups".to_string() } else { format!("backups older than {} days", days) };
+        let msg = format!("Purged {} ({} file(s), {:.1} MB freed).", scope, removed, freed as f64 / 1_048_576.0);
+        discord_embed(&h, &msg, COLOR_SUCCESS).await;
+    }
+    Ok(removed)
+}
+
+// The server dropped without a shutdown from this app: zip the save as it was left, before a
+// restart can overwrite it. crash-*.zip never matches the backup template, so CRASH_BACKUP_KEEP
+// applies instead of the retention settings.
+fn on_unexpected_down(rest: &RestState, cfg: &ApiConfig) {
+    if !cfg.backup_on_crash { return; }
+    let cfg = cfg.clone();
+    let rest = rest.clone();
+    tauri::async_runtime::spawn(async move {
+        let res = async {
+            let (src, dest_root) = resolve_backup_paths(&cfg, None, None)?;
+            std::fs::create_dir_all(&dest_root).map_err(|e| e.to_string())?;
+            let dest = dest_root.join(format!("crash-{}.zip", Local::now().format("%Y%m%d-%H%M%S")));
+            let dest_clone = dest.clone();
+            tokio::task::spawn_blocking(move || -> anyhow::Result<()> {
+                zip_directory(&src, &dest_clone, |_, _| {})?;
+                prune_prefixed_backups(&dest_root, "crash-", CRASH_BACKUP_KEEP);
+                Ok(())
+            })
+            .await
+            .map_err(|e| e.to_string())?
+            .map_err(|e| e.to_string())?;
+            Ok::<PathBuf, String>(dest)
+        }
+        .await;
+        audit("crash_backup", &cfg.base_url, res.is_ok(), res.as_ref().err().cloned());
+        if let Some(h) = discord_hook(&rest, &cfg) {
+            let msg = match &res {
+                Ok(p) => format!("Server went down unexpectedly. Crash backup created: {}", p.display()),
+                Err(e) => format!("Server went down unexpectedly. Crash backup failed: {}", e),
+            };
+            discord_embed(&h, &msg, COLOR_ERROR).await;
+        }
+    });
+}
+
+// Rollback archives kept per destination; these never match the backup template, so the
+// regular retention pruning leaves them alone and this count applies instead.
+const PRE_RESTART_KEEP: usize = 3;
+const CRASH_BACKUP_KEEP: usize = 5;
+const PRE_RESTORE_KEEP: usize = 5;
+
+async fn pre_restart_backup(cfg: &ApiConfig) -> Result<PathBuf, String> {
+    let (src, dest_root) = resolve_backup_paths(cfg, None, None)?;
+    std::fs::create_dir_all(&dest_root).map_err(|e| e.to_string())?;
+    let dest = dest_root.join(format!("pre-restart-{}.zip", Local::now().format("%Y%m%d-%H%M%S")));
+    let dest_clone = dest.clone();
+    tokio::task::spawn_blocking(move || -> anyhow::Result<()> {
+        zip_directory(&src, &dest_clone, |_, _| {})?;
+        prune_prefixed_backups(&dest_root, "pre-restart-", PRE_RESTART_KEEP);
+        Ok(())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())?;
+    Ok(dest)
+}
+
+// Keep only the newest `keep` archives named <prefix><ts>.zip (timestamped names sort oldest first).
+fn prune_prefixed_backups(dir: &Path, prefix: &str, keep: usize) -> usize {
+    let Ok(rd) = std::fs::read_dir(dir) else { return 0 };
+    let mut archives: Vec<PathBuf> = rd
+        .filter_map(Result::ok)
+        .map(|e| e.path())
+        .filter(|p| {
+            let name = p.file_name().and_then(|n| n.to_str()).unwrap_or("");
+            name.starts_with(prefix) && name.ends_with(".zip")
+        })
+        .collect();
+    archives.sort();
+    let excess = archives.len().saturating_sub(keep);
+    archives.iter().take(excess).filter(|p| std::fs::remove_file(p).is_ok()).count()
+}
+
+// One-off backup under an admin-chosen name ("before-mod-update.zip"). The name must not look
+// like an automatic backup, so retention pruning never removes it.
+#[tauri::command]
+async fn backup_named(
+    state: State<'_, AppState>,
+    name: String,
+    src_override: Option<String>,
+    dest_override: Option<String>,
+) -> Result<String, String> {
+    let cfg = state.config.lock().clone();
+    if !cfg.allow_actions { return Err("actions disabled".into()); }
+    let trimmed = name.trim();
+    let stem = trimmed
+        .strip_suffix(".zip")
+        .or_else(|| trimmed.strip_suffix(".ZIP"))
+        .unwrap_or(trimmed);
+    if stem.is_empty() { return Err("backup name is empty".into()); }
+    let file_name = with_zip_ext(&sanitize_file_part(stem));
+    if let Some(prefix) = RESERVED_BACKUP_PREFIXES.iter().find(|p| file_name.starts_with(*p)) {
+        return Err(format!("'{}' starts with '{}', which is reserved for automatic safety archives; pick another name", file_name, prefix));
+    }
+    if backup_name_regex(backup_template(&cfg)).is_match(&file_name) {
+        return Err(format!("'{}' matches the automatic backup naming and would be pruned; pick another name", file_name));
+    }
+    let (src, dest_root) = resolve_backup_paths(&cfg, src_override, dest_override)?;
+    let dest = dest_root.join(&file_name);
+    if dest.exists() {
+        return Err(format!("backup already exists: {}", dest.display()));
+    }
+    if cfg.backup_save_first {
+        pre_backup_save(&state.rest, &cfg.base_url, &admin_login(&cfg), discord_hook(&state.rest, &cfg)).await;
+    }
+    let _ = std::fs::create_dir_all(&dest_root);
+    let dest_clone = dest.clone();
+    let daily = state.daily.clone();
+    let result = tokio::task::spawn_blocking(move || -> anyhow::Result<()> {
+        zip_directory(&src, &dest_clone, |_, _| {})?;
+        record_save_size(&src);
+        daily.lock().backups += 1;
+        Ok(())
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+    let (msg, color) = match &result {
+        Ok(()) => (format!("Named backup created: {}", dest.display()), COLOR_SUCCESS),
+        Err(e) => (format!("Named backup failed: {}", e), COLOR_ERROR),
+    };
+    if let Some(h) = discord_hook(&state.rest, &cfg) {
+        discord_embed(&h, &msg, color).await;
+    }
+    result.map(|()| dest.to_string_lossy().to_string()).map_err(|e| e.to_string())
+}
+
+// Payload of the "backup-progress" event emitted while backup_now zips.
+#[derive(Clone, Serialize)]
+struct BackupProgress {
+    dest: String,
+    done: usize,
+    total: usize,
+}
+
+#[tauri::command]
+async fn backup_now(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    src_override: Option<String>,
+    dest_override: Option<String>,
+) -> Result<String, String> {
+    let cfg = state.config.lock().clone();
+    if !cfg.allow_actions { return Err("actions disabled".into()); }
+    let (src, dest_root) = resolve_backup_paths(&cfg, src_override, dest_override)?;
+    if cfg.backup_save_first {
+        pre_backup_save(&state.rest, &cfg.base_url, &admin_login(&cfg), discord_hook(&state.rest, &cfg)).await;
+    }
+    let _ = std::fs::create_dir_all(&dest_root);
+    let pattern = backup_name_regex(backup_template(&cfg));
+    let retention = backup_retention_days(&cfg);
+    let max_count = cfg.backup_max_count.filter(|n| *n > 0);
+    let space = {
+        let (src, dest_root, pattern) = (src.clone(), dest_root.clone(), pattern.clone());
+        tokio::task::spawn_blocking(move || ensure_backup_space(&src, &dest_root, &pattern, retention, max_count))
+            .await
+            .map_err(|e| e.to_string())?
+    };
+    if space.removed > 0 || !space.ok {
+        if let Some(h) = discord_hook(&state.rest, &cfg) {
+            discord_embed(&h, &backup_space_message(&space), if space.ok { COLOR_INFO } else { COLOR_ERROR }).await;
+        }
+        if !space.ok {
+            note_backup_result(&state.backup_streak, discord_hook(&state.rest, &cfg), Err(backup_space_message(&space))).await;
+            return Err(backup_space_message(&space));
+        }
+    }
+    let dest = dest_root.join(next_backup_name(&state.rest, &cfg).await);
+    let src_clone = src.clone();
+    let dest_clone = dest.clone();
+    let daily = state.daily.clone();
+    let result = tokio::task::spawn_blocking(move || -> anyhow::Result<()> {
+        let dest_name = dest_clone.to_string_lossy().to_string();
+        // roughly one event per percent, plus the final one
+        let mut last_pct = None;
+        zip_directory(&src_clone, &dest_clone, |done, total| {
+            let pct = done * 100 / total.max(1);
+            if last_pct != Some(pct) || done == total {
+                last_pct = Some(pct);
+                let _ = app.emit_all("backup-progress", BackupProgress { dest: dest_name.clone(), done, total });
+            }
+        })?;
+        record_save_size(&src_clone);
+        daily.lock().backups += 1;
+        Ok(())
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+    note_backup_result(&state.backup_streak, discord_hook(&state.rest, &cfg), result.as_ref().map(|_| ()).map_err(|e| e.to_string())).await;
+    match result {
+        Ok(()) => {
+            if let Some(h) = discord_hook(&state.rest, &cfg) {
+                discord_embed(&h, &format!("Manual backup created: {}", dest.display()), COLOR_SUCCESS).await;
+                match prune_old_backups(&dest_root, retention, &pattern) {
+                    Ok(n) if n > 0 => discord_embed(&h, &format!("Pruned {} backup(s) older than {} days.", n, retention), COLOR_INFO).await,
+                    Ok(_) => {}
+                    Err(e) => discord_embed(&h, &format!("Prune old backups failed: {}", e), COLOR_ERROR).await,
+                }
+                if let Some(max) = cfg.backup_max_count.filter(|n| *n > 0) {
+                    let n = prune_backups_by_count(&dest_root, max, &pattern);
+                    if n > 0 {
+                        discord_embed(&h, &format!("Pruned {} backup(s) beyond the newest {}.", n, max), COLOR_INFO).await;
+                    }
+                }
+            } else {
+                let _ = prune_old_backups(&dest_root, retention, &pattern);
+                if let Some(max) = cfg.backup_max_count.filter(|n| *n > 0) { prune_backups_by_count(&dest_root, max, &pattern); }
+            }
+            Ok(dest.to_string_lossy().to_string())
+        }
+        Err(e) => {
+            if let Some(h) = discord_hook(&state.rest, &cfg) {
+                discord_embed(&h, &format!("Manual backup failed: {}", e), COLOR_ERROR).await;
+            }
+            Err(e.to_string())
+        }
+    }
+}
+
+// Uncompressed save size recorded at each backup, oldest first.
+#[tauri::command]
+fn save_size_history() -> Vec<SaveSizeSample> {
+    load_data(SAVE_SIZES_FILE).unwrap_or_default()
+}
+
+// No write anywhere in the save folder for this long means the server may not be saving.
+const SAVE_STALE_WARN_SECS: i64 = 3 * 3600;
+const SAVE_LARGE_FILE_BYTES: u64 = 512 * 1024 * 1024;
+
+#[derive(Serialize, Default)]
+struct SaveDirDiagnostics {
+    path: String,
+    exists: bool,
+    total_bytes: u64,
+    file_count: usize,
+    newest_write: Option<DateTime<Utc>>,
+    largest_file: Option<(String, u64)>,
+    warnings: Vec<String>,
+}
+
+fn diagnose_save_dir(src: &Path, now: DateTime<Utc>) -> SaveDirDiagnostics {
+    let mut d = SaveDirDiagnostics { path: src.to_string_lossy().to_string(), exists: src.is_dir(), ..Default::default() };
+    if !d.exists {
+        d.warnings.push(format!("save folder does not exist: {}", src.display()));
+        return d;
+    }
+    let src_abs = src.canonicalize().unwrap_or_else(|_| src.to_path_buf());
+    let backups_dir = src_abs.join("_backups");
+    let (mut level, mut players) = (None, 0usize);
+    for entry in walkdir::WalkDir::new(&src_abs).into_iter().filter_map(Result::ok) {
+        if !entry.file_type().is_file() || entry.path().starts_with(&backups_dir) { continue; }
+        let Ok(meta) = entry.metadata() else { continue };
+        d.file_count += 1;
+        d.total_bytes += meta.len();
+        if let Ok(m) = meta.modified() {
+            let m = DateTime::<Utc>::from(m);
+            d.newest_write = d.newest_write.max(Some(m));
+        }
+        let rel = entry.path().strip_prefix(&src_abs).unwrap_or(entry.path()).to_string_lossy().to_string();
+        if d.largest_file.as_ref().is_none_or(|(_, n)| meta.len() > *n) {
+            d.largest_file = Some((rel.clone(), meta.len()));
+        }
+        let name = entry.file_name().to_string_lossy();
+        if name.eq_ignore_ascii_case("Level.sav") {
+            level = Some(meta.len());
+        } else if name.ends_with(".sav") && entry.path().parent().and_then(|p| p.file_name()).is_some_and(|p| p == "Players") {
+            players += 1;
+        }
+    }
+    match level {
+        None => d.warnings.push("no Level.sav found; backup_dir may not point at the world save folder".into()),
+        Some(0) => d.warnings.push("Level.sav is empty".into()),
+        Some(_) => {}
+    }
+    if level.is_some() && players == 0 {
+        d.warnings.push("no player saves (Players/*.sav) found next to Level.sav".into());
+    }
+    if let Some(newest) = d.newest_write {
+        let age = (now - newest).num_seconds();
+        if age > SAVE_STALE_WARN_SECS {
+            d.warnings.push(format!("nothing has been written for {}h{:02}m; the server may not be saving", age / 3600, age % 3600 / 60));
+        }
+    }
+    if let Some((name, n)) = &d.largest_file {
+        if *n > SAVE_LARGE_FILE_BYTES {
+            d.warnings.push(format!("{} is unusually large ({:.0} MB)", name, *n as f64 / 1_048_576.0));
+        }
+    }
+    d
+}
+
+// Health of the configured save folder: last write, size, largest file and missing save files.
+#[tauri::command]
+async fn save_dir_diagnostics(state: State<'_, AppState>) -> Result<SaveDirDiagnostics, String> {
+    let src = state.config.lock().backup_dir.clone().filter(|s| !s.trim().is_empty()).ok_or("backup_dir not configured")?;
+    tokio::task::spawn_blocking(move || diagnose_save_dir(Path::new(&src), Utc::now()))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+// In-game countdown to a custom event ("PvP event in 30 seconds."); no shutdown involved.
+// Starting a new countdown replaces any running one.
+#[tauri::command]
+fn announce_countdown(state: State<'_, AppState>, total_secs: u64, label: String) -> Result<(), String> {
+    let cfg = state.config.lock().clone();
+    if !cfg.allow_actions { return Err("actions disabled".into()); }
+    let label = label.trim().to_string();
+    if label.is_empty() { return Err("label is empty".into()); }
+    let gen = state.countdown_gen.clone();
+    let my_id = gen.fetch_add(1, Ordering::SeqCst) + 1;
+    if let Some(h) = discord_hook(&state.rest, &cfg) {
+        let msg = format!("Countdown started: {} in {} seconds.", label, total_secs);
+        tauri::async_runtime::spawn(async move { discord_embed(&h, &msg, COLOR_INFO).await });
+    }
+    let rest = state.rest.clone();
+    tauri::async_runtime::spawn(async move {
+        let client = rest.client();
+        let message = |cp: u64| format!("{} in {} seconds.", label, cp);
+        run_countdown(&rest, &client, &cfg, total_secs, None, message, Some((&gen, my_id))).await;
+    });
+    Ok(())
+}
+
+#[tauri::command]
+fn cancel_countdown(state: State<'_, AppState>) {
+    state.countdown_gen.fetch_add(1, Ordering::SeqCst);
+}
+
+// Start (or replace) a recurring broadcast; returns its id. The first message goes out after one interval.
+#[tauri::command]
+fn start_recurring_announce(
+    state: State<'_, AppState>,
+    id: Option<String>,
+    message: String,
+    interval_secs: u64,
+) -> Result<String, String> {
+    let cfg = state.config.lock().clone();
+    if !cfg.allow_actions { return Err("actions disabled".into()); }
+    let message = message.trim().to_string();
+    if message.is_empty() { return Err("message is empty".into()); }
+    if interval_secs < MIN_RECURRING_INTERVAL_SECS {
+        return Err(format!("interval must be at least {} seconds", MIN_RECURRING_INTERVAL_SECS));
+    }
+    let id = id
+        .map(|i| i.trim().to_string())
+        .filter(|i| !i.is_empty())
+        .unwrap_or_else(|| format!("announce-{}", Local::now().format("%Y%m%d-%H%M%S")));
+    {
+        let mut recurring = state.recurring.lock();
+        recurring.insert(id.clone(), RecurringAnnounce { id: id.clone(), message, interval_secs, pool: vec![], next: 0 });
+        save_data(RECURRING_FILE, &*recurring);
+    }
+    restart_recurring_announces(&state, &cfg);
+    Ok(id)
+}
+
+// Rotate through `messages` (tips, rules, links), one per interval, as the "pool" recurring entry.
+// An empty list removes the pool. Keeps the rotation position when the same pool is set again.
+#[tauri::command]
+fn set_announce_pool(state: State<'_, AppState>, messages: Vec<String>, interval_secs: u64) -> Result<(), String> {
+    let cfg = state.config.lock().clone();
+    if !cfg.allow_actions { return Err("actions disabled".into()); }
+    let pool: Vec<String> = messages.iter().map(|m| m.trim().to_string()).filter(|m| !m.is_empty()).collect();
+    if !pool.is_empty() && interval_secs < MIN_RECURRING_INTERVAL_SECS {
+        return Err(format!("interval must be at least {} seconds", MIN_RECURRING_INTERVAL_SECS));
+    }
+    {
+        let mut recurring = state.recurring.lock();
+        if pool.is_empty() {
+            recurring.remove(ANNOUNCE_POOL_ID);
+        } else {
+            let next = recurring.get(ANNOUNCE_POOL_ID).filter(|e| e.pool == pool).map_or(0, |e| e.next);
+            recurring.insert(
+                ANNOUNCE_POOL_ID.to_string(),
+                RecurringAnnounce { id: ANNOUNCE_POOL_ID.to_string(), message: pool[0].clone(), interval_secs, pool, next },
+            );
+        }
+        save_data(RECURRING_FILE, &*recurring);
+    }
+    restart_recurring_announces(&state, &cfg);
+    Ok(())
+}
+
+// Stop one recurring broadcast by id, or all of them when no id is given.
 #[tauri::command]
-async fn get_server_info(state: State<'_, AppState>) -> Result<ServerInfo, String> {
-    let cfg = state.config.lock().clone();
-    let v = api_get_value(&cfg, "info").await.map_err(|e| e.to_string())?;
-    let mut info = coerce_server_info(&v);
-    if info.uptime_seconds.is_none() {
-        if let Ok(mv) = api_get_value(&cfg, "metrics").await {
-            if let Some(up) = u64_alt(&mv, &["uptime", "uptimeSeconds", "Uptime"]) {
-                info.uptime_seconds = Some(up);
+fn stop_recurring_announce(state: State<'_, AppState>, id: Option<String>) -> Result<(), String> {
+    {
+        let mut recurring = state.recurring.lock();
+        match id {
+            Some(id) => {
+                if recurring.remove(id.trim()).is_none() {
+                    return Err(format!("no recurring announcement '{}'", id));
+                }
             }
+            None => recurring.clear(),
         }
+        save_data(RECURRING_FILE, &*recurring);
     }
-    Ok(info)
+    let cfg = state.config.lock().clone();
+    restart_recurring_announces(&state, &cfg);
+    Ok(())
 }
 
 #[tauri::command]
-async fn get_players(state: State<'_, AppState>) -> Result<Vec<Player>, String> {
-    let cfg = state.config.lock().clone();
-    let v = api_get_value(&cfg, "players").await.map_err(|e| e.to_string())?;
-    let mut players = coerce_players(&v);
-    {
-        let mut tr = state.tracker.lock();
-        tr.update_with(&players);
-        for p in players.iter_mut() {
-            if p.connected_seconds.is_none() {
-                p.connected_seconds = tr.connected_for(&p.id);
-            }
-        }
+fn list_recurring_announces(state: State<'_, AppState>) -> Vec<RecurringAnnounce> {
+    let mut out: Vec<RecurringAnnounce> = state.recurring.lock().values().cloned().collect();
+    out.sort_by(|a, b| a.id.cmp(&b.id));
+    out
+}
+
+#[tauri::command]
+fn cancel_restart() {
+    RESTART_GEN.fetch_add(1, Ordering::SeqCst);
+}
+
+fn forget_ban(state: &AppState, player_id: &str) {
+    let mut bans = state.bans.lock();
+    if bans.remove(player_id).is_some() {
+        save_data(BANS_FILE, &*bans);
     }
-    // join/leave detection + optional Discord webhook (use names when possible)
-    let (joined, left, names_current, names_prev, hook_opt) = {
-        let current_ids: HashSet<String> = players.iter().map(|p| p.id.clone()).collect();
-        let current_names: HashMap<String, String> = players
-            .iter()
-            .map(|p| (p.id.clone(), p.name.clone()))
-            .collect();
-        let mut last = state.last_players.lock();
-        let mut lastn = state.last_names.lock();
-        let prev_names = lastn.clone();
-        let joined: Vec<String> = current_ids.difference(&*last).cloned().collect();
-        let left: Vec<String> = last.difference(&current_ids).cloned().collect();
-        *last = current_ids.clone();
-        *lastn = current_names.clone();
-        (joined, left, current_names, prev_names, state.config.lock().discord_webhook.clone())
-    };
-    if let Some(hook) = hook_opt {
-        for id in joined {
-            let name = names_current.get(&id).cloned().unwrap_or(id.clone());
-            discord_embed(&hook, &format!("Player joined: {}", name), COLOR_INFO).await;
+}
+
+// Try multiple endpoints and body shapes for compatibility.
+async fn try_unban(rest: &RestState, cfg: &ApiConfig, player_id: &str) -> Result<(), String> {
+    let paths = ["unban", "pardon"];
+    let bodies = [
+        serde_json::json!({ "steamId": player_id }),
+        serde_json::json!({ "playerId": player_id }),
+        serde_json::json!({ "id": player_id }),
+    ];
+    for p in &paths {
+        for b in &bodies {
+            if api_post_value(rest, cfg, p, Some(b.clone())).await.is_ok() {
+                return Ok(());
+            }
         }
-        for id in left {
-            let name = names_prev.get(&id).cloned().unwrap_or(id.clone());
-            discord_embed(&hook, &format!("Player left: {}", name), COLOR_INFO).await;
+        if api_post_value(rest, cfg, p, None).await.is_ok() {
+            return Ok(());
         }
     }
-    Ok(players)
-}
-
-#[tauri::command]
-fn player_durations(state: State<'_, AppState>) -> HashMap<String, i64> {
-    state
-        .tracker
-        .lock()
-        .seen
-        .iter()
-        .map(|(k, t)| (k.clone(), (Utc::now() - *t).num_seconds()))
-        .collect()
+    Err("unban failed".into())
 }
 
+// Lifting a temp ban by hand also drops its record, which cancels the scheduled auto-unban.
 #[tauri::command]
-async fn announce_message(state: State<'_, AppState>, message: String) -> Result<(), String> {
+async fn unban_player(state: State<'_, AppState>, player_id: String) -> Result<(), String> {
+    let player_id = normalize_player_id(&player_id);
     let cfg = state.config.lock().clone();
-    let body = serde_json::json!({ "message": message });
-    let res = api_post_value(&cfg, "announce", Some(body)).await;
-    // Discord webhook log
-    if let Some(hook) = cfg.discord_webhook.clone() {
+    if !cfg.allow_actions { return Err("actions disabled".into()); }
+    let hook = discord_hook(&state.rest, &cfg);
+    let res = try_unban(&state.rest, &cfg, &player_id).await;
+    if res.is_ok() {
+        forget_ban(&state, &player_id);
+    }
+    if let Some(h) = hook {
         match &res {
-            Ok(_) => discord_embed(&hook, &format!("Broadcast sent: {}", message), COLOR_SUCCESS).await,
-            Err(e) => discord_embed(&hook, &format!("Broadcast failed: {}", e), COLOR_ERROR).await,
+            Ok(()) => discord_embed(&h, &format!("Unban succeeded: {}", player_id), COLOR_SUCCESS).await,
+            Err(_) => discord_embed(&h, &format!("Unban failed: {}", player_id), COLOR_ERROR).await,
         }
     }
-    res.map(|_| ()).map_err(|e| e.to_string())
+    res
+}
+// Try the known kick body shapes, then a bare POST.
+async fn try_kick(rest: &RestState, cfg: &ApiConfig, player_id: &str) -> Result<()> {
+    let bodies = [
+        serde_json::json!({ "steamId": player_id }),
+        serde_json::json!({ "playerId": player_id }),
+        serde_json::json!({ "id": player_id }),
+    ];
+    for b in bodies {
+        if api_post_value(rest, cfg, "kick", Some(b)).await.is_ok() {
+            return Ok(());
+        }
+    }
+    api_post_value(rest, cfg, "kick", None).await?;
+    Ok(())
 }
 
-// Utility: return raw /players JSON pretty-printed for debugging
 #[tauri::command]
-async fn dump_players_json(state: State<'_, AppState>) -> Result<String, String> {
+async fn kick_player(state: State<'_, AppState>, player_id: String) -> Result<(), String> {
     let cfg = state.config.lock().clone();
-    api_get_value(&cfg, "players")
-        .await
-        .map(|v| serde_json::to_string_pretty(&v).unwrap_or_else(|_| v.to_string()))
-        .map_err(|e| e.to_string())
+    if !cfg.allow_actions { return Err("actions disabled".into()); }
+    match try_kick(&state.rest, &cfg, &player_id).await {
+        Ok(()) => {
+            if let Some(h) = discord_hook(&state.rest, &cfg) { discord_embed(&h, &format!("Kick succeeded: {}", player_id), COLOR_SUCCESS).await; }
+            Ok(())
+        }
+        Err(e) => {
+            if let Some(h) = discord_hook(&state.rest, &cfg) { discord_embed(&h, &format!("Kick failed: {} ({})", player_id, e), COLOR_ERROR).await; }
+            Err(e.to_string())
+        }
+    }
 }
 
+// Kick every player currently flagged by stale_players; returns the ids that were kicked.
 #[tauri::command]
-async fn force_save(state: State<'_, AppState>) -> Result<String, String> {
+async fn kick_stale_players(state: State<'_, AppState>) -> Result<Vec<String>, String> {
     let cfg = state.config.lock().clone();
-    let base = cfg.base_url.clone();
-    let pass = cfg.password.clone().unwrap_or_default();
-    if let Some(h) = cfg.discord_webhook.clone() { discord_embed(&h, "Manual save requested.", COLOR_INFO).await; }
-
-    let save_url_for_log = format!("{}/save", v1_base(&base));
-    let return_url = save_url_for_log.clone();
-
-    if SAVING.swap(true, Ordering::SeqCst) {
-        return Ok("save already in progress".into());
+    if !cfg.allow_actions { return Err("actions disabled".into()); }
+    let ids: Vec<String> = state.tracker.lock().stale().into_iter().map(|(id, _)| id).collect();
+    let mut kicked = Vec::new();
+    for id in ids {
+        if try_kick(&state.rest, &cfg, &id).await.is_ok() {
+            state.tracker.lock().silent_polls.remove(&id);
+            kicked.push(id);
+        }
+    }
+    audit("kick_stale_players", &kicked.join(","), true, None);
+    if !kicked.is_empty() {
+        if let Some(h) = discord_hook(&state.rest, &cfg) {
+            discord_embed(&h, &format!("Kicked {} stale player(s): {}", kicked.len(), kicked.join(", ")), COLOR_SUCCESS).await;
+        }
     }
+    Ok(kicked)
+}
 
-    tauri::async_runtime::spawn(async move {
-        let client = match reqwest::Client::builder()
-            .http1_only()
-            .pool_idle_timeout(Duration::from_secs(0))
-            .build()
-        {
-            Ok(c) => c,
-            Err(_) => {
-                SAVING.store(false, Ordering::SeqCst);
-                return;
-            }
-        };
+const BANS_FILE: &str = "bans.json";
 
-        let _ = announce_multi(&client, &base, &pass, "Saving world…").await;
+// How often the expiry task looks for temp bans to lift.
+const BAN_EXPIRY_CHECK_SECS: u64 = 60;
+const MAX_TEMP_BAN_MINS: u64 = 365 * 24 * 60;
 
-        let status_opt = client
-            .post(&save_url_for_log)
-            .basic_auth("admin", Some(&pass))
-            .header(CONTENT_LENGTH, "0")
-            .header(CONNECTION, "close")
-            .header(ACCEPT, "*/*")
-            .header(USER_AGENT, "curl/8.13.0")
-            .send()
-            .await
-            .ok()
-            .map(|r| r.status());
+// A ban issued through this app. The REST API has no ban list, so this is the app's own record.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct BanRecord {
+    id: String,
+    name: Option<String>,
+    reason: Option<String>,
+    banned_at: DateTime<Utc>,
+    // temp bans only: when the expiry task unbans the player; None is permanent
+    #[serde(default)]
+    expires_at: Option<DateTime<Utc>>,
+}
 
-        match status_opt {
-            Some(s) if s.is_success() => {
-                let _ = announce_multi(&client, &base, &pass, "Game saved").await;
-            }
-            Some(s) => {
-                let _ = announce_multi(&client, &base, &pass, &format!("Save failed: {s}")).await;
-            }
-            None => {
-                let _ = announce_multi(&client, &base, &pass, "Save error: request failed").await;
-            }
-        }
+impl BanRecord {
+    // Seconds until a temp ban is lifted (0 once it is due); None for a permanent ban.
+    fn remaining_secs(&self, now: DateTime<Utc>) -> Option<i64> {
+        self.expires_at.map(|at| (at - now).num_seconds().max(0))
+    }
+}
 
-        SAVING.store(false, Ordering::SeqCst);
-    });
+#[derive(Serialize)]
+struct ActiveBan {
+    id: String,
+    name: Option<String>,
+    reason: Option<String>,
+    banned_at: DateTime<Utc>,
+    expires_at: Option<DateTime<Utc>>,
+    remaining_secs: Option<i64>,
+}
 
-    Ok(format!("dispatched POST {}", return_url))
+fn record_bans(state: &AppState, ids: &[String], reason: Option<&str>, expires_at: Option<DateTime<Utc>>) {
+    let names = state.tracker.lock().names.clone();
+    let mut bans = state.bans.lock();
+    for id in ids {
+        bans.insert(
+            id.clone(),
+            BanRecord {
+                id: id.clone(),
+                name: names.get(id).cloned(),
+                reason: reason.map(str::to_string),
+                banned_at: Utc::now(),
+                expires_at,
+            },
+        );
+    }
+    save_data(BANS_FILE, &*bans);
 }
 
+// Bans issued through the app and not lifted yet, newest first. Temp bans carry the time left
+// so the UI can count down; unban_player lifts either kind early.
 #[tauri::command]
-async fn shutdown_server(
-    state: State<'_, AppState>,
-    seconds: Option<u64>,
-    msg: Option<String>,
-) -> Result<(), String> {
-    let cfg = state.config.lock().clone();
-    let s = seconds.unwrap_or(60);
-    let m = msg.unwrap_or_else(|| "Server restarting...".into());
-    if let Some(h) = cfg.discord_webhook.clone() { discord_embed(&h, &format!("Shutdown requested in {}s: {}", s, m), COLOR_INFO).await; }
+fn active_bans(state: State<'_, AppState>) -> Vec<ActiveBan> {
+    let now = Utc::now();
+    let mut out: Vec<ActiveBan> = state
+        .bans
+        .lock()
+        .values()
+        .map(|b| ActiveBan {
+            id: b.id.clone(),
+            name: b.name.clone(),
+            reason: b.reason.clone(),
+            banned_at: b.banned_at,
+            expires_at: b.expires_at,
+            remaining_secs: b.remaining_secs(now),
+        })
+        .collect();
+    out.sort_by_key(|b| std::cmp::Reverse(b.banned_at));
+    out
+}
 
-    tauri::async_runtime::spawn({
-        let cfg = cfg.clone();
-        let m = m.clone();
-        async move {
-            let client = reqwest::Client::new();
-            let base = cfg.base_url.clone();
-            let pass = cfg.password.clone().unwrap_or_default();
-            let _ = announce_multi(&client, &base, &pass, &format!("{} in {} seconds.", m, s)).await;
-            if s > 1 { tokio::time::sleep(Duration::from_secs(s)).await; }
-            // After waiting, send minimal waittime accepted by some providers
-            let bodies = [
-                serde_json::json!({ "waittime": 1, "message": &m }),
-                serde_json::json!({ "seconds": 1,  "message": &m }),
-                serde_json::json!({ "time": 1,     "message": &m }),
-                serde_json::json!({ "duration": 1, "message": &m }),
-            ];
-            for b in bodies {
-                if api_post_value(&cfg, "shutdown", Some(b)).await.is_ok() {
-                    if let Some(h) = cfg.discord_webhook.clone() { let _ = discord_embed(&h, "Shutdown command sent.", COLOR_INFO).await; }
-                    return;
+// Lift temp bans once they expire. Records live in bans.json, so bans that ran out while the
+// app was closed are lifted on the first check; a failed unban is retried on the next one.
+fn spawn_ban_expiry(rest: &RestState, gen: Arc<AtomicUsize>, beats: TaskBeats, bans: Arc<Mutex<HashMap<String, BanRecord>>>, cfg: &ApiConfig) {
+    let my_id = gen.fetch_add(1, Ordering::SeqCst) + 1;
+    let cfg = cfg.clone();
+    let rest = rest.clone();
+    tauri::async_runtime::spawn(async move {
+        task_started(&beats, "ban_expiry", my_id, 2 * BAN_EXPIRY_CHECK_SECS);
+        // ids whose failed unban was already reported, so a retry loop posts once
+        let mut reported: HashSet<String> = HashSet::new();
+        loop {
+            tokio::time::sleep(Duration::from_secs(BAN_EXPIRY_CHECK_SECS)).await;
+            if gen.load(Ordering::SeqCst) != my_id { break; }
+            task_ran(&beats, "ban_expiry");
+            let now = Utc::now();
+            let due: Vec<String> = bans.lock().values().filter(|b| b.remaining_secs(now) == Some(0)).map(|b| b.id.clone()).collect();
+            for id in due {
+                let res = try_unban(&rest, &cfg, &id).await;
+                if res.is_ok() {
+                    let mut map = bans.lock();
+                    // an unban_player or a new ban in the meantime wins over the expiry
+                    if map.get(&id).is_some_and(|b| b.remaining_secs(Utc::now()) == Some(0)) {
+                        map.remove(&id);
+                        save_data(BANS_FILE, &*map);
+                    }
+                } else if !reported.insert(id.clone()) {
+                    continue;
+                }
+                audit("temp_ban_expired", &id, res.is_ok(), res.as_ref().err().cloned());
+                if let Some(h) = discord_hook(&rest, &cfg) {
+                    let (msg, color) = match &res {
+                        Ok(()) => (format!("Temp ban expired: {} unbanned.", id), COLOR_SUCCESS),
+                        Err(e) => (format!("Temp ban expired but unbanning {} failed ({}); retrying.", id, e), COLOR_ERROR),
+                    };
+                    discord_embed(&h, &msg, color).await;
+                }
+                if res.is_ok() {
+                    reported.remove(&id);
                 }
             }
-            let _ = api_post_value(&cfg, "shutdown", None).await;
-            if let Some(h) = cfg.discord_webhook.clone() { let _ = discord_embed(&h, "Shutdown command sent.", COLOR_INFO).await; }
         }
+        task_ended(&beats, "ban_expiry", my_id);
     });
-    Ok(())
 }
 
-#[tauri::command]
-async fn restart_now(state: State<'_, AppState>, seconds: Option<u64>) -> Result<(), String> {
-    let cfg = state.config.lock().clone();
-    let lead = seconds.unwrap_or(60);
-    let base = cfg.base_url.clone();
-    let pass = cfg.password.clone().unwrap_or_default();
-    let start_cmd = cfg.start_cmd.clone();
-
-    // single client used for all steps
-    let client = match reqwest::Client::builder()
-        .http1_only()
-        .pool_idle_timeout(Duration::from_secs(0))
-        .build()
-    {
-        Ok(c) => c,
-        Err(e) => return Err(e.to_string()),
-    };
-
-    // Discord log of scheduling, then staged in-game countdown
-    if let Some(hook) = cfg.discord_webhook.clone() {
-        discord_embed(&hook, &format!("Manual restart scheduled in {} seconds.", lead), COLOR_INFO).await;
-    }
-    warn_countdown(&client, &base, &pass, lead, cfg.discord_webhook.clone()).await;
-
-    // save (best-effort)
-    let _ = client
-        .post(format!("{}/save", v1_base(&base)))
-        .basic_auth("admin", Some(&pass))
-        .header(CONTENT_LENGTH, "0")
-        .header(CONNECTION, "close")
-        .header(ACCEPT, "*/*")
-        .header(USER_AGENT, "curl/8.13.0")
-        .send()
-        .await;
-
-    let _ = announce_multi(&client, &base, &pass, "Restarting server.").await;
-    if let Some(hook) = cfg.discord_webhook.clone() { discord_embed(&hook, "Manual restart executing.", COLOR_INFO).await; }
-    let _ = attempt_shutdown(&base, &pass, cfg.discord_webhook.clone(), "Auto restart").await;
-
-    // wait for REST to go down (max 120s) before starting new instance
-    if let Some(hook) = cfg.discord_webhook.clone() {
-        discord_embed(&hook, "Waiting for server to stop (up to 120s)...", COLOR_INFO).await;
+// Try the known ban body shapes, then a bare POST. `reason` is passed as the server-side message.
+async fn try_ban(rest: &RestState, cfg: &ApiConfig, player_id: &str, reason: Option<&str>) -> Result<()> {
+    let mut bodies = [
+        serde_json::json!({ "steamId": player_id }),
+        serde_json::json!({ "playerId": player_id }),
+        serde_json::json!({ "id": player_id }),
+    ];
+    if let Some(r) = reason {
+        for b in bodies.iter_mut() {
+            b["message"] = Value::from(r);
+        }
     }
-    let stopped = wait_for_server_down(&base, &pass, 120).await;
-    if let Some(hook) = cfg.discord_webhook.clone() {
-        if stopped {
-            discord_embed(&hook, "Server appears offline. Starting new instance...", COLOR_SUCCESS).await;
-        } else {
-            discord_embed(&hook, "Server did not stop in time (120s). Starting anyway.", COLOR_ERROR).await;
+    for b in bodies {
+        if api_post_value(rest, cfg, "ban", Some(b)).await.is_ok() {
+            return Ok(());
         }
     }
+    api_post_value(rest, cfg, "ban", None).await.map(|_| ())
+}
 
-    if let Some(c) = start_cmd {
-        if let Some(hook) = cfg.discord_webhook.clone() { let _ = discord_embed(&hook, &format!("Starting server via: {}", c), COLOR_INFO).await; }
-        if c.trim().to_lowercase().ends_with(".bat") {
-            let _ = Command::new("cmd").args(["/C", &c]).spawn();
-        } else {
-            let _ = Command::new(&c).spawn();
+// `duration_mins` makes it a temp ban that the expiry task lifts; without it the ban is permanent.
+#[tauri::command]
+async fn ban_player(state: State<'_, AppState>, player_id: String, duration_mins: Option<u64>) -> Result<(), String> {
+    let player_id = normalize_player_id(&player_id);
+    let cfg = state.config.lock().clone();
+    if !cfg.allow_actions { return Err("actions disabled".into()); }
+    if let Some(m) = duration_mins.filter(|m| *m == 0 || *m > MAX_TEMP_BAN_MINS) {
+        return Err(format!("temp ban duration must be 1-{} minutes (got {}); omit it for a permanent ban", MAX_TEMP_BAN_MINS, m));
+    }
+    let hook = discord_hook(&state.rest, &cfg);
+    match try_ban(&state.rest, &cfg, &player_id, None).await {
+        Ok(()) => {
+            audit("ban", &player_id, true, None);
+            let expires_at = duration_mins.map(|m| Utc::now() + chrono::Duration::minutes(m as i64));
+            record_bans(&state, std::slice::from_ref(&player_id), None, expires_at);
+            if let Some(h) = hook {
+                let msg = match duration_mins {
+                    Some(m) => format!("Temp ban succeeded: {} for {} minute(s)", player_id, m),
+                    None => format!("Ban succeeded: {}", player_id),
+                };
+                discord_embed(&h, &msg, COLOR_SUCCESS).await;
+            }
+            Ok(())
+        }
+        Err(e) => {
+            audit("ban", &player_id, false, Some(e.to_string()));
+            if let Some(h) = hook { discord_embed(&h, &format!("Ban failed: {} ({})", player_id, e), COLOR_ERROR).await; }
+            Err(e.to_string())
         }
     }
-
-    Ok(())
 }
-/* ------------ optional stub for manual backup button ------------ */
 
+// Max bans in flight at once for ban_many.
+const BULK_BAN_CONCURRENCY: usize = 4;
+
+// Ban a pasted list of ids: normalized, deduplicated, sent with bounded concurrency. Ids that
+// already have a permanent ban in bans.json are skipped and reported as Err("already banned").
+// Returns a per-id result and posts one summary embed instead of one per ban.
 #[tauri::command]
-async fn backup_now(
+async fn ban_many(
     state: State<'_, AppState>,
-    src_override: Option<String>,
-    dest_override: Option<String>,
-) -> Result<String, String> {
+    ids: Vec<String>,
+    reason: Option<String>,
+) -> Result<HashMap<String, Result<(), String>>, String> {
     let cfg = state.config.lock().clone();
     if !cfg.allow_actions { return Err("actions disabled".into()); }
-    let src_s = src_override
-        .or(cfg.backup_dir.clone())
-        .ok_or_else(|| "backup source not configured".to_string())?;
-    let src = PathBuf::from(src_s);
-    if !src.exists() {
-        return Err(format!("backup source not found: {}", src.display()));
-    }
-    let dest_root = if let Some(d) = dest_override.or(cfg.backup_dest_dir.clone()) {
-        PathBuf::from(d)
-    } else {
-        src.join("_backups")
-    };
-    let _ = std::fs::create_dir_all(&dest_root);
-    let ts = chrono::Local::now().format("%Y%m%d-%H%M%S");
-    let dest = dest_root.join(format!("backup-{}.zip", ts));
-    let src_clone = src.clone();
-    let dest_clone = dest.clone();
-    let result = tokio::task::spawn_blocking(move || zip_directory(&src_clone, &dest_clone))
-        .await
-        .map_err(|e| e.to_string())?;
-    match result {
-        Ok(()) => {
-            if let Some(h) = cfg.discord_webhook.clone() {
-                discord_embed(&h, &format!("Manual backup created: {}", dest.display()), COLOR_SUCCESS).await;
-                match prune_old_backups(&dest_root, 3) {
-                    Ok(n) if n > 0 => discord_embed(&h, &format!("Pruned {} backup(s) older than 3 days.", n), COLOR_INFO).await,
-                    Ok(_) => {}
-                    Err(e) => discord_embed(&h, &format!("Prune old backups failed: {}", e), COLOR_ERROR).await,
-                }
+    let mut unique = Vec::new();
+    let mut seen = HashSet::new();
+    let mut already = Vec::new();
+    {
+        let bans = state.bans.lock();
+        for raw in ids {
+            let id = normalize_player_id(&raw);
+            if id.is_empty() || !seen.insert(id.clone()) { continue; }
+            // a temp ban is re-issued as permanent rather than skipped
+            if bans.get(&id).is_some_and(|b| b.expires_at.is_none()) {
+                already.push(id);
             } else {
-                let _ = prune_old_backups(&dest_root, 3);
+                unique.push(id);
             }
-            Ok(dest.to_string_lossy().to_string())
         }
-        Err(e) => {
-            if let Some(h) = cfg.discord_webhook.clone() {
-                discord_embed(&h, &format!("Manual backup failed: {}", e), COLOR_ERROR).await;
+    }
+    let limit = Arc::new(tokio::sync::Semaphore::new(BULK_BAN_CONCURRENCY));
+    let mut handles = Vec::with_capacity(unique.len());
+    for id in unique {
+        let cfg = cfg.clone();
+        let limit = limit.clone();
+        let reason = reason.clone();
+        let target = id.clone();
+        let rest = state.rest.clone();
+        let handle = tauri::async_runtime::spawn(async move {
+            let _permit = limit.acquire_owned().await;
+            try_ban(&rest, &cfg, &target, reason.as_deref()).await.map_err(|e| e.to_string())
+        });
+        handles.push((id, handle));
+    }
+    let mut results = HashMap::new();
+    for (id, handle) in handles {
+        let res = handle.await.unwrap_or_else(|e| Err(e.to_string()));
+        audit("ban", &id, res.is_ok(), res.as_ref().err().cloned());
+        results.insert(id, res);
+    }
+    let banned: Vec<String> = results.iter().filter(|(_, r)| r.is_ok()).map(|(id, _)| id.clone()).collect();
+    record_bans(&state, &banned, reason.as_deref(), None);
+    if let Some(h) = discord_hook(&state.rest, &cfg) {
+        let failed: Vec<&str> = results.iter().filter(|(_, r)| r.is_err()).map(|(id, _)| id.as_str()).collect();
+        let ok = results.len() - failed.len();
+        let mut msg = format!("Bulk ban: {} succeeded, {} failed, {} already banned.", ok, failed.len(), already.len());
+        if let Some(r) = &reason { msg.push_str(&format!(" Reason: {}", r)); }
+        if !failed.is_empty() { msg.push_str(&format!("\nFailed: {}", failed.join(", "))); }
+        discord_embed(&h, &msg, if failed.is_empty() { COLOR_SUCCESS } else { COLOR_ERROR }).await;
+    }
+    results.extend(already.into_iter().map(|id| (id, Err("already banned".to_string()))));
+    Ok(results)
+}
+/* ----------------------- settings editor ----------------------- */
+// The official REST API only has GET /settings; writes need a server or wrapper that adds POST.
+// An empty POST changes nothing, so it doubles as the capability check before any write.
+// Ok(false) means the server answered without supporting it; Err that it couldn't be asked.
+async fn settings_writes_supported(rest: &RestState, cfg: &ApiConfig) -> Result<bool, String> {
+    let client = rest.client();
+    let login = admin_login(cfg);
+    for url in candidate_urls(&cfg.base_url, "settings") {
+        match client.post(&url).admin_auth(&login).json(&serde_json::json!({})).send_limited(rest).await {
+            Ok(r) if r.status().is_success() => return Ok(true),
+            Ok(r) if r.status() == reqwest::StatusCode::UNAUTHORIZED => {
+                return Err("POST /settings -> 401: the server rejected the admin credentials".into())
             }
-            Err(e.to_string())
+            Ok(_) => {}
+            Err(e) => return Err(format!("could not reach the server: {}", e)),
         }
     }
+    Ok(false)
+}
+
+async fn check_settings_writable(rest: &RestState, cfg: &ApiConfig) -> Result<(), String> {
+    match settings_writes_supported(rest, cfg).await? {
+        true => Ok(()),
+        false => Err("this server does not accept POST /settings; the official Palworld REST API can only read settings".into()),
+    }
+}
+
+#[derive(Clone, Copy)]
+enum SettingKind {
+    Bool,
+    Float(f64, f64),
+    Int(i64, i64),
+    Enum(&'static [&'static str]),
+    Text,
+    Secret,
+}
+
+// Known PalWorldSettings keys with labels and the ranges from the official settings guide.
+const SETTINGS_SCHEMA: &[(&str, &str, SettingKind)] = &[
+    ("ServerName", "Server name", SettingKind::Text),
+    ("ServerDescription", "Server description", SettingKind::Text),
+    ("ServerPassword", "Join password", SettingKind::Secret),
+    ("AdminPassword", "Admin password", SettingKind::Secret),
+    ("ServerPlayerMaxNum", "Max players", SettingKind::Int(1, 32)),
+    ("CoopPlayerMaxNum", "Max co-op players", SettingKind::Int(1, 4)),
+    ("Difficulty", "Difficulty", SettingKind::Enum(&["None", "Casual", "Normal", "Hard"])),
+    ("DeathPenalty", "Death penalty", SettingKind::Enum(&["None", "Item", "ItemAndEquipment", "All"])),
+    ("DayTimeSpeedRate", "Day time speed", SettingKind::Float(0.1, 5.0)),
+    ("NightTimeSpeedRate", "Night time speed", SettingKind::Float(0.1, 5.0)),
+    ("ExpRate", "EXP rate", SettingKind::Float(0.1, 20.0)),
+    ("PalCaptureRate", "Pal capture rate", SettingKind::Float(0.5, 2.0)),
+    ("PalSpawnNumRate", "Pal spawn rate", SettingKind::Float(0.5, 3.0)),
+    ("PalDamageRateAttack", "Pal damage dealt", SettingKind::Float(0.1, 5.0)),
+    ("PalDamageRateDefense", "Pal damage taken", SettingKind::Float(0.1, 5.0)),
+    ("PlayerDamageRateAttack", "Player damage dealt", SettingKind::Float(0.1, 5.0)),
+    ("PlayerDamageRateDefense", "Player damage taken", SettingKind::Float(0.1, 5.0)),
+    ("PlayerStomachDecreaceRate", "Player hunger rate", SettingKind::Float(0.1, 5.0)),
+    ("PlayerStaminaDecreaceRate", "Player stamina drain", SettingKind::Float(0.1, 5.0)),
+    ("PlayerAutoHPRegeneRate", "Player HP regen", SettingKind::Float(0.1, 5.0)),
+    ("PlayerAutoHpRegeneRateInSleep", "Player HP regen while sleeping", SettingKind::Float(0.1, 5.0)),
+    ("PalStomachDecreaceRate", "Pal hunger rate", SettingKind::Float(0.1, 5.0)),
+    ("PalStaminaDecreaceRate", "Pal stamina drain", SettingKind::Float(0.1, 5.0)),
+    ("PalAutoHPRegeneRate", "Pal HP regen", SettingKind::Float(0.1, 5.0)),
+    ("PalAutoHpRegeneRateInSleep", "Pal HP regen in Palbox", SettingKind::Float(0.1, 5.0)),
+    ("BuildObjectDamageRate", "Structure damage", SettingKind::Float(0.5, 3.0)),
+    ("BuildObjectDeteriorationDamageRate", "Structure deterioration", SettingKind::Float(0.0, 10.0)),
+    ("CollectionDropRate", "Gatherable item drops", SettingKind::Float(0.5, 3.0)),
+    ("CollectionObjectHpRate", "Gatherable object HP", SettingKind::Float(0.5, 3.0)),
+    ("CollectionObjectRespawnSpeedRate", "Gatherable respawn interval", SettingKind::Float(0.5, 3.0)),
+    ("EnemyDropItemRate", "Enemy drops", SettingKind::Float(0.5, 3.0)),
+    ("WorkSpeedRate", "Work speed", SettingKind::Float(0.1, 5.0)),
+    ("PalEggDefaultHatchingTime", "Egg hatching time (hours)", SettingKind::Float(0.0, 240.0)),
+    ("DropItemMaxNum", "Max dropped items", SettingKind::Int(0, 5000)),
+    ("DropItemAliveMaxHours", "Dropped item lifetime (hours)", SettingKind::Float(0.0, 24.0)),
+    ("BaseCampMaxNum", "Max bases", SettingKind::Int(1, 500)),
+    ("BaseCampWorkerMaxNum", "Max workers per base", SettingKind::Int(1, 50)),
+    ("GuildPlayerMaxNum", "Max guild members", SettingKind::Int(1, 100)),
+    ("AutoResetGuildTimeNoOnlinePlayers", "Guild reset after offline (hours)", SettingKind::Float(0.0, 1000.0)),
+    ("bAutoResetGuildNoOnlinePlayers", "Reset inactive guilds", SettingKind::Bool),
+    ("bIsPvP", "PvP", SettingKind::Bool),
+    ("bEnablePlayerToPlayerDamage", "Player vs player damage", SettingKind::Bool),
+    ("bEnableFriendlyFire", "Friendly fire", SettingKind::Bool),
+    ("bEnableInvaderEnemy", "Raids", SettingKind::Bool),
+    ("bEnableFastTravel", "Fast travel", SettingKind::Bool),
+    ("bIsStartLocationSelectByMap", "Choose start location on map", SettingKind::Bool),
+    ("bExistPlayerAfterLogout", "Keep player body after logout", SettingKind::Bool),
+    ("bEnableDefenseOtherGuildPlayer", "Defend against other guilds", SettingKind::Bool),
+    ("bCanPickupOtherGuildDeathPenaltyDrop", "Loot other guilds' death drops", SettingKind::Bool),
+    ("bEnableNonLoginPenalty", "Penalty for not logging in", SettingKind::Bool),
+    ("bShowPlayerList", "Show player list", SettingKind::Bool),
+    ("bIsUseBackupSaveData", "Built-in save backups", SettingKind::Bool),
+    ("AllowConnectPlatform", "Allowed platform", SettingKind::Enum(&["Steam", "Xbox"])),
+    ("LogFormatType", "Log format", SettingKind::Enum(&["Text", "Json"])),
+];
+
+#[derive(Serialize)]
+struct SettingField {
+    key: String,
+    label: String,
+    // "bool", "float", "int", "enum", "string" or "secret"
+    kind: &'static str,
+    // secrets are never sent to the frontend
+    value: Value,
+    min: Option<f64>,
+    max: Option<f64>,
+    options: Vec<String>,
+    known: bool,
+}
+
+fn setting_fields(current: &serde_json::Map<String, Value>) -> Vec<SettingField> {
+    let mut out = Vec::with_capacity(current.len());
+    for (key, label, kind) in SETTINGS_SCHEMA {
+        let Some(value) = current.get(*key) else { continue };
+        let (kind_name, min, max, options) = match *kind {
+            SettingKind::Bool => ("bool", None, None, vec![]),
+            SettingKind::Float(lo, hi) => ("float", Some(lo), Some(hi), vec![]),
+            SettingKind::Int(lo, hi) => ("int", Some(lo as f64), Some(hi as f64), vec![]),
+            SettingKind::Enum(opts) => ("enum", None, None, opts.iter().map(|o| o.to_string()).collect()),
+            SettingKind::Text => ("string", None, None, vec![]),
+            SettingKind::Secret => ("secret", None, None, vec![]),
+        };
+        let value = if kind_name == "secret" { Value::Null } else { value.clone() };
+        out.push(SettingField { key: key.to_string(), label: label.to_string(), kind: kind_name, value, min, max, options, known: true });
+    }
+    let mut unknown: Vec<(&String, &Value)> =
+        current.iter().filter(|(k, _)| !SETTINGS_SCHEMA.iter().any(|(s, _, _)| s == k)).collect();
+    unknown.sort_by(|a, b| a.0.cmp(b.0));
+    out.extend(unknown.into_iter().map(|(k, v)| SettingField {
+        key: k.clone(),
+        label: k.clone(),
+        kind: "string",
+        value: v.clone(),
+        min: None,
+        max: None,
+        options: vec![],
+        known: false,
+    }));
+    out
 }
 
+// Current /settings values with form metadata (type, range, label) for the settings editor.
+// Keys the schema doesn't know come last as plain string fields.
 #[tauri::command]
-fn cancel_restart() {
-    RESTART_GEN.fetch_add(1, Ordering::SeqCst);
+async fn get_editable_settings(state: State<'_, AppState>) -> Result<Vec<SettingField>, ApiError> {
+    let cfg = state.config.lock().clone();
+    let v = api_get_value(&state.rest, &cfg, "settings").await?;
+    let map = v.as_object().ok_or_else(|| ApiError::Other("settings response is not an object".into()))?;
+    Ok(setting_fields(map))
 }
 
+// Apply several server settings as one batch (servers that accept POST /settings only): every
+// key is checked against the current /settings first, then changes go out one by one; if one fails, the ones already applied are
+// set back to the values read up front. Returns a per-key result and posts one summary embed.
 #[tauri::command]
-async fn unban_player(state: State<'_, AppState>, player_id: String) -> Result<(), String> {
+async fn apply_settings(
+    state: State<'_, AppState>,
+    changes: HashMap<String, Value>,
+) -> Result<HashMap<String, Result<(), String>>, String> {
     let cfg = state.config.lock().clone();
     if !cfg.allow_actions { return Err("actions disabled".into()); }
-    let hook = cfg.discord_webhook.clone();
-    // Try multiple endpoints and body shapes for compatibility
-    let paths = ["unban", "pardon"];
-    let bodies = [
-        serde_json::json!({ "steamId": player_id }),
-        serde_json::json!({ "playerId": player_id }),
-        serde_json::json!({ "id": player_id }),
-    ];
-    for p in &paths {
-        for b in &bodies {
-            if api_post_value(&cfg, p, Some(b.clone())).await.is_ok() {
-                if let Some(h) = hook.clone() { discord_embed(&h, &format!("Unban succeeded: {}", player_id), COLOR_SUCCESS).await; }
-                return Ok(());
+    if changes.is_empty() { return Ok(HashMap::new()); }
+    check_settings_writable(&state.rest, &cfg).await?;
+    let current = api_get_value(&state.rest, &cfg, "settings").await.map_err(|e| format!("could not read settings: {}", e))?;
+    let current = current.as_object().ok_or("settings response is not an object")?;
+    let mut invalid = Vec::new();
+    for (key, value) in &changes {
+        match current.get(key) {
+            None => invalid.push(format!("unknown setting '{}'", key)),
+            Some(prev) if std::mem::discriminant(prev) != std::mem::discriminant(value) => {
+                invalid.push(format!("'{}' expects the same type as its current value {}", key, prev))
             }
+            Some(_) => {}
         }
-        if api_post_value(&cfg, p, None).await.is_ok() {
-            if let Some(h) = hook.clone() { discord_embed(&h, &format!("Unban succeeded: {}", player_id), COLOR_SUCCESS).await; }
-            return Ok(());
+    }
+    if !invalid.is_empty() {
+        return Err(invalid.join("; "));
+    }
+
+    let mut keys: Vec<&String> = changes.keys().collect();
+    keys.sort();
+    let mut results: HashMap<String, Result<(), String>> = HashMap::new();
+    let mut applied: Vec<&String> = Vec::new();
+    let mut failure = None;
+    let mut rollback_failed: Vec<&String> = Vec::new();
+    for key in &keys {
+        match api_post_value(&state.rest, &cfg, "settings", Some(serde_json::json!({ key.as_str(): changes[*key] }))).await {
+            Ok(_) => applied.push(key),
+            Err(e) => {
+                failure = Some(((*key).clone(), e.to_string()));
+                break;
+            }
         }
     }
-    if let Some(h) = hook { discord_embed(&h, &format!("Unban failed: {}", player_id), COLOR_ERROR).await; }
-    Err("unban failed".into())
+    if let Some((failed_key, err)) = &failure {
+        for key in applied.iter().rev() {
+            let restored = api_post_value(&state.rest, &cfg, "settings", Some(serde_json::json!({ key.as_str(): current[key.as_str()] }))).await;
+            let note = match restored {
+                Ok(_) => format!("rolled back after '{}' failed", failed_key),
+                Err(e) => {
+                    rollback_failed.push(key);
+                    format!("applied, but rollback failed: {}", e)
+                }
+            };
+            results.insert((*key).clone(), Err(note));
+        }
+        for key in &keys {
+            results.entry((*key).clone()).or_insert_with(|| Err("not applied".into()));
+        }
+        results.insert(failed_key.clone(), Err(err.clone()));
+    } else {
+        for key in &keys {
+            results.insert((*key).clone(), Ok(()));
+        }
+    }
+
+    let summary: Vec<String> = keys
+        .iter()
+        .map(|k| format!("{}: {} -> {}", k, current[k.as_str()], changes[*k]))
+        .collect();
+    audit("apply_settings", &summary.join("; "), failure.is_none(), failure.as_ref().map(|(k, e)| format!("{}: {}", k, e)));
+    if let Some(h) = discord_hook(&state.rest, &cfg) {
+        let (title, color) = match &failure {
+            None => ("Settings applied".to_string(), COLOR_SUCCESS),
+            Some((k, e)) if applied.is_empty() => (format!("Settings NOT applied ('{}' failed: {})", k, e), COLOR_ERROR),
+            Some((k, e)) if rollback_failed.is_empty() => (format!("Settings NOT applied ('{}' failed: {}), changes rolled back", k, e), COLOR_ERROR),
+            Some((k, e)) => {
+                let names: Vec<&str> = rollback_failed.iter().map(|k| k.as_str()).collect();
+                (format!("Settings PARTLY applied ('{}' failed: {}); rolling back {} failed, those keep the new value", k, e, names.join(", ")), COLOR_ERROR)
+            }
+        };
+        discord_embed(&h, &format!("{}\n{}", title, summary.join("\n")), color).await;
+    }
+    Ok(results)
 }
+
+// Rotate the admin password through POST /settings (AdminPassword; servers that accept settings
+// writes only). It counts as changed once the new password works and the old one gets a 401.
+// If the request itself errored because the old password stopped working mid-request, the new
+// one is still stored so polling stays authenticated, but the call reports the error.
 #[tauri::command]
-async fn kick_player(state: State<'_, AppState>, player_id: String) -> Result<(), String> {
+async fn change_admin_password(state: State<'_, AppState>, new_password: String) -> Result<(), String> {
     let cfg = state.config.lock().clone();
     if !cfg.allow_actions { return Err("actions disabled".into()); }
-    let hook = cfg.discord_webhook.clone();
-    let bodies = [
-        serde_json::json!({ "steamId": player_id }),
-        serde_json::json!({ "playerId": player_id }),
-        serde_json::json!({ "id": player_id }),
-    ];
-    for b in bodies {
-        if api_post_value(&cfg, "kick", Some(b)).await.is_ok() {
-            if let Some(h) = hook.clone() { discord_embed(&h, &format!("Kick succeeded: {}", player_id), COLOR_SUCCESS).await; }
-            return Ok(());
-        }
+    if new_password.trim().is_empty() { return Err("new password is empty".into()); }
+    if cfg.password.as_deref() == Some(new_password.as_str()) { return Err("that is already the admin password".into()); }
+    check_settings_writable(&state.rest, &cfg).await?;
+    let mut with_old = cfg.clone();
+    with_old.fallback_password = None;
+    let mut with_new = with_old.clone();
+    with_new.password = Some(new_password.clone());
+    with_new.auth_required = Some(true);
+
+    let sent = api_post_value(&state.rest, &with_old, "settings", Some(serde_json::json!({ "AdminPassword": new_password }))).await;
+    let new_works = api_get_value(&state.rest, &with_new, "info").await.is_ok();
+    let old_rejected = api_get_value(&state.rest, &with_old, "info")
+        .await
+        .is_err_and(|e| error_status(&e) == Some(reqwest::StatusCode::UNAUTHORIZED));
+    let (store, res) = match (&sent, new_works, old_rejected) {
+        (Ok(_), true, true) => (true, Ok(())),
+        (Err(e), true, true) => (true, Err(format!("the request failed ({}) but the server now only accepts the new password, so it was saved", e))),
+        (Err(e), _, _) => (false, Err(format!("password change failed: {}", e))),
+        (Ok(_), true, false) => (false, Err("the server accepted the request but still answers to the old password too (it may not check auth); nothing was saved".into())),
+        (Ok(_), false, false) => (false, Err("the server accepted the change but still answers to the old password; it probably applies after a restart, so update the password in Settings then".into())),
+        (Ok(_), false, true) => (false, Err("the server accepted the change but neither the old nor the new password works now".into())),
+    };
+    audit("change_admin_password", "", res.is_ok(), res.as_ref().err().cloned());
+    if !store { return res; }
+
+    let snapshot = {
+        let mut c = state.config.lock();
+        c.password = Some(new_password);
+        c.clone()
+    };
+    save_config(&snapshot);
+    apply_config(&state, &snapshot);
+    if let Some(h) = discord_hook(&state.rest, &snapshot) {
+        discord_embed(&h, "Admin password changed.", COLOR_INFO).await;
     }
-    match api_post_value(&cfg, "kick", None).await {
-        Ok(_) => {
-            if let Some(h) = hook { discord_embed(&h, &format!("Kick succeeded: {}", player_id), COLOR_SUCCESS).await; }
-            Ok(())
+    res
+}
+
+/* ------------------- scheduler (specific times) ------------------- */
+
+fn parse_times_hhmm(v: &[String]) -> Vec<NaiveTime> {
+    v.iter()
+        .filter_map(|s| NaiveTime::parse_from_str(s.trim(), "%H:%M").ok())
+        .collect()
+}
+
+// A restart occupies its warning countdown plus the wait for shutdown; times closer
+// than that overlap. Pairs are checked in order, including the wrap past midnight.
+fn restart_schedule_warnings(times: &[String]) -> Vec<String> {
+    let mut parsed = parse_times_hhmm(times);
+    parsed.sort();
+    let min_gap = (COUNTDOWN_CHECKPOINTS.iter().max().copied().unwrap_or(0) + SHUTDOWN_WAIT_SECS) as i64;
+    let mut out = Vec::new();
+    if parsed.len() < 2 {
+        return out;
+    }
+    for (i, a) in parsed.iter().enumerate() {
+        let b = parsed[(i + 1) % parsed.len()];
+        let mut gap = (b - *a).num_seconds();
+        if i + 1 == parsed.len() {
+            gap += 86_400;
         }
-        Err(e) => {
-            if let Some(h) = cfg.discord_webhook.clone() { discord_embed(&h, &format!("Kick failed: {} ({})", player_id, e), COLOR_ERROR).await; }
-            Err(e.to_string())
+        if gap < min_gap {
+            out.push(format!(
+                "{} and {} are {}s apart; restarts need at least {}s (countdown + shutdown) and may overlap",
+                a.format("%H:%M"),
+                b.format("%H:%M"),
+                gap,
+                min_gap
+            ));
         }
     }
+    out
 }
 
-#[tauri::command]
-async fn ban_player(state: State<'_, AppState>, player_id: String) -> Result<(), String> {
-    let cfg = state.config.lock().clone();
-    if !cfg.allow_actions { return Err("actions disabled".into()); }
-    let hook = cfg.discord_webhook.clone();
-    let bodies = [
-        serde_json::json!({ "steamId": player_id }),
-        serde_json::json!({ "playerId": player_id }),
-        serde_json::json!({ "id": player_id }),
-    ];
-    for b in bodies {
-        if api_post_value(&cfg, "ban", Some(b)).await.is_ok() {
-            if let Some(h) = hook.clone() { discord_embed(&h, &format!("Ban succeeded: {}", player_id), COLOR_SUCCESS).await; }
-            return Ok(());
+// Weekday keys are "Mon".."Sun" (full names and any case are accepted too); every time must be HH:MM.
+fn validate_restart_schedule(map: &HashMap<String, Vec<String>>) -> Result<(), String> {
+    for (day, times) in map {
+        if day.trim().parse::<Weekday>().is_err() {
+            return Err(format!("invalid weekday '{}' in restart_schedule: use Mon, Tue, Wed, Thu, Fri, Sat or Sun", day));
+        }
+        for t in times {
+            if NaiveTime::parse_from_str(t.trim(), "%H:%M").is_err() {
+                return Err(format!("invalid restart time '{}' for {}: use HH:MM 24-hour format", t, day));
+            }
         }
     }
-    match api_post_value(&cfg, "ban", None).await {
-        Ok(_) => {
-            if let Some(h) = hook { discord_embed(&h, &format!("Ban succeeded: {}", player_id), COLOR_SUCCESS).await; }
-            Ok(())
+    Ok(())
+}
+
+// Restart times per weekday. The weekday map wins when set (unlisted days get none);
+// otherwise restart_times applies to every day.
+fn restart_times_by_day(cfg: &ApiConfig) -> HashMap<Weekday, Vec<NaiveTime>> {
+    let mut out = HashMap::new();
+    match &cfg.restart_schedule {
+        Some(map) => {
+            for (day, times) in map {
+                if let Ok(d) = day.trim().parse::<Weekday>() {
+                    out.entry(d).or_insert_with(Vec::new).extend(parse_times_hhmm(times));
+                }
+            }
         }
-        Err(e) => {
-            if let Some(h) = cfg.discord_webhook.clone() { discord_embed(&h, &format!("Ban failed: {} ({})", player_id, e), COLOR_ERROR).await; }
-            Err(e.to_string())
+        None => {
+            let times = parse_times_hhmm(&cfg.restart_times);
+            let mut d = Weekday::Mon;
+            for _ in 0..7 {
+                out.insert(d, times.clone());
+                d = d.succ();
+            }
         }
     }
+    out
 }
-/* ------------------- scheduler (specific times) ------------------- */
 
-fn parse_times_hhmm(v: &[String]) -> Vec<NaiveTime> {
-    v.iter()
-        .filter_map(|s| NaiveTime::parse_from_str(s.trim(), "%H:%M").ok())
+// Overlap warnings for the configured schedule; weekday entries are checked per day.
+fn config_schedule_warnings(cfg: &ApiConfig) -> Vec<String> {
+    let Some(map) = &cfg.restart_schedule else {
+        return restart_schedule_warnings(&cfg.restart_times);
+    };
+    let mut days: Vec<(&String, &Vec<String>)> = map.iter().collect();
+    days.sort_by_key(|(d, _)| d.trim().parse::<Weekday>().map(|w| w.num_days_from_monday()).unwrap_or(7));
+    days.into_iter()
+        .flat_map(|(d, times)| restart_schedule_warnings(times).into_iter().map(move |w| format!("{}: {}", d, w)))
         .collect()
 }
 
 fn next_fire_from(now: DateTime<Local>, times: &[NaiveTime]) -> Option<DateTime<Local>> {
-    if times.is_empty() {
-        return None;
+    next_fire_by_day(now, |_| times)
+}
+
+// Earliest time after `now` using each date's own weekday times; looks up to a week ahead.
+fn next_fire_by_day<'a>(now: DateTime<Local>, times_for: impl Fn(Weekday) -> &'a [NaiveTime]) -> Option<DateTime<Local>> {
+    let mut day = now.date_naive();
+    for _ in 0..=7 {
+        let next = times_for(day.weekday())
+            .iter()
+            .filter_map(|t| Local.from_local_datetime(&day.and_time(*t)).single())
+            .filter(|dt| *dt > now)
+            .min();
+        if next.is_some() {
+            return next;
+        }
+        day = day.succ_opt()?;
     }
-    let today = now.date_naive();
-    let mut candidates: Vec<_> = times
-        .iter()
-        .filter_map(|t| Local.from_local_datetime(&today.and_time(*t)).single())
-        .collect();
-    candidates.sort_unstable();
-    for dt in &candidates {
-        if *dt > now {
-            return Some(*dt);
+    None
+}
+
+// Latest scheduled time before `now`, using each date's own weekday times; looks up to a week back.
+fn prev_fire_by_day<'a>(now: DateTime<Local>, times_for: impl Fn(Weekday) -> &'a [NaiveTime]) -> Option<DateTime<Local>> {
+    let mut day = now.date_naive();
+    for _ in 0..=7 {
+        let prev = times_for(day.weekday())
+            .iter()
+            .filter_map(|t| Local.from_local_datetime(&day.and_time(*t)).single())
+            .filter(|dt| *dt <= now)
+            .max();
+        if prev.is_some() {
+            return prev;
         }
+        day = day.pred_opt()?;
     }
-    // tomorrow at the first time
-    let tomorrow = today.succ_opt()?;
-    let mut next_day: Vec<_> = times
-        .iter()
-        .filter_map(|t| Local.from_local_datetime(&tomorrow.and_time(*t)).single())
-        .collect();
-    next_day.sort_unstable();
-    next_day.first().copied()
+    None
 }
 
-fn spawn_scheduler(sched: Arc<AtomicUsize>, cfg: &ApiConfig) {
-    let times = parse_times_hhmm(&cfg.restart_times);
-    let base = cfg.base_url.clone();
-    let pass = cfg.password.clone().unwrap_or_default();
-    let cmd  = cfg.start_cmd.clone();
-    let hook = cfg.discord_webhook.clone();
+/* ----------------------- clock check ----------------------- */
+const NTP_SERVER: &str = "pool.ntp.org:123";
+const NTP_TIMEOUT_SECS: u64 = 3;
+// Seconds between 1900-01-01 (NTP epoch) and 1970-01-01.
+const NTP_UNIX_OFFSET: f64 = 2_208_988_800.0;
+const CLOCK_SKEW_WARN_SECS: f64 = 30.0;
+
+#[derive(Debug, Serialize)]
+struct ClockCheck {
+    source: String,
+    // reference time minus local time; positive means the local clock is behind
+    skew_secs: f64,
+    utc_offset: String,
+    warning: Option<String>,
+}
+
+fn unix_now_f64() -> f64 {
+    std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).map(|d| d.as_secs_f64()).unwrap_or(0.0)
+}
+
+// One SNTP (v3, client mode) exchange; the offset assumes a symmetric round trip.
+async fn sntp_offset(server: &str) -> Result<f64, String> {
+    let sock = tokio::net::UdpSocket::bind("0.0.0.0:0").await.map_err(|e| e.to_string())?;
+    sock.connect(server).await.map_err(|e| e.to_string())?;
+    let mut packet = [0u8; 48];
+    packet[0] = 0x1B;
+    let sent = unix_now_f64();
+    sock.send(&packet).await.map_err(|e| e.to_string())?;
+    let n = tokio::time::timeout(Duration::from_secs(NTP_TIMEOUT_SECS), sock.recv(&mut packet))
+        .await
+        .map_err(|_| format!("no answer from {}", server))?
+        .map_err(|e| e.to_string())?;
+    let received = unix_now_f64();
+    if n < 48 {
+        return Err("short NTP reply".into());
+    }
+    let secs = u32::from_be_bytes([packet[40], packet[41], packet[42], packet[43]]) as f64;
+    let frac = u32::from_be_bytes([packet[44], packet[45], packet[46], packet[47]]) as f64 / 4_294_967_296.0;
+    if secs == 0.0 {
+        return Err("NTP reply has no transmit time".into());
+    }
+    Ok(secs + frac - NTP_UNIX_OFFSET - (sent + received) / 2.0)
+}
+
+// Fallback reference: the Date header on the game server's /info reply (1 s resolution).
+async fn server_date_offset(rest: &RestState, cfg: &ApiConfig) -> Result<f64, String> {
+    let url = candidate_urls(&cfg.base_url, "info").into_iter().next().ok_or("base_url not set")?;
+    let sent = unix_now_f64();
+    let resp = rest.client().get(&url).admin_auth(&admin_login(cfg)).send_limited(rest).await.map_err(|e| e.to_string())?;
+    let received = unix_now_f64();
+    let date = resp
+        .headers()
+        .get(reqwest::header::DATE)
+        .and_then(|d| d.to_str().ok())
+        .and_then(|d| DateTime::parse_from_rfc2822(d).ok())
+        .ok_or("server reply has no Date header")?;
+    Ok(date.timestamp() as f64 + 0.5 - (sent + received) / 2.0)
+}
+
+async fn measure_clock(rest: &RestState, cfg: &ApiConfig) -> Result<ClockCheck, String> {
+    let ntp = if cfg.ntp_check == Some(false) { Err("disabled by ntp_check".to_string()) } else { sntp_offset(NTP_SERVER).await };
+    let (source, skew) = match ntp {
+        Ok(s) => (NTP_SERVER.to_string(), s),
+        Err(ntp_err) => match server_date_offset(rest, cfg).await {
+            Ok(s) => ("game server Date header".to_string(), s),
+            Err(e) => return Err(format!("NTP: {}; game server: {}", ntp_err, e)),
+        },
+    };
+    let skew_secs = (skew * 10.0).round() / 10.0;
+    let warning = (skew_secs.abs() > CLOCK_SKEW_WARN_SECS).then(|| {
+        format!(
+            "local clock is {:.0}s {} ({}); scheduled restarts will fire off by that much",
+            skew_secs.abs(),
+            if skew_secs > 0.0 { "behind" } else { "ahead" },
+            source
+        )
+    });
+    Ok(ClockCheck { source, skew_secs, utc_offset: Local::now().format("%:z").to_string(), warning })
+}
+
+// Compare the local clock with NTP (or the game server's Date header when NTP is blocked).
+// The UTC offset is included so a wrong timezone is easy to spot too.
+#[tauri::command]
+async fn check_clock(state: State<'_, AppState>) -> Result<ClockCheck, String> {
+    let cfg = state.config.lock().clone();
+    measure_clock(&state.rest, &cfg).await
+}
+
+// Config changes respawn the scheduler; the clock is checked on the first spawn and then at most
+// this often rather than on every apply.
+const CLOCK_CHECK_INTERVAL_SECS: u64 = 6 * 3600;
+
+fn spawn_scheduler(
+    rest: &RestState,
+    sched: Arc<AtomicUsize>,
+    status: Arc<Mutex<SchedulerStatus>>,
+    pid_slot: Arc<Mutex<Option<u32>>>,
+    shutdown_shape: Arc<Mutex<Option<usize>>>,
+    watch: Arc<ServerWatch>,
+    daily: Arc<Mutex<DailyStats>>,
+    greet: Arc<AtomicUsize>,
+    cfg: &ApiConfig,
+) {
+    let by_day = restart_times_by_day(cfg);
+    let cfg = cfg.clone();
 
     // bump generation; my_id is what this task will check
     let my_id = sched.fetch_add(1, Ordering::SeqCst) + 1;
 
-    if times.is_empty() {
+    if by_day.values().all(Vec::is_empty) {
         return;
     }
 
+    let check_clock = {
+        let mut status = status.lock();
+        status.running_gen = Some(my_id);
+        let due = status.clock_checked.is_none_or(|t| t.elapsed().as_secs() >= CLOCK_CHECK_INTERVAL_SECS);
+        if due {
+            status.clock_checked = Some(Instant::now());
+        }
+        due
+    };
+    if check_clock {
+        let cfg = cfg.clone();
+        let rest = rest.clone();
+        tauri::async_runtime::spawn(async move {
+            if let Ok(ClockCheck { source, warning: Some(w), .. }) = measure_clock(&rest, &cfg).await {
+                audit("clock_check", &source, false, Some(w.clone()));
+                if let Some(h) = discord_hook(&rest, &cfg) {
+                    discord_embed(&h, &format!("Clock skew: {}", w), COLOR_ERROR).await;
+                }
+            }
+        });
+    }
+    let suspended_until = cfg.restarts_suspended_until.map(|u| u.with_timezone(&Local)).filter(|u| *u > Local::now());
+    if let (Some(until), Some(h)) = (suspended_until, discord_hook(rest, &cfg)) {
+        let sched = sched.clone();
+        tauri::async_runtime::spawn(async move {
+            let wait = (until - Local::now()).num_seconds().max(0) as u64;
+            tokio::time::sleep(Duration::from_secs(wait)).await;
+            if sched.load(Ordering::SeqCst) == my_id {
+                discord_embed(&h, "Scheduled restarts resumed.", COLOR_INFO).await;
+            }
+        });
+    }
+    let rest = rest.clone();
     tauri::async_runtime::spawn(async move {
-        // (rest of the function unchanged)
-        // make sure all references to `state.sched` are replaced with `sched`
         // build shared client
-        let client = match reqwest::Client::builder()
-            .http1_only()
-            .pool_idle_timeout(Duration::from_secs(0))
-            .build()
-        {
-            Ok(c) => c,
-            Err(_) => return,
-        };
+        let Ok(client) = restart_client(&rest) else { return };
 
         loop {
             // canceled/replaced?
@@ -1167,84 +7305,20 @@ fn spawn_scheduler(sched: Arc<AtomicUsize>, cfg: &ApiConfig) {
             }
 
             let now = Local::now();
-            let Some(next_dt) = next_fire_from(now, &times) else {
+            // nothing fires before a suspension ends
+            let from = suspended_until.map_or(now, |u| u.max(now));
+            let Some(next_dt) = next_fire_by_day(from, |d| by_day.get(&d).map(Vec::as_slice).unwrap_or(&[])) else {
                 break;
             };
             let remaining = (next_dt - now).num_seconds().max(0) as u64;
-            warn_countdown(&client, &base, &pass, remaining, hook.clone()).await;
-
-            if sched.load(Ordering::SeqCst) != my_id {
+            if !perform_restart(&rest, &watch, &daily, &greet, &client, &cfg, remaining, Some((&sched, my_id)), "Auto-restart", pid_slot.clone(), &shutdown_shape).await {
                 break;
             }
-            // save (best-effort)
-            let _ = client
-                .post(format!("{}/save", v1_base(&base)))
-                .basic_auth("admin", Some(&pass))
-                .header(CONTENT_LENGTH, "0")
-                .header(CONNECTION, "close")
-                .header(ACCEPT, "*/*")
-                .header(USER_AGENT, "curl/8.13.0")
-                .send()
-                .await;
-
-            let _ = announce_multi(&client, &base, &pass, "Restarting server…").await;
-
-            if let Some(h) = hook.clone() { let _ = discord_embed(&h, "Auto-restart executing.", COLOR_INFO).await; }
-            // try various shutdown shapes
-            let bodies = [
-                serde_json::json!({ "waittime": 1, "message": "Auto restart" }),
-                serde_json::json!({ "seconds": 1,  "message": "Auto restart" }),
-                serde_json::json!({ "time": 1,     "message": "Auto restart" }),
-                serde_json::json!({ "duration": 1, "message": "Auto restart" }),
-            ];
-            let mut ok = false;
-            for b in bodies {
-                if client
-                    .post(format!("{}/shutdown", v1_base(&base)))
-                    .basic_auth("admin", Some(&pass))
-                    .json(&b)
-                    .send()
-                    .await
-                    .map(|r| r.status().is_success())
-                    .unwrap_or(false)
-                {
-                    ok = true;
-                    break;
-                }
-            }
-            if !ok {
-                // last attempt without body
-                let _ = client
-                    .post(format!("{}/shutdown", v1_base(&base)))
-                    .basic_auth("admin", Some(&pass))
-                    .header(CONTENT_LENGTH, "0")
-                    .send()
-                    .await;
-            }
-
-            // Extra robust attempt with detailed Discord logging
-            let _ = attempt_shutdown(&base, &pass, hook.clone(), "Auto restart").await;
-
-            // wait for REST to go down (max 120s) before starting new instance
-            if let Some(h) = hook.clone() { let _ = discord_embed(&h, "Waiting for server to stop (up to 120s)...", COLOR_INFO).await; }
-            let stopped = wait_for_server_down(&base, &pass, 120).await;
-            if let Some(h) = hook.clone() {
-                if stopped {
-                    let _ = discord_embed(&h, "Server appears offline. Starting new instance...", COLOR_SUCCESS).await;
-                } else {
-                    let _ = discord_embed(&h, "Server did not stop in time (120s). Starting anyway.", COLOR_ERROR).await;
-                }
-            }
-
-            if let Some(c) = &cmd {
-                if let Some(h) = hook.clone() { let _ = discord_embed(&h, &format!("Starting server via: {}", c), COLOR_INFO).await; }
-                // Start the Windows .bat / .exe
-                if c.trim().to_lowercase().ends_with(".bat") {
-                    let _ = Command::new("cmd").args(["/C", c]).spawn();
-                } else {
-                    let _ = Command::new(c).spawn();
-                }
-            }
+            status.lock().last_fired = Some(Local::now());
+        }
+        let mut st = status.lock();
+        if st.running_gen == Some(my_id) {
+            st.running_gen = None;
         }
     });
 }
@@ -1253,35 +7327,603 @@ fn spawn_scheduler(sched: Arc<AtomicUsize>, cfg: &ApiConfig) {
 
 #[tokio::main]
 async fn main() {
+    let cfg = load_saved_config().unwrap_or_default();
+    migrate_player_ids();
+    let rest_state = RestState::default();
+    rest_state.set_limit(&cfg);
+    set_http_prefs(&rest_state, &cfg);
     tauri::Builder::default()
         .manage(AppState {
-            config: Mutex::new(load_saved_config().unwrap_or_default()),
-            tracker: Mutex::new(PlayerTracker::default()),
+            config: Mutex::new(cfg),
+            tracker: Mutex::new(PlayerTracker::load()),
             sched: Arc::new(AtomicUsize::new(0)),
+            sched_status: Arc::new(Mutex::new(SchedulerStatus::default())),
             last_players: Mutex::new(HashSet::new()),
             last_names: Mutex::new(HashMap::new()),
+            last_version: Mutex::new(None),
+            badge: Arc::new(Mutex::new(None)),
+            last_map: Mutex::new(None),
+            near_capacity: AtomicBool::new(false),
+            restart_health_warned: Mutex::new(None),
             autosave_gen: Arc::new(AtomicUsize::new(0)),
+            save_sched_gen: Arc::new(AtomicUsize::new(0)),
+            motd_gen: Arc::new(AtomicUsize::new(0)),
+            settings_event_gen: Arc::new(AtomicUsize::new(0)),
+            settings_events: Arc::new(Mutex::new(load_data(SETTINGS_EVENTS_FILE).unwrap_or_default())),
             backup_gen: Arc::new(AtomicUsize::new(0)),
+            summary_gen: Arc::new(AtomicUsize::new(0)),
+            countdown_gen: Arc::new(AtomicUsize::new(0)),
+            greet_gen: Arc::new(AtomicUsize::new(0)),
+            ws_gen: Arc::new(AtomicUsize::new(0)),
+            recurring: Arc::new(Mutex::new(load_data(RECURRING_FILE).unwrap_or_default())),
+            recurring_gen: Arc::new(AtomicUsize::new(0)),
+            bans: Arc::new(Mutex::new(load_data(BANS_FILE).unwrap_or_default())),
+            ban_expiry_gen: Arc::new(AtomicUsize::new(0)),
+            watchlist: Mutex::new(load_data(WATCHLIST_FILE).unwrap_or_default()),
+            task_beats: TaskBeats::default(),
+            backup_streak: Arc::new(Mutex::new(BackupStreak::default())),
+            server_pid: Arc::new(Mutex::new(None)),
+            shutdown_shape: Arc::new(Mutex::new(None)),
+            watch: Arc::new(ServerWatch {
+                uptime: Mutex::new(load_data(UPTIME_FILE).unwrap_or_default()),
+                ..ServerWatch::default()
+            }),
+            daily: Arc::new(Mutex::new(DailyStats::default())),
+            rest: rest_state,
+        })
+        .setup(|app| {
+            spawn_config_watcher(app.handle());
+            Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             get_config,
+            get_effective_config,
+            config_health,
+            migrate_config,
+            is_configured,
+            get_storage_usage,
+            export_audit,
+            action_status,
+            check_restart_schedule,
+            validate_restart_time,
+            scheduler_debug,
+            task_health,
+            get_shutdown_shape,
+            suspend_scheduler_until,
+            resume_scheduler,
+            restart_health,
             set_config,
+            toggle_autosave,
+            toggle_backup,
             get_server_info,
+            get_all_server_info,
+            get_announce_status,
+            check_query_port,
             get_players,
+            get_raw_vs_coerced_count,
+            get_dashboard,
+            post_status_to_discord,
+            server_resource_usage,
             dump_players_json,
+            dump_info_json,
+            dump_metrics_json,
+            get_game_time,
+            preview_discord_embed,
+            test_discord_webhook,
+            test_credentials,
+            trace_connectivity,
             player_durations,
+            platform_breakdown,
+            availability,
+            test_connection,
+            get_settings_schedule,
+            set_settings_schedule,
+            list_backup_contents,
+            restore_files,
+            check_clock,
+            get_motd_schedule,
+            set_motd_schedule,
+            backup_failure_status,
+            reset_config,
+            recent_errors,
+            check_backup_space,
+            suggest_restart_times,
+            warmup,
+            current_map,
+            normalize_ids,
+            status_badge_svg,
+            save_dir_diagnostics,
+            get_editable_settings,
+            restart_self_test,
+            recent_roster,
+            change_admin_password,
+            watch_player,
+            validate_discord_webhook,
+            unwatch_player,
+            list_watchlist,
+            player_ping_history,
+            stale_players,
+            kick_stale_players,
+            player_first_seen,
+            search_players,
+            engagement_stats,
             announce_message,
             force_save,
             shutdown_server,
             cancel_restart,
+            announce_countdown,
+            cancel_countdown,
+            preview_countdown,
+            start_recurring_announce,
+            stop_recurring_announce,
+            list_recurring_announces,
+            set_announce_pool,
             kick_player,
             ban_player,
+            active_bans,
+            ban_many,
+            apply_settings,
             unban_player,
             restart_now,
-            backup_now
+            hard_restart,
+            trigger_scheduled_restart_now,
+            backup_now,
+            backup_named,
+            purge_backups,
+            detect_save_dir,
+            save_size_history
         ])
         // Devtools no longer auto-open; keep setup minimal
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn scratch_dir(tag: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("pal-{}-{}", tag, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn write_zip(path: &Path, entries: &[(&str, &[u8])]) {
+        let mut zip = zip::ZipWriter::new(File::create(path).unwrap());
+        for (name, data) in entries {
+            zip.start_file(*name, zip::write::FileOptions::default()).unwrap();
+            zip.write_all(data).unwrap();
+        }
+        zip.finish().unwrap();
+    }
+
+    #[test]
+    fn safe_extract_rejects_parent_traversal() {
+        let root = scratch_dir("zipslip");
+        let archive = root.join("evil.zip");
+        write_zip(&archive, &[("ok.txt", b"fine"), ("../../evil", b"pwned")]);
+        let dest = root.join("out").join("nested");
+
+        assert!(safe_extract(&archive, &dest, None).is_err());
+        assert!(!root.join("evil").exists());
+        // validation happens up front, so not even the benign entry is written
+        assert!(!dest.join("ok.txt").exists());
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn safe_entry_path_rejects_escapes() {
+        let dest = Path::new("saves");
+        assert!(safe_entry_path(dest, "/etc/passwd").is_none());
+        assert!(safe_entry_path(dest, "..\\..\\evil").is_none());
+        assert!(safe_entry_path(dest, "a/../../b").is_none());
+        assert!(safe_entry_path(dest, "./").is_none());
+        assert_eq!(safe_entry_path(dest, "./Level.sav"), Some(dest.join("Level.sav")));
+    }
+
+    #[test]
+    fn normalize_player_id_canonicalizes_steam_ids() {
+        assert_eq!(normalize_player_id("steam_76561198000000001"), "steam_76561198000000001");
+        assert_eq!(normalize_player_id("STEAM76561198000000001"), "steam_76561198000000001");
+        assert_eq!(normalize_player_id(" 76561198000000001 "), "steam_76561198000000001");
+        // 18 digits is not a SteamID64; leave it alone
+        assert_eq!(normalize_player_id("765611980000000012"), "765611980000000012");
+        assert_eq!(normalize_player_id("A1B2C3D4"), "A1B2C3D4");
+    }
+
+    #[test]
+    fn tracker_flags_players_without_ping_as_stale() {
+        let player = |id: &str, ping: Option<u32>, secs: i64| Player {
+            id: id.into(),
+            name: id.into(),
+            level: None,
+            ping,
+            connected_seconds: Some(secs),
+            platform: String::new(),
+        };
+        let mut tr = PlayerTracker::default();
+        // flush() is never called, so nothing is written to disk
+        for id in ["ghost", "live"] {
+            tr.first_seen.insert(id.into(), Utc::now());
+        }
+        for i in 0..STALE_POLL_THRESHOLD as i64 {
+            tr.update_with(&[player("ghost", Some(0), 60 * i), player("live", Some(40), 60 * i)]);
+        }
+        assert_eq!(tr.stale(), vec![("ghost".to_string(), STALE_POLL_THRESHOLD)]);
+        // a ping coming back clears the flag, and so does leaving the list
+        tr.update_with(&[player("ghost", Some(35), 9999)]);
+        assert!(tr.stale().is_empty());
+        assert!(!tr.silent_polls.contains_key("live"));
+    }
+
+    #[test]
+    fn weekday_schedule_overrides_daily_times() {
+        let cfg = ApiConfig {
+            restart_times: vec!["03:00".into()],
+            restart_schedule: Some(HashMap::from([("Sat".to_string(), vec!["06:00".to_string()])])),
+            ..ApiConfig::default()
+        };
+        let by_day = restart_times_by_day(&cfg);
+        // Friday 2024-06-07 12:00 -> next restart is Saturday 06:00, not 03:00 on any day
+        let now = Local.with_ymd_and_hms(2024, 6, 7, 12, 0, 0).unwrap();
+        let next = next_fire_by_day(now, |d| by_day.get(&d).map(Vec::as_slice).unwrap_or(&[])).unwrap();
+        assert_eq!(next, Local.with_ymd_and_hms(2024, 6, 8, 6, 0, 0).unwrap());
+        assert!(validate_restart_schedule(&HashMap::from([("Funday".to_string(), vec![])])).is_err());
+        assert!(validate_restart_schedule(&HashMap::from([("Mon".to_string(), vec!["25:00".to_string()])])).is_err());
+    }
+
+    #[test]
+    fn engagement_counts_new_returning_and_churned() {
+        let now = Utc::now();
+        let days = |n: i64| now - chrono::Duration::days(n);
+        let session = |id: &str, start: DateTime<Utc>, mins: i64| Session {
+            id: id.into(),
+            start,
+            end: start + chrono::Duration::minutes(mins),
+        };
+        let mut tr = PlayerTracker::default();
+        tr.first_seen.insert("old".into(), days(30));
+        tr.first_seen.insert("gone".into(), days(30));
+        tr.first_seen.insert("new".into(), days(2));
+        tr.sessions.extend([
+            session("gone", days(10), 60),
+            session("old", days(10), 60),
+            session("old", days(3), 30),
+            session("new", days(2), 90),
+        ]);
+        let stats = tr.engagement(now, chrono::Duration::days(7));
+        assert_eq!(stats.sessions, 2);
+        assert_eq!(stats.avg_session_secs, Some(60 * 60));
+        assert_eq!((stats.active_players, stats.new_players, stats.returning_players), (2, 1, 1));
+        assert_eq!(stats.churned_players, 1);
+        assert_eq!(stats.churn_rate, Some(0.5));
+    }
+
+    #[test]
+    fn coerce_players_merges_duplicate_id_forms() {
+        let v = serde_json::json!({ "players": [
+            { "name": "Bob", "playerId": "76561198000000001", "ping": 0 },
+            { "name": "Bob", "userId": "steam_76561198000000001", "level": 12, "ping": 48 },
+            { "name": "Ann", "userId": "steam_76561198000000002" },
+        ]});
+        assert_eq!(coerce_players_raw(&v, None).len(), 3);
+        let players = coerce_players(&v, None);
+        assert_eq!(players.len(), 2);
+        assert_eq!(players[0].id, "steam_76561198000000001");
+        assert_eq!((players[0].level, players[0].ping), (Some(12), Some(48)));
+    }
+
+    #[test]
+    fn quiet_window_handles_midnight_crossing() {
+        let t = |s: &str| NaiveTime::parse_from_str(s, "%H:%M").unwrap();
+        assert!(in_quiet_window(t("03:00"), t("23:00"), t("07:00")));
+        assert!(in_quiet_window(t("23:00"), t("23:00"), t("07:00")));
+        assert!(!in_quiet_window(t("07:00"), t("23:00"), t("07:00")));
+        assert!(!in_quiet_window(t("12:00"), t("23:00"), t("07:00")));
+        assert!(in_quiet_window(t("13:30"), t("13:00"), t("14:00")));
+        assert!(!in_quiet_window(t("13:00"), t("13:00"), t("13:00")));
+    }
+
+    #[test]
+    fn restart_schedule_flags_close_times_across_midnight() {
+        let v = |xs: &[&str]| xs.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert!(restart_schedule_warnings(&v(&["03:00", "15:00"])).is_empty());
+        assert_eq!(restart_schedule_warnings(&v(&["03:00", "03:02", "15:00"])).len(), 1);
+        let w = restart_schedule_warnings(&v(&["23:59", "00:01", "12:00"]));
+        assert_eq!(w.len(), 1);
+        assert!(w[0].starts_with("23:59 and 00:01"));
+    }
+
+    #[test]
+    fn backup_template_names_match_prune_pattern() {
+        let now = Local.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap();
+        let name = render_backup_name("{server}-{date}-{time}-p{players}", now, "My Server!", 7);
+        assert_eq!(name, "My_Server_-20240102-030405-p7.zip");
+        let re = backup_name_regex("{server}-{date}-{time}-p{players}");
+        assert!(re.is_match(&name));
+        assert!(!re.is_match("before-mod-update.zip"));
+        // the default template keeps the historical names
+        assert!(backup_name_regex(DEFAULT_BACKUP_NAME_TEMPLATE).is_match("backup-20240101-030000.zip"));
+        assert!(validate_backup_template("{server}-{date}").is_err());
+        assert!(validate_backup_template("../{date}-{time}").is_err());
+        assert!(validate_backup_template("{date}-{time}-{bogus}").is_err());
+        assert!(validate_backup_template("crash-{date}-{time}").is_err());
+        // a {server} value can't make pruning reach the safety archives
+        let re = backup_name_regex("{server}-{date}-{time}");
+        assert!(is_template_backup("palworld-20240101-030000.zip", &re));
+        assert!(!is_template_backup("crash-20240101-030000.zip", &re));
+    }
+
+    #[test]
+    fn start_invocation_picks_the_shell() {
+        assert_eq!(start_invocation(r"C:\srv\start.bat", None).0, "cmd");
+        let (prog, args) = start_invocation(r"C:\srv\start.ps1", None);
+        assert_eq!(prog, "powershell");
+        assert_eq!(args.last().map(String::as_str), Some(r"C:\srv\start.ps1"));
+        assert_eq!(start_invocation("PalServer.exe", None), ("PalServer.exe".to_string(), vec![]));
+        let (prog, args) = start_invocation("run.sh", Some(r#"wsl "bash" -lc {cmd}"#));
+        assert_eq!((prog.as_str(), args), ("wsl", vec!["bash".to_string(), "-lc".into(), "run.sh".into()]));
+        assert_eq!(start_invocation("run.sh", Some("bash")).1, vec!["run.sh".to_string()]);
+    }
+
+    #[test]
+    fn safe_extract_writes_nested_entries() {
+        let root = scratch_dir("extract");
+        let archive = root.join("good.zip");
+        write_zip(&archive, &[("Level.sav", b"level"), ("Players/0001.sav", b"player")]);
+        let dest = root.join("out");
+
+        assert_eq!(safe_extract(&archive, &dest, None).unwrap(), 2);
+        assert_eq!(std::fs::read(dest.join("Players").join("0001.sav")).unwrap(), b"player");
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn webhook_check_rejects_channel_links() {
+        let hook = "https://discord.com/api/webhooks/123456/abc-DEF";
+        assert_eq!(check_discord_webhook(hook, false).unwrap(), hook);
+        assert!(check_discord_webhook("https://canary.discordapp.com/api/webhooks/1/t", false).is_ok());
+        assert!(check_discord_webhook("https://discord.com/api/v10/webhooks/123456/abc", false).is_ok());
+        assert!(check_discord_webhook("https://discord.com/api/vx/webhooks/123456/abc", false).is_err());
+        let err = check_discord_webhook("https://discord.com/channels/111/222", false).unwrap_err();
+        assert!(err.contains("channel link"));
+        assert!(check_discord_webhook("https://discord.com/api/webhooks/abc/t", false).is_err());
+        assert!(check_discord_webhook("http://discord.com/api/webhooks/1/t", false).is_err());
+        assert!(check_discord_webhook("https://hooks.example.net/relay", false).is_err());
+        assert!(check_discord_webhook("https://hooks.example.net/relay", true).is_ok());
+    }
+
+    #[test]
+    fn availability_skips_unmonitored_gaps() {
+        let now = Utc::now();
+        let at = |h: i64| now - chrono::Duration::hours(h);
+        let ev = |h: i64, up: bool, first: bool, seen: i64| UpDownEvent { at: at(h), up, first, confirmed: Some(at(seen)) };
+        // up, down from -10h to -8h, up until the app closed at -4h, up again from -2h
+        let log = vec![ev(20, true, true, 10), ev(10, false, false, 8), ev(8, true, false, 4), ev(2, true, true, 0)];
+        let r = availability_over(&log, now, 12);
+        assert_eq!(r.up_secs, 8 * 3600);
+        assert_eq!(r.down_secs, 2 * 3600);
+        assert_eq!(r.unmonitored_secs, 2 * 3600);
+        assert_eq!((r.outages, r.longest_outage_secs), (1, 2 * 3600));
+        assert_eq!(r.uptime_percent, Some(80.0));
+        assert_eq!(availability_over(&[], now, 12).uptime_percent, None);
+    }
+
+    #[test]
+    fn save_dir_diagnostics_flags_stale_and_missing_saves() {
+        let root = scratch_dir("savediag");
+        std::fs::write(root.join("Level.sav"), b"level").unwrap();
+        let d = diagnose_save_dir(&root, Utc::now());
+        assert_eq!((d.file_count, d.total_bytes), (1, 5));
+        assert_eq!(d.warnings.len(), 1, "{:?}", d.warnings);
+        assert!(d.warnings[0].contains("Players"));
+
+        std::fs::create_dir_all(root.join("Players")).unwrap();
+        std::fs::write(root.join("Players").join("0001.sav"), b"p").unwrap();
+        let later = diagnose_save_dir(&root, Utc::now() + chrono::Duration::hours(4));
+        assert_eq!(later.warnings.len(), 1, "{:?}", later.warnings);
+        assert!(later.warnings[0].contains("may not be saving"));
+        assert!(!diagnose_save_dir(&root.join("missing"), Utc::now()).exists);
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn normalize_id_list_dedupes_mixed_formats() {
+        let raw = "steam_76561198000000001, 76561198000000001\n\"STEAM76561198000000002\"; gdk_2535412345 banned\tAB12CD34AB12CD34AB12CD34AB12CD34";
+        let got = normalize_id_list(raw);
+        assert_eq!(
+            got.ids,
+            vec!["steam_76561198000000001", "steam_76561198000000002", "gdk_2535412345", "AB12CD34AB12CD34AB12CD34AB12CD34"]
+        );
+        assert_eq!(got.unparseable, vec!["banned"]);
+    }
+
+    #[test]
+    fn quiet_slots_follow_the_emptiest_hours() {
+        let day = Utc.with_ymd_and_hms(2024, 3, 4, 0, 0, 0).unwrap();
+        let at = |h: i64, m: i64| day + chrono::Duration::minutes(h * 60 + m);
+        // busy all day except 04:00-05:00 and 16:00-16:30
+        let spans = vec![(at(0, 0), at(4, 0)), (at(5, 0), at(16, 0)), (at(16, 30), at(24, 0)), (at(18, 0), at(20, 0))];
+        let occ = occupancy_by_slot(&spans, &Utc);
+        assert_eq!(occ[0], 900);
+        assert_eq!(occ[16], 0);
+        assert_eq!(occ[72], 1800);
+        let picks: Vec<String> = quietest_slots(&occ, 2).iter().map(|t| t.format("%H:%M").to_string()).collect();
+        assert_eq!(picks, vec!["04:00", "16:00"]);
+    }
+
+    #[test]
+    fn safe_extract_can_restore_selected_entries() {
+        let root = scratch_dir("partial");
+        let archive = root.join("backup.zip");
+        write_zip(&archive, &[("Level.sav", b"level"), ("Players/0001.sav", b"player")]);
+        let dest = root.join("out");
+        let only: HashSet<String> = ["Players/0001.sav".to_string()].into_iter().collect();
+
+        assert_eq!(safe_extract(&archive, &dest, Some(&only)).unwrap(), 1);
+        assert!(dest.join("Players").join("0001.sav").exists());
+        assert!(!dest.join("Level.sav").exists());
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn settings_event_windows_wrap_the_week() {
+        let ev = |start: &str, end: &str| SettingsEvent {
+            name: "2x XP".into(),
+            start: start.into(),
+            end: end.into(),
+            changes: HashMap::from([("ExpRate".to_string(), Value::from(2.0))]),
+        };
+        let (win, period) = validate_settings_event(&ev("Fri 18:00", "Sun 23:59")).unwrap();
+        assert_eq!(period, 7 * 86_400);
+        let at = |s: &str| event_boundary(s).unwrap().0;
+        assert!(event_active_at(at("Sat 12:00"), win));
+        assert!(!event_active_at(at("Mon 12:00"), win));
+        // a window crossing the end of the week
+        let (wrap, _) = validate_settings_event(&ev("Sun 20:00", "Mon 02:00")).unwrap();
+        assert!(event_active_at(at("Mon 01:00"), wrap));
+        assert!(!event_active_at(at("Mon 02:00"), wrap));
+        assert!(validate_settings_event(&ev("Fri 18:00", "23:00")).is_err());
+        assert!(validate_settings_event(&ev("Someday 18:00", "Sun 23:00")).is_err());
+    }
+
+    #[test]
+    fn count_prune_keeps_the_newest_archives() {
+        let root = scratch_dir("count-prune");
+        let pattern = backup_name_regex(DEFAULT_BACKUP_NAME_TEMPLATE);
+        let base = std::time::SystemTime::now() - Duration::from_secs(3600);
+        for i in 0..5u64 {
+            let path = root.join(format!("backup-2024010{}-000000.zip", i + 1));
+            let f = std::fs::File::create(&path).unwrap();
+            f.set_modified(base + Duration::from_secs(i * 60)).unwrap();
+        }
+        std::fs::write(root.join("notes.txt"), b"keep me").unwrap();
+
+        assert_eq!(prune_backups_by_count(&root, 2, &pattern), 3);
+        let left: Vec<_> = template_backups(&root, &pattern).into_iter().map(|(p, _, _)| p).collect();
+        assert_eq!(left, vec![root.join("backup-20240104-000000.zip"), root.join("backup-20240105-000000.zip")]);
+        assert!(root.join("notes.txt").exists());
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn connection_errors_match_on_the_status_not_the_url() {
+        let err = |status| anyhow::Error::from(HttpStatusError { method: "GET", url: "http://10.0.0.5:24010/v1/api/info".into(), status });
+        assert!(connection_error_message(&err(reqwest::StatusCode::NOT_FOUND)).starts_with("the server answered with an error"));
+        assert!(connection_error_message(&err(reqwest::StatusCode::UNAUTHORIZED)).starts_with("unauthorized (401)"));
+        let timeout = anyhow::Error::from(TimedOut { url: "http://10.0.0.5:8212/v1/api/info".into(), secs: 10 });
+        assert_eq!(error_kind(&timeout), "timeout");
+    }
+
+    #[test]
+    fn sealed_secrets_round_trip() {
+        let key = || ring::aead::LessSafeKey::new(ring::aead::UnboundKey::new(&ring::aead::AES_256_GCM, &[7u8; 32]).unwrap());
+        let sealed = seal_secret(&key(), "hunter2").unwrap();
+        assert!(sealed.starts_with(SECRET_PREFIX) && !sealed.contains("hunter2"));
+        assert_eq!(open_secret(&key(), &sealed).as_deref(), Some("hunter2"));
+        let other = ring::aead::LessSafeKey::new(ring::aead::UnboundKey::new(&ring::aead::AES_256_GCM, &[8u8; 32]).unwrap());
+        assert_eq!(open_secret(&other, &sealed), None);
+    }
+
+    #[test]
+    fn profile_passwords_are_sealed_with_the_config() {
+        let key = ring::aead::LessSafeKey::new(ring::aead::UnboundKey::new(&ring::aead::AES_256_GCM, &[7u8; 32]).unwrap());
+        let mut v = serde_json::json!({
+            "password": "hunter2",
+            "profiles": [{ "name": "eu", "base_url": "http://eu", "password": "swordfish" }],
+        });
+        seal_config_secrets(&mut v, &key);
+        assert!(!v.to_string().contains("hunter2") && !v.to_string().contains("swordfish"));
+        // sealing twice (a load that couldn't read the key, then a save) doesn't wrap it again
+        let once = v.clone();
+        seal_config_secrets(&mut v, &key);
+        assert_eq!(v, once);
+        open_config_secrets(&mut v, &key);
+        assert_eq!(v["password"], "hunter2");
+        assert_eq!(v["profiles"][0]["password"], "swordfish");
+    }
+
+    #[test]
+    fn settings_events_sharing_a_key_may_not_overlap() {
+        let ev = |name: &str, start: &str, end: &str, key: &str| SettingsEvent {
+            name: name.into(),
+            start: start.into(),
+            end: end.into(),
+            changes: HashMap::from([(key.to_string(), Value::from(2.0))]),
+        };
+        let weekend = ev("weekend", "Fri 18:00", "Sun 23:59", "ExpRate");
+        assert!(settings_events_overlap(&weekend, &ev("nightly", "20:00", "22:00", "ExpRate")).is_some());
+        assert!(settings_events_overlap(&weekend, &ev("nightly", "20:00", "22:00", "PalCaptureRate")).is_none());
+        assert!(settings_events_overlap(&weekend, &ev("midweek", "Tue 18:00", "Wed 18:00", "ExpRate")).is_none());
+    }
+
+    #[test]
+    fn repeated_settings_event_failures_are_reported_once() {
+        let mut reported = ReportedFailures::new();
+        assert!(first_report(&mut reported, "weekend", true, "failed to set ExpRate"));
+        assert!(!first_report(&mut reported, "weekend", true, "failed to set ExpRate"));
+        assert!(first_report(&mut reported, "weekend", false, "failed to set ExpRate"));
+        assert!(first_report(&mut reported, "weekend", true, "failed to set ExpRate, PalCaptureRate"));
+    }
+
+    #[test]
+    fn prefixed_prune_only_touches_its_own_archives() {
+        let root = scratch_dir("prefixed-prune");
+        for name in ["crash-20240101-000000.zip", "crash-20240102-000000.zip", "crash-20240103-000000.zip", "pre-restart-20240101-000000.zip"] {
+            std::fs::write(root.join(name), b"").unwrap();
+        }
+        assert_eq!(prune_prefixed_backups(&root, "crash-", 2), 1);
+        assert!(!root.join("crash-20240101-000000.zip").exists());
+        assert!(root.join("crash-20240103-000000.zip").exists());
+        assert!(root.join("pre-restart-20240101-000000.zip").exists());
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn temp_bans_count_down_and_permanent_bans_do_not() {
+        let now = Utc::now();
+        let ban = |expires_at| BanRecord { id: "steam_1".into(), name: None, reason: None, banned_at: now, expires_at };
+        assert_eq!(ban(None).remaining_secs(now), None);
+        assert_eq!(ban(Some(now + chrono::Duration::minutes(5))).remaining_secs(now), Some(300));
+        assert_eq!(ban(Some(now - chrono::Duration::minutes(5))).remaining_secs(now), Some(0));
+        // records written before temp bans existed load as permanent
+        let old: BanRecord = serde_json::from_str(r#"{"id":"steam_1","name":null,"reason":null,"banned_at":"2024-01-01T00:00:00Z"}"#).unwrap();
+        assert_eq!(old.expires_at, None);
+    }
+
+    #[test]
+    fn stored_ids_are_rekeyed_and_merged() {
+        let old = HashMap::from([
+            ("76561198000000001".to_string(), 3),
+            ("steam_76561198000000001".to_string(), 5),
+            ("gdk_42".to_string(), 1),
+        ]);
+        let (map, changed) = normalize_id_keys(old, |a, b| a.max(b));
+        assert!(changed);
+        assert_eq!(map, HashMap::from([("steam_76561198000000001".to_string(), 5), ("gdk_42".to_string(), 1)]));
+        let (_, changed) = normalize_id_keys(map, |a, _| a);
+        assert!(!changed);
+    }
+
+    #[test]
+    fn a2s_replies_are_info_or_challenge_packets() {
+        assert!(is_a2s_reply(b"\xFF\xFF\xFF\xFFI\x11Palworld"));
+        assert!(is_a2s_reply(b"\xFF\xFF\xFF\xFFA\x01\x02\x03\x04"));
+        assert!(!is_a2s_reply(b"\xFF\xFF\xFF\xFF"));
+        assert!(!is_a2s_reply(b"HTTP/1.1 400 Bad Request"));
+    }
+
+    #[test]
+    fn segments_of_different_files_sort_by_age() {
+        let mut files = [
+            PathBuf::from("sessions-20240301-080000.jsonl.gz"),
+            PathBuf::from("audit-20240502-120000.jsonl.gz"),
+            PathBuf::from("save_sizes-20240101-000000.jsonl.gz"),
+        ];
+        files.sort_by_key(|p| segment_stamp(p));
+        assert_eq!(files[0], PathBuf::from("save_sizes-20240101-000000.jsonl.gz"));
+        assert_eq!(files[2], PathBuf::from("audit-20240502-120000.jsonl.gz"));
+    }
+}
@@ -92,23 +92,16 @@ export default function Dashboard() {
           // One-time auto-apply on start using the fully loaded values to avoid overwriting
           const payload: any = {
             base_url,
-            baseUrl: base_url,
             password: password || null,
             restart_times: c.restart_times ?? [],
-            restartTimes: c.restart_times ?? [],
             start_cmd: c.start_cmd || null,
-            startCmd: c.start_cmd || null,
             backup_dir: c.backup_dir || null,
-            backupDir: c.backup_dir || null,
             backup_dest_dir: (c as any).backup_dest_dir || null,
-            backupDestDir: (c as any).backup_dest_dir || null,
             discord_webhook: (c as any).discord_webhook || null,
-            discordWebhook: (c as any).discord_webhook || null,
             allow_actions: (c as any).allow_actions ?? true,
-            allowActions: (c as any).allow_actions ?? true,
           };
           try {
-            await invoke("set_config", payload);
+            await invoke("set_config", { patch: payload });
             lastAppliedRef.current = `${normalizeBaseUrl(base_url)}|${password}|${(c.restart_times??[]).join(",")}|${c.start_cmd||""}|${c.backup_dir||""}|${(c as any).backup_dest_dir||""}|${(c as any).discord_webhook||""}`;
             pushLog("Settings applied from disk");
           } catch (e: any) {
@@ -185,28 +178,21 @@ export default function Dashboard() {
     try {
       const base = normalizeBaseUrl((next.base_url || "").trim());
       const restart_times = parseRestartTimes(restartTimesText);
-      // Send both snake_case and camelCase for maximum compatibility with older binaries
       const payload: any = {
         base_url: base,
-        baseUrl: base,
         password: next.password || null,
         restart_times,
-        restartTimes: restart_times,
         start_cmd: startCmd || null,
-        startCmd: startCmd || null,
         backup_dir: backupDir || null,
-        backupDir: backupDir || null,
         backup_dest_dir: backupDestDir || null,
-        backupDestDir: backupDestDir || null,
         discord_webhook: (discordHook || null),
-        discordWebhook: (discordHook || null),
       };
       try {
         // helpful debug entry to verify the active bundle
         const keys = Object.keys(payload).join(", ");
         pushLog(`set_config payload keys -> ${keys}`);
       } catch {}
-      await invoke("set_config", payload);
+      await invoke("set_config", { patch: payload });
       setCfg({ ...next, base_url: base, restart_times });
       setRestartTimesText(restart_times.join(", "));
       pushLog(`Config applied. Base: ${base}${restart_times.length ? ` | restarts: ${restart_times.join(",")}` : ""}`);